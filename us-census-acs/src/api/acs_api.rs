@@ -1,134 +1,305 @@
-use crate::model::{acs_api_query_params::AcsApiQueryParams, acs_geoid_query::DeserializeGeoidFn};
-use futures::future;
+use crate::model::acs_api_query_params::{group_name, AcsApiQueryParams};
+use crate::model::acs_client_config::AcsClientConfig;
+use crate::model::acs_geoid_query::DeserializeGeoidFn;
+use futures::{stream, StreamExt};
 use itertools::Itertools;
 use kdam::BarExt;
 use reqwest::{Client, StatusCode};
+use std::collections::{HashMap, HashSet};
+use std::future::Future;
 use std::sync::{Arc, Mutex};
+use us_census_core::error::CensusError;
+use us_census_core::model::acs::acs_estimate::pair_margins_of_error;
+use us_census_core::model::acs::acs_jam_value::pair_annotations;
 use us_census_core::model::acs::acs_value::AcsValue;
-use us_census_core::model::identifier::geoid::Geoid;
+use us_census_core::model::acs::{AcsPredicateType, AcsVariableValue};
+use us_census_core::model::identifier::{geoid::Geoid, GeographyVintage};
 
-/// sets up a run of ACS queries.
-pub async fn batch_run<'a>(
+/// rows of the successful queries of a batch, along with the failures of the others.
+pub type AcsBatchResult = (Vec<(Geoid, Vec<AcsValue>)>, Vec<CensusError>);
+
+/// sets up a run of ACS queries. at most `config.concurrency` queries run at once and
+/// failed requests are retried as configured. queries that still fail are reported
+/// alongside the rows of the other queries instead of failing the batch.
+pub async fn batch_run(
     client: &Client,
     queries: Vec<AcsApiQueryParams>,
-) -> Result<Vec<(Geoid, Vec<AcsValue>)>, String> {
+    config: &AcsClientConfig,
+) -> Result<AcsBatchResult, CensusError> {
     let pb_builder = kdam::BarBuilder::default()
         .total(queries.len())
         .desc("ACS API calls");
-    let pb = Arc::new(Mutex::new(pb_builder.build()?));
+    let pb = Arc::new(Mutex::new(pb_builder.build().map_err(|e| {
+        CensusError::Internal(format!("error building progress bar: {}", e))
+    })?));
 
     let response = queries.into_iter().map(|params| {
         let pb = pb.clone();
         async move {
             let desc = params.build_url()?;
-            let res = run(client, params).await;
+            let res = run(client, params, config).await;
 
             // update progress bar
-            let mut pb_update = pb
-                .lock()
-                .map_err(|e| format!("failure aquiring progress bar mutex lock: {}", e))?;
+            let mut pb_update = pb.lock().map_err(|e| {
+                CensusError::Internal(format!("failure aquiring progress bar mutex lock: {}", e))
+            })?;
             pb_update
                 .update(1)
-                .map_err(|e| format!("failure on pb update: {}", e))?;
+                .map_err(|e| CensusError::Internal(format!("failure on pb update: {}", e)))?;
 
             pb_update.set_description(&desc);
 
-            res
+            Ok(res)
         }
     });
-    let result = future::join_all(response)
-        .await
-        .into_iter()
-        .collect::<Result<Vec<_>, _>>()?
-        .into_iter()
-        .flatten()
-        .collect_vec();
+    let responses: Vec<Result<_, CensusError>> = stream::iter(response)
+        .buffered(config.concurrency.max(1))
+        .collect()
+        .await;
+
+    let mut rows = vec![];
+    let mut errors = vec![];
+    for response in responses.into_iter() {
+        match response? {
+            Ok(query_rows) => rows.extend(query_rows),
+            Err(e) => errors.push(e),
+        }
+    }
 
     eprintln!(); // terminate progress bar
+    Ok((rows, errors))
+}
+
+/// sets up a run of an ACS query. queries with more variables than the API accepts, or
+/// with `group(...)` entries, are split into chunks that are requested in turn, and the
+/// chunk responses are merged by Geoid.
+/// estimates are paired with their margins of error and the text of their annotation
+/// variables.
+pub async fn run(
+    client: &Client,
+    query: AcsApiQueryParams,
+    config: &AcsClientConfig,
+) -> Result<Vec<(Geoid, Vec<AcsValue>)>, CensusError> {
+    let mut responses = vec![];
+    for chunk in query.chunks().into_iter() {
+        responses.push(run_chunk_with_retry(client, chunk, config).await?);
+    }
+    let result = merge_responses(responses)
+        .into_iter()
+        .map(|(geoid, values)| (geoid, pair_margins_of_error(pair_annotations(values))))
+        .collect_vec();
     Ok(result)
 }
 
-/// sets up a run of an ACS query.
+/// merges the responses of the chunks of a query by Geoid, keeping the order of the
+/// first response. a column returned by more than one chunk, such as `NAME` from a
+/// `group(...)` query, is kept once.
 ///
-/// todo: this is faster than not parallel but we could probably do better if we
-/// remove the awaits and let the coroutines do the work.
-pub async fn run(
+/// # Examples
+///
+/// ```rust
+/// use us_census_acs::api::acs_api::merge_responses;
+/// use us_census_core::model::acs::AcsValue;
+/// use us_census_core::model::identifier::geoid::Geoid;
+/// use serde_json::json;
+///
+/// let co: Geoid = "08".parse().unwrap();
+/// let wy: Geoid = "56".parse().unwrap();
+/// let value = |name: &str, v: &str| AcsValue::new(String::from(name), json![v]);
+/// let first = vec![
+///     (co.clone(), vec![value("NAME", "Colorado"), value("B01001_001E", "5770790")]),
+///     (wy.clone(), vec![value("NAME", "Wyoming"), value("B01001_001E", "577929")]),
+/// ];
+/// let second = vec![
+///     (wy.clone(), vec![value("NAME", "Wyoming"), value("B01001_002E", "294912")]),
+///     (co.clone(), vec![value("NAME", "Colorado"), value("B01001_002E", "2903157")]),
+/// ];
+/// let merged = merge_responses(vec![first, second]);
+/// assert_eq!(merged.len(), 2);
+/// assert_eq!(merged[0].0, co);
+/// let names = merged[0].1.iter().map(|v| v.name.as_str()).collect::<Vec<_>>();
+/// assert_eq!(names, vec!["NAME", "B01001_001E", "B01001_002E"]);
+/// ```
+pub fn merge_responses(responses: Vec<Vec<(Geoid, Vec<AcsValue>)>>) -> Vec<(Geoid, Vec<AcsValue>)> {
+    let mut order: Vec<Geoid> = vec![];
+    let mut merged: HashMap<Geoid, (HashSet<String>, Vec<AcsValue>)> = HashMap::new();
+    for (geoid, values) in responses.into_iter().flatten() {
+        let (names, acc) = merged.entry(geoid.clone()).or_insert_with(|| {
+            order.push(geoid);
+            (HashSet::new(), vec![])
+        });
+        for value in values.into_iter() {
+            if names.insert(value.name.clone()) {
+                acc.push(value);
+            }
+        }
+    }
+    order
+        .into_iter()
+        .filter_map(|geoid| {
+            let (_, values) = merged.remove(&geoid)?;
+            Some((geoid, values))
+        })
+        .collect_vec()
+}
+
+/// runs a single request of an ACS query, retrying retryable failures with an
+/// exponential backoff.
+async fn run_chunk_with_retry(
     client: &Client,
     query: AcsApiQueryParams,
-) -> Result<Vec<(Geoid, Vec<AcsValue>)>, String> {
+    config: &AcsClientConfig,
+) -> Result<Vec<(Geoid, Vec<AcsValue>)>, CensusError> {
+    with_retry(config, || run_chunk(client, query.clone())).await
+}
+
+/// runs a request, retrying retryable failures as configured with an exponential backoff.
+pub(crate) async fn with_retry<T, F, Fut>(
+    config: &AcsClientConfig,
+    mut request: F,
+) -> Result<T, CensusError>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T, CensusError>>,
+{
+    let mut attempt = 0;
+    loop {
+        match request().await {
+            Err(e) if e.is_retryable() && attempt < config.max_retries => {
+                let wait = config.backoff(attempt);
+                log::warn!("{}, retrying in {:?}", e, wait);
+                tokio::time::sleep(wait).await;
+                attempt += 1;
+            }
+            result => return result,
+        }
+    }
+}
+
+/// runs a single request of an ACS query.
+async fn run_chunk(
+    client: &Client,
+    query: AcsApiQueryParams,
+) -> Result<Vec<(Geoid, Vec<AcsValue>)>, CensusError> {
     let url = query.build_url()?;
 
     let response = client
         .get(&url)
         .send()
         .await
-        .map_err(|e| format!("failure calling {}: {}", url, e))?;
+        .map_err(|e| CensusError::Http {
+            url: url.clone(),
+            source: Box::new(e),
+        })?;
     let status_code = response.status();
     match response.error_for_status() {
-        Err(e) => Err(format!(
-            "API request failed with error code {}. error: {}",
-            status_code, e
-        )),
-        Ok(r) if r.status() == StatusCode::NO_CONTENT => {
-            Err(format!("requested URL {} has no content", url))
-        }
+        Err(_) => Err(CensusError::HttpStatus {
+            url,
+            status: status_code.as_u16(),
+        }),
+        Ok(r) if r.status() == StatusCode::NO_CONTENT => Err(CensusError::ResponseSchema(format!(
+            "requested URL {} has no content",
+            url
+        ))),
         Ok(res) => {
-            let json = res
-                .json::<serde_json::Value>()
-                .await
-                .map_err(|e| format!("failure parsing JSON for response from {}: {}", url, e))?;
+            let json = res.json::<serde_json::Value>().await.map_err(|e| {
+                CensusError::ResponseSchema(format!(
+                    "failure parsing JSON for response from {}: {}",
+                    url, e
+                ))
+            })?;
 
             // confirm the correct column names in the response arrays before deserializing
-            validate_header(&query, &json)?;
+            let get_cols = validate_header(&query, &json)?;
 
             let deserialize_fn = query.for_query.build_deserialize_geoid_fn();
             let n_for_cols = query.for_query.response_column_count();
+            let vintage = Some(GeographyVintage::from_year(query.year));
 
             let result = json
                 .as_array()
-                .ok_or_else(|| String::from("JSON response root must be array"))?
+                .ok_or_else(|| {
+                    CensusError::ResponseSchema(String::from("JSON response root must be array"))
+                })?
                 .iter()
                 .skip(1) // skip the header!
                 .map(move |row| {
-                    deserialize(row, &query.get_query, n_for_cols, deserialize_fn.clone())
+                    let (geoid, values) = deserialize(
+                        row,
+                        &get_cols,
+                        n_for_cols,
+                        deserialize_fn.clone(),
+                        &query.predicate_types,
+                    )?;
+                    let tagged = values.into_iter().map(|v| {
+                        let measure = query.measures.get(&v.name).copied();
+                        let statistic = query.statistics.get(&v.name).copied();
+                        v.with_vintage(vintage)
+                            .with_measure(measure)
+                            .with_statistic(statistic)
+                    });
+                    Ok((geoid, tagged.collect()))
                 })
-                .collect::<Result<Vec<_>, String>>()?;
+                .collect::<Result<Vec<_>, CensusError>>()?;
 
             Ok(result)
         }
     }
 }
 
-fn validate_header(query: &AcsApiQueryParams, response: &serde_json::Value) -> Result<(), String> {
-    let expected = query.column_names();
-
+/// confirms the header of a response matches the query and returns the names of its
+/// `get` columns. a query with `group(...)` entries returns the columns of its groups,
+/// so only its requested variables and geography columns are confirmed.
+fn validate_header(
+    query: &AcsApiQueryParams,
+    response: &serde_json::Value,
+) -> Result<Vec<String>, CensusError> {
     let header_json_opt = response
         .as_array()
         .and_then(|outer| outer.first())
         .and_then(|header| header.as_array());
     let header = match header_json_opt {
-        None => Err(String::from("malformed ACS header")),
+        None => Err(CensusError::ResponseSchema(String::from(
+            "malformed ACS header",
+        ))),
         Some(h) => h
             .iter()
             .map(|v| {
-                v.as_str()
-                    .ok_or(format!("contents of header not a string: {}", v))
+                v.as_str().ok_or_else(|| {
+                    CensusError::ResponseSchema(format!("contents of header not a string: {}", v))
+                })
             })
-            .collect::<Result<Vec<_>, String>>(),
+            .collect::<Result<Vec<_>, CensusError>>(),
     }?;
 
-    for (exp, found) in expected.iter().zip(&header) {
-        if exp != found {
-            let exp_str = expected.iter().join(",");
-            let fnd_str = header.iter().join(",");
-            return Err(format!(
-                "expected headers did not match found\nexpected: {}\nfound: {}",
-                exp_str, fnd_str
-            ));
+    let mismatch = |expected: &[String]| {
+        CensusError::ResponseSchema(format!(
+            "expected headers did not match found\nexpected: {}\nfound: {}",
+            expected.iter().join(","),
+            header.iter().join(",")
+        ))
+    };
+
+    if !query.has_groups() {
+        let expected = query.column_names();
+        if expected.iter().zip(&header).any(|(e, f)| e != f) {
+            return Err(mismatch(&expected));
         }
+        return Ok(query.get_query.clone());
     }
 
-    Ok(())
+    let for_cols = query.for_query.response_column_names();
+    let n_get_cols = header.len().saturating_sub(for_cols.len());
+    let (get_header, for_header) = header.split_at(n_get_cols);
+    let missing_variable = query
+        .get_query
+        .iter()
+        .filter(|v| group_name(v).is_none())
+        .any(|v| !get_header.contains(&v.as_str()));
+    if missing_variable || for_cols.iter().zip(for_header).any(|(e, f)| e != f) {
+        return Err(mismatch(&query.column_names()));
+    }
+    Ok(get_header.iter().map(|c| String::from(*c)).collect_vec())
 }
 
 /// deserializes a row of JSON values returned from an ACS response.
@@ -146,7 +317,8 @@ fn validate_header(query: &AcsApiQueryParams, response: &serde_json::Value) -> R
 /// for a given row, this function will
 ///   1. turn all 'for' columns into a single Geoid instance (via the deserialize_fn)
 ///   2. for each 'get' column, create an AcsValue which pairs the get_column_name with
-///      the corresponding get_value.
+///      the corresponding get_value, parsed following the predicate type of the column.
+///      the types of columns without a predicate type are inferred.
 ///
 /// # Examples
 ///
@@ -155,8 +327,10 @@ fn validate_header(query: &AcsApiQueryParams, response: &serde_json::Value) -> R
 /// for 2020.
 ///
 /// ```rust
+/// use std::collections::HashMap;
 /// use us_census_acs::api::acs_api::deserialize;
 /// use us_census_acs::model::acs_geoid_query::AcsGeoidQuery;
+/// use us_census_core::model::acs::{AcsPredicateType, AcsVariableValue};
 /// use us_census_core::model::identifier::fips;
 /// use us_census_core::model::identifier::geoid::Geoid;
 ///
@@ -175,8 +349,13 @@ fn validate_header(query: &AcsApiQueryParams, response: &serde_json::Value) -> R
 /// let deserialize_fn = query.build_deserialize_geoid_fn();
 /// let get_cols = vec![String::from("NAME"),String::from("B01001_001E")];
 /// let n_for_cols: usize = query.response_column_count();
-/// let (geoid, acs_values) = deserialize(&row, &get_cols, n_for_cols, deserialize_fn.clone()).unwrap();
-/// assert_eq!(geoid, Geoid::CountySubdivision(fips::State(48), fips::County(13), fips::CountySubdivision(90595)))
+/// let types = HashMap::from([
+///     (String::from("NAME"), AcsPredicateType::String),
+///     (String::from("B01001_001E"), AcsPredicateType::Int),
+/// ]);
+/// let (geoid, acs_values) = deserialize(&row, &get_cols, n_for_cols, deserialize_fn.clone(), &types).unwrap();
+/// assert_eq!(geoid, Geoid::CountySubdivision(fips::State(48), fips::County(13), fips::CountySubdivision(90595)));
+/// assert_eq!(acs_values[1].value, AcsVariableValue::Integer(438));
 ///
 /// ```
 ///
@@ -185,18 +364,19 @@ pub fn deserialize(
     get_cols: &[String],
     n_for_cols: usize,
     deserialize_fn: DeserializeGeoidFn,
-) -> Result<(Geoid, Vec<AcsValue>), String> {
+    predicate_types: &HashMap<String, AcsPredicateType>,
+) -> Result<(Geoid, Vec<AcsValue>), CensusError> {
     let n_get_cols = get_cols.len();
-    let values = row
-        .as_array()
-        .ok_or_else(|| format!("row should be an array, found: {}", row))?;
+    let values = row.as_array().ok_or_else(|| {
+        CensusError::ResponseSchema(format!("row should be an array, found: {}", row))
+    })?;
     let expected_len = n_get_cols + n_for_cols;
     if values.len() < expected_len {
-        return Err(format!(
+        return Err(CensusError::ResponseSchema(format!(
             "row should have length {}, found {}",
             expected_len,
             values.len()
-        ));
+        )));
     }
 
     // grab geoid from row
@@ -207,11 +387,11 @@ pub fn deserialize(
     let mut acs_values: Vec<AcsValue> = vec![];
     for idx in 0..n_get_cols {
         let name = &get_cols[idx];
-        let value = values[idx].clone();
-        let row = AcsValue {
-            name: String::from(name),
-            value,
-        };
+        let predicate_type = predicate_types.get(name).copied();
+        let value = AcsVariableValue::parse(&values[idx], predicate_type).map_err(|e| {
+            CensusError::ResponseSchema(format!("failure parsing value of {}: {}", name, e))
+        })?;
+        let row = AcsValue::new(String::from(name), value);
         acs_values.push(row);
     }
     Ok((geoid, acs_values))
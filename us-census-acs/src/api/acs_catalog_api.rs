@@ -0,0 +1,117 @@
+use super::acs_api::with_retry;
+use crate::model::{
+    acs_api_query_params::AcsApiQueryParams, acs_catalog::AcsCatalog,
+    acs_client_config::AcsClientConfig,
+};
+use reqwest::Client;
+use std::path::Path;
+use us_census_core::error::CensusError;
+use us_census_core::model::dataset::CensusDataset;
+
+/// loads the variable and group catalog of an ACS or decennial dataset from its `variables.json` and
+/// `groups.json`. when the config has a catalog directory, metadata found there is read
+/// instead of downloaded, and downloaded metadata is written there, so each dataset is
+/// fetched once. downloads are retried following the config.
+pub async fn run(
+    client: &Client,
+    base_url: Option<&str>,
+    year: u64,
+    dataset: CensusDataset,
+    config: &AcsClientConfig,
+) -> Result<AcsCatalog, CensusError> {
+    let dataset_url = AcsApiQueryParams::dataset_url(base_url, year, dataset);
+    let prefix = format!("{}-{}", year, dataset);
+    let cache_dir = config.catalog_dir.as_deref();
+    let variables = fetch(
+        client,
+        &dataset_url,
+        "variables.json",
+        &prefix,
+        cache_dir,
+        config,
+    )
+    .await?;
+    let groups = fetch(
+        client,
+        &dataset_url,
+        "groups.json",
+        &prefix,
+        cache_dir,
+        config,
+    )
+    .await?;
+    AcsCatalog::from_json(&variables, Some(&groups))
+}
+
+async fn fetch(
+    client: &Client,
+    dataset_url: &str,
+    filename: &str,
+    prefix: &str,
+    cache_dir: Option<&Path>,
+    config: &AcsClientConfig,
+) -> Result<String, CensusError> {
+    let cache_path = cache_dir.map(|dir| dir.join(format!("{}-{}", prefix, filename)));
+    if let Some(path) = cache_path.as_ref().filter(|p| p.exists()) {
+        return read_cache(path);
+    }
+
+    let url = format!("{}/{}", dataset_url, filename);
+    let contents = with_retry(config, || download(client, &url)).await?;
+
+    if let Some(path) = cache_path {
+        write_cache(&path, &contents)?;
+    }
+    Ok(contents)
+}
+
+async fn download(client: &Client, url: &str) -> Result<String, CensusError> {
+    let url = String::from(url);
+    let response = client
+        .get(&url)
+        .send()
+        .await
+        .map_err(|e| CensusError::Http {
+            url: url.clone(),
+            source: Box::new(e),
+        })?;
+    let status_code = response.status();
+    let response = response
+        .error_for_status()
+        .map_err(|_| CensusError::HttpStatus {
+            url: url.clone(),
+            status: status_code.as_u16(),
+        })?;
+    response.text().await.map_err(|e| {
+        CensusError::ResponseSchema(format!("failure reading response from {}: {}", url, e))
+    })
+}
+
+fn read_cache(path: &Path) -> Result<String, CensusError> {
+    std::fs::read_to_string(path).map_err(|e| {
+        CensusError::Internal(format!(
+            "failure reading cached ACS metadata {}: {}",
+            path.display(),
+            e
+        ))
+    })
+}
+
+fn write_cache(path: &Path, contents: &str) -> Result<(), CensusError> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).map_err(|e| {
+            CensusError::Internal(format!(
+                "failure creating ACS metadata cache {}: {}",
+                parent.display(),
+                e
+            ))
+        })?;
+    }
+    std::fs::write(path, contents).map_err(|e| {
+        CensusError::Internal(format!(
+            "failure writing cached ACS metadata {}: {}",
+            path.display(),
+            e
+        ))
+    })
+}
@@ -1 +1,2 @@
 pub mod acs_api;
+pub mod acs_catalog_api;
@@ -1,3 +1,5 @@
 pub mod api;
 pub mod model;
 pub mod ops;
+
+pub use us_census_core::error::CensusError;
@@ -0,0 +1,343 @@
+use std::collections::{HashMap, HashSet};
+use std::fmt::Display;
+
+use super::AcsJamPolicy;
+use itertools::Itertools;
+use serde::{Deserialize, Serialize};
+use us_census_core::error::CensusError;
+use us_census_core::model::acs::acs_estimate::{estimate_variable, is_moe_variable};
+use us_census_core::model::acs::{AcsMeasure, AcsStatistic};
+use us_census_core::model::dataset::census_dataset::is_table_variable;
+
+static ADDITIVE: AcsAggregationRule = AcsAggregationRule::Additive;
+static NON_AGGREGATABLE: AcsAggregationRule = AcsAggregationRule::NonAggregatable;
+
+/// a bin of an ACS distribution table, such as B19001_002E (households with income less
+/// than $10,000), with the bounds of the values it counts. the last bin of a table is
+/// usually open-ended.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct AcsDistributionBin {
+    pub variable: String,
+    pub lower: f64,
+    pub upper: Option<f64>,
+}
+
+impl AcsDistributionBin {
+    pub fn new(variable: &str, lower: f64, upper: Option<f64>) -> AcsDistributionBin {
+        AcsDistributionBin {
+            variable: String::from(variable),
+            lower,
+            upper,
+        }
+    }
+}
+
+/// how an ACS variable is aggregated to a coarser geography. margin of error variables
+/// follow the rule of their estimate variable.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(rename_all = "snake_case", tag = "type")]
+pub enum AcsAggregationRule {
+    /// counts, aggregated with the requested aggregation function and weight
+    Additive,
+    /// averages, such as per capita income, aggregated by their mean weighted by a
+    /// universe variable, such as total population
+    WeightedBy { universe: String },
+    /// values that cannot be aggregated, such as percentages or medians without a
+    /// distribution table. aggregating these is an error
+    NonAggregatable,
+    /// medians estimated by Pareto interpolation over the aggregated counts of the bins
+    /// of a distribution table, see [`pareto_median`]. their margins of error are dropped
+    ParetoMedian { bins: Vec<AcsDistributionBin> },
+}
+
+impl Display for AcsAggregationRule {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AcsAggregationRule::Additive => write!(f, "additive"),
+            AcsAggregationRule::WeightedBy { universe } => write!(f, "weighted by {}", universe),
+            AcsAggregationRule::NonAggregatable => write!(f, "non-aggregatable"),
+            AcsAggregationRule::ParetoMedian { .. } => write!(f, "pareto median"),
+        }
+    }
+}
+
+/// table of aggregation rules by ACS estimate variable. variables without a rule are
+/// additive, except the percentages of data profile tables (ending in `PE`) and variables
+/// outside of tables (such as `NAME`), which are non-aggregatable. the percent columns of
+/// subject tables and the medians and means of all tables are only known from their
+/// labels, and are non-aggregatable once added with [`AcsAggregationRules::with_percents`]
+/// and [`AcsAggregationRules::with_statistics`]. the default table includes rules for
+/// common medians and averages of the detailed tables that can be aggregated, or that
+/// are non-aggregatable without their labels. jam values are treated following the
+/// table's [`AcsJamPolicy`].
+///
+/// # Examples
+///
+/// ```rust
+/// use std::collections::HashSet;
+/// use us_census_acs::model::{AcsAggregationRule, AcsAggregationRules, AcsJamPolicy};
+/// use us_census_core::model::acs::AcsStatistic;
+///
+/// let rules = AcsAggregationRules::default();
+/// assert_eq!(rules.rule("B01001_001E"), &AcsAggregationRule::Additive);
+/// assert_eq!(rules.rule("DP02_0002PE"), &AcsAggregationRule::NonAggregatable);
+/// assert_eq!(rules.rule("NAME"), &AcsAggregationRule::NonAggregatable);
+///
+/// // S1701_C03_001E is the percent below poverty level, as labeled in the variable catalog
+/// assert_eq!(rules.rule("S1701_C03_001E"), &AcsAggregationRule::Additive);
+/// let rules = rules.with_percents(["S1701_C03_001E"]);
+/// assert_eq!(rules.rule("S1701_C03_001E"), &AcsAggregationRule::NonAggregatable);
+/// assert_eq!(rules.rule("S1701_C03_001M"), &AcsAggregationRule::NonAggregatable);
+/// assert_eq!(rules.rule("B19301_001M"), rules.rule("B19301_001E"));
+///
+/// // B19113_001E is median family income, as labeled in the variable catalog
+/// assert_eq!(rules.rule("B19113_001E"), &AcsAggregationRule::Additive);
+/// let rules = rules.with_statistics([("B19113_001E", AcsStatistic::Median)]);
+/// assert_eq!(rules.rule("B19113_001E"), &AcsAggregationRule::NonAggregatable);
+/// assert!(rules.validate(&HashSet::from(["B19113_001E"])).is_err());
+///
+/// // median household income requires its household income distribution
+/// let query = HashSet::from(["B19013_001E"]);
+/// assert!(rules.validate(&query).is_err());
+///
+/// let rules = AcsAggregationRules::additive().with_rule(
+///     "B25010_001E",
+///     AcsAggregationRule::WeightedBy { universe: String::from("B25003_001E") }
+/// );
+/// let query = HashSet::from(["B25010_001E", "B25003_001E", "B19013_001E"]);
+/// assert!(rules.validate(&query).is_ok());
+///
+/// let rules = AcsAggregationRules::default().with_jam_policy(AcsJamPolicy::Skip);
+/// assert_eq!(rules.jam_policy(), AcsJamPolicy::Skip);
+/// ```
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct AcsAggregationRules {
+    rules: HashMap<String, AcsAggregationRule>,
+    #[serde(default)]
+    jam_policy: AcsJamPolicy,
+}
+
+impl Default for AcsAggregationRules {
+    fn default() -> Self {
+        let household_income_bins = [
+            (2, 0.0),
+            (3, 10000.0),
+            (4, 15000.0),
+            (5, 20000.0),
+            (6, 25000.0),
+            (7, 30000.0),
+            (8, 35000.0),
+            (9, 40000.0),
+            (10, 45000.0),
+            (11, 50000.0),
+            (12, 60000.0),
+            (13, 75000.0),
+            (14, 100000.0),
+            (15, 125000.0),
+            (16, 150000.0),
+            (17, 200000.0),
+        ];
+        let bins = household_income_bins
+            .iter()
+            .enumerate()
+            .map(|(idx, (table_idx, lower))| {
+                let upper = household_income_bins.get(idx + 1).map(|(_, u)| *u);
+                AcsDistributionBin::new(&format!("B19001_{:03}E", table_idx), *lower, upper)
+            })
+            .collect_vec();
+        let weighted_by = |universe: &str| AcsAggregationRule::WeightedBy {
+            universe: String::from(universe),
+        };
+        AcsAggregationRules::additive()
+            // median household income
+            .with_rule("B19013_001E", AcsAggregationRule::ParetoMedian { bins })
+            // median age, total, male and female
+            .with_rule("B01002_001E", AcsAggregationRule::NonAggregatable)
+            .with_rule("B01002_002E", AcsAggregationRule::NonAggregatable)
+            .with_rule("B01002_003E", AcsAggregationRule::NonAggregatable)
+            // median gross rent and median home value
+            .with_rule("B25064_001E", AcsAggregationRule::NonAggregatable)
+            .with_rule("B25077_001E", AcsAggregationRule::NonAggregatable)
+            // per capita income by total population
+            .with_rule("B19301_001E", weighted_by("B01003_001E"))
+            // average household size by occupied housing units
+            .with_rule("B25010_001E", weighted_by("B25003_001E"))
+    }
+}
+
+impl AcsAggregationRules {
+    /// an empty rule table, where all table variables other than percentages are additive.
+    pub fn additive() -> AcsAggregationRules {
+        AcsAggregationRules {
+            rules: HashMap::new(),
+            jam_policy: AcsJamPolicy::default(),
+        }
+    }
+
+    /// sets the rule of an estimate variable.
+    pub fn with_rule(mut self, variable: &str, rule: AcsAggregationRule) -> AcsAggregationRules {
+        self.rules.insert(String::from(variable), rule);
+        self
+    }
+
+    /// sets the rule of percent variables that have no rule to non-aggregatable, such as
+    /// the percent columns of subject tables, which are only told apart by their label,
+    /// see [`us_census_core::model::acs::AcsMeasure::from_label`].
+    pub fn with_percents<'a>(
+        mut self,
+        variables: impl IntoIterator<Item = &'a str>,
+    ) -> AcsAggregationRules {
+        for variable in variables.into_iter() {
+            let name = estimate_variable(variable).unwrap_or_else(|| String::from(variable));
+            self.rules
+                .entry(name)
+                .or_insert(AcsAggregationRule::NonAggregatable);
+        }
+        self
+    }
+
+    /// sets the rule of medians and means that have no rule to non-aggregatable, such as
+    /// median family income, which are only told apart by their label, see
+    /// [`AcsStatistic::from_label`]. rules of medians that can be interpolated and of
+    /// weighted averages are kept.
+    pub fn with_statistics<'a>(
+        mut self,
+        variables: impl IntoIterator<Item = (&'a str, AcsStatistic)>,
+    ) -> AcsAggregationRules {
+        for (variable, statistic) in variables.into_iter() {
+            if statistic.is_additive() {
+                continue;
+            }
+            let name = estimate_variable(variable).unwrap_or_else(|| String::from(variable));
+            self.rules
+                .entry(name)
+                .or_insert(AcsAggregationRule::NonAggregatable);
+        }
+        self
+    }
+
+    /// sets how jam values are treated.
+    pub fn with_jam_policy(self, jam_policy: AcsJamPolicy) -> AcsAggregationRules {
+        AcsAggregationRules { jam_policy, ..self }
+    }
+
+    pub fn jam_policy(&self) -> AcsJamPolicy {
+        self.jam_policy
+    }
+
+    /// the rule of an estimate variable, or of the estimate of a margin of error variable.
+    pub fn rule(&self, variable: &str) -> &AcsAggregationRule {
+        let estimate = estimate_variable(variable);
+        let name = estimate.as_deref().unwrap_or(variable);
+        match self.rules.get(name) {
+            Some(rule) => rule,
+            None if !is_table_variable(name) => &NON_AGGREGATABLE,
+            None if AcsMeasure::from_variable(name) == Some(AcsMeasure::Percent) => {
+                &NON_AGGREGATABLE
+            }
+            None => &ADDITIVE,
+        }
+    }
+
+    /// the variables of the distribution tables used by Pareto median rules.
+    pub fn distribution_bins(&self) -> HashSet<&str> {
+        self.rules
+            .values()
+            .flat_map(|rule| match rule {
+                AcsAggregationRule::ParetoMedian { bins } => bins.iter().collect_vec(),
+                _ => vec![],
+            })
+            .map(|bin| bin.variable.as_str())
+            .collect()
+    }
+
+    /// checks that the variables of a query can be aggregated: none are non-aggregatable,
+    /// and the universe variables and distribution bins of the others are present.
+    pub fn validate(&self, variables: &HashSet<&str>) -> Result<(), CensusError> {
+        let mut errors = vec![];
+        for variable in variables.iter().sorted() {
+            match self.rule(variable) {
+                AcsAggregationRule::Additive => {}
+                AcsAggregationRule::NonAggregatable => {
+                    errors.push(format!("{} cannot be aggregated", variable))
+                }
+                AcsAggregationRule::WeightedBy { universe } => {
+                    if !variables.contains(universe.as_str()) {
+                        errors.push(format!(
+                            "{} is weighted by {}, which is missing from the query",
+                            variable, universe
+                        ))
+                    }
+                }
+                AcsAggregationRule::ParetoMedian { bins } => {
+                    let missing = bins
+                        .iter()
+                        .filter(|b| !variables.contains(b.variable.as_str()))
+                        .map(|b| b.variable.as_str())
+                        .collect_vec();
+                    if !is_moe_variable(variable) && !missing.is_empty() {
+                        errors.push(format!(
+                            "{} is interpolated from a distribution table, but bins [{}] are missing from the query",
+                            variable,
+                            missing.join(", ")
+                        ))
+                    }
+                }
+            }
+        }
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(CensusError::Aggregation(format!(
+                "query has variables that cannot be aggregated: \n{}",
+                errors.join("\n")
+            )))
+        }
+    }
+}
+
+/// estimates the median of a distribution table from the counts of its bins, following
+/// the Census Bureau's Pareto interpolation. the median is interpolated linearly when it
+/// falls in a bin with a lower bound of zero, and is the lower bound of an open-ended bin
+/// when it falls there. None if the bins are empty.
+///
+/// # Examples
+///
+/// ```rust
+/// use us_census_acs::model::acs_aggregation_rule::{pareto_median, AcsDistributionBin};
+///
+/// let bins = vec![
+///     AcsDistributionBin::new("B19001_002E", 0.0, Some(10000.0)),
+///     AcsDistributionBin::new("B19001_003E", 10000.0, Some(20000.0)),
+///     AcsDistributionBin::new("B19001_004E", 20000.0, None),
+/// ];
+/// let median = pareto_median(&bins, &[40.0, 20.0, 40.0]).unwrap();
+/// assert!((median - 13657.2).abs() < 0.1);
+/// assert_eq!(pareto_median(&bins, &[20.0, 20.0, 60.0]), Some(20000.0));
+/// assert_eq!(pareto_median(&bins, &[20.0, 0.0, 0.0]), Some(5000.0));
+/// assert_eq!(pareto_median(&bins, &[0.0, 0.0, 0.0]), None);
+/// ```
+pub fn pareto_median(bins: &[AcsDistributionBin], counts: &[f64]) -> Option<f64> {
+    let total: f64 = counts.iter().sum();
+    if total <= 0.0 {
+        return None;
+    }
+    let half = total / 2.0;
+    let mut below = 0.0;
+    for (bin, count) in bins.iter().zip(counts) {
+        if *count <= 0.0 || below + count < half {
+            below += count;
+            continue;
+        }
+        let Some(upper) = bin.upper else {
+            return Some(bin.lower);
+        };
+        let (p1, p2) = (below / total, (below + count) / total);
+        if bin.lower <= 0.0 || p2 >= 1.0 {
+            return Some(bin.lower + (half - below) / count * (upper - bin.lower));
+        }
+        let theta = ((1.0 - p1).ln() - (1.0 - p2).ln()) / (upper.ln() - bin.lower.ln());
+        return Some(bin.lower * (((1.0 - p1).ln() - 0.5_f64.ln()) / theta).exp());
+    }
+    None
+}
@@ -1,23 +1,40 @@
+use crate::model::acs_catalog::AcsCatalog;
 use crate::model::acs_geoid_query::AcsGeoidQuery;
 use itertools::Itertools;
-use us_census_core::model::acs::acs_type::AcsType;
+use std::collections::HashMap;
+use us_census_core::error::CensusError;
+use us_census_core::model::acs::{AcsMeasure, AcsPredicateType, AcsStatistic};
+use us_census_core::model::dataset::CensusDataset;
 
+#[derive(Clone)]
 pub struct AcsApiQueryParams {
     pub base_url: Option<String>,
     pub year: u64,
-    pub acs_type: AcsType,
+    pub dataset: CensusDataset,
     pub get_query: Vec<String>,
     pub for_query: AcsGeoidQuery,
     pub api_token: Option<String>,
+    /// value types of the requested variables, used to parse the response. the types of
+    /// variables missing here are inferred, see [`AcsCatalog::predicate_types`]
+    pub predicate_types: HashMap<String, AcsPredicateType>,
+    /// what the requested variables measure, from their catalog labels. variables missing
+    /// here are classified by their names, see [`AcsCatalog::measures`]
+    pub measures: HashMap<String, AcsMeasure>,
+    /// the statistics the requested variables report, from their catalog labels, see
+    /// [`AcsCatalog::statistics`]
+    pub statistics: HashMap<String, AcsStatistic>,
 }
 
 impl AcsApiQueryParams {
     pub const BASE_URL: &'static str = "https://api.census.gov/data";
 
+    /// the Census Data API limit on the number of variables of a `get` query.
+    pub const MAX_GET_VARIABLES: usize = 50;
+
     pub fn new(
         base_url: Option<String>,
         year: u64,
-        acs_type: AcsType,
+        dataset: impl Into<CensusDataset>,
         get_query: Vec<String>,
         for_query: AcsGeoidQuery,
         api_token: Option<String>,
@@ -25,20 +42,38 @@ impl AcsApiQueryParams {
         AcsApiQueryParams {
             base_url,
             year,
-            acs_type,
+            dataset: dataset.into(),
             get_query,
             for_query,
             api_token,
+            predicate_types: HashMap::new(),
+            measures: HashMap::new(),
+            statistics: HashMap::new(),
+        }
+    }
+
+    /// sets the value types, measures and statistics of the requested variables from the
+    /// catalog of the dataset.
+    pub fn with_catalog(self, catalog: &AcsCatalog) -> AcsApiQueryParams {
+        let predicate_types = catalog.predicate_types(&self.get_query);
+        let measures = catalog.measures(&self.get_query);
+        let statistics = catalog.statistics(&self.get_query);
+        AcsApiQueryParams {
+            predicate_types,
+            measures,
+            statistics,
+            ..self
         }
     }
 
     pub fn acs_dataset_url(&self) -> String {
-        let base = self
-            .base_url
-            .clone()
-            .unwrap_or(String::from(AcsApiQueryParams::BASE_URL));
-        let type_s = self.acs_type.to_directory_name();
-        format!("{}/{}/acs/{}", base, self.year, type_s)
+        AcsApiQueryParams::dataset_url(self.base_url.as_deref(), self.year, self.dataset)
+    }
+
+    /// the URL of a Census dataset, which is the root of its queries and metadata.
+    pub fn dataset_url(base_url: Option<&str>, year: u64, dataset: CensusDataset) -> String {
+        let base = base_url.unwrap_or(AcsApiQueryParams::BASE_URL);
+        format!("{}/{}/{}", base, year, dataset.to_directory_name())
     }
 
     /// builds an ACS REST query URL from application parameters.
@@ -85,7 +120,30 @@ impl AcsApiQueryParams {
     /// let api_url = api_query_params.build_url().unwrap();
     /// assert_eq!(api_url, String::from("https://api.census.gov/data/2022/acs/acs5?get=NAME,B01001_001E&for=county:*&in=state:08"))
     /// ```
-    pub fn build_url(&self) -> Result<String, String> {
+    /// Example 3. Get 2020 redistricting population counts for all blocks of a census tract.
+    /// block-level queries are only supported by decennial datasets.
+    ///
+    /// ```rust
+    /// use us_census_core::model::identifier::{fips, geoid_type::GeoidType, geoid::Geoid};
+    /// use us_census_core::model::acs::AcsType;
+    /// use us_census_core::model::decennial::DecennialType;
+    /// use us_census_acs::model::{
+    ///     acs_geoid_query::AcsGeoidQuery,
+    ///     acs_api_query_params::AcsApiQueryParams
+    /// };
+    ///
+    /// let tract = Geoid::CensusTract(fips::State(8), fips::County(31), fips::CensusTract(100));
+    /// let queries = vec![String::from("P1_001N")];
+    /// let geoid_query = AcsGeoidQuery::new(Some(tract), Some(GeoidType::Block)).unwrap();
+    /// let params = AcsApiQueryParams::new(None, 2020, DecennialType::Pl, queries.clone(), geoid_query.clone(), None);
+    /// let api_url = params.build_url().unwrap();
+    /// assert_eq!(api_url, String::from("https://api.census.gov/data/2020/dec/pl?get=P1_001N&for=block:*&in=state:08&in=county:031&in=tract:000100"));
+    ///
+    /// let params = AcsApiQueryParams::new(None, 2020, AcsType::FiveYear, queries, geoid_query, None);
+    /// assert!(params.build_url().is_err());
+    /// ```
+    pub fn build_url(&self) -> Result<String, CensusError> {
+        self.validate()?;
         let dataset_url = self.acs_dataset_url();
         let get_query = self.get_query.iter().join(",");
         let for_query = self.for_query.to_query_key();
@@ -100,9 +158,72 @@ impl AcsApiQueryParams {
         Ok(query)
     }
 
+    /// checks that the dataset was published for the query year and at the query GeoidType,
+    /// and that the query variables follow the naming of the dataset's tables.
+    pub fn validate(&self) -> Result<(), CensusError> {
+        self.dataset.validate_year(self.year)?;
+        for variable in self.get_query.iter() {
+            self.dataset.validate_variable(variable)?;
+        }
+        let geoid_type = self.for_query.get_geoid_type();
+        if !self.dataset.supports_geoid_type(&geoid_type) {
+            return Err(CensusError::InvalidQuery(format!(
+                "{} does not support {} queries",
+                self.dataset, geoid_type
+            )));
+        }
+        Ok(())
+    }
+
+    /// true if the `get` query includes `group(...)` entries, which expand into all of
+    /// the columns of a table group in the response.
+    pub fn has_groups(&self) -> bool {
+        self.get_query.iter().any(|v| group_name(v).is_some())
+    }
+
+    /// splits this query into queries of at most [`AcsApiQueryParams::MAX_GET_VARIABLES`]
+    /// variables each, keeping the order of the variables. each `group(...)` entry is sent
+    /// in a query of its own, as a group expands into many columns.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use us_census_core::model::identifier::geoid_type::GeoidType;
+    /// use us_census_core::model::acs::AcsType;
+    /// use us_census_acs::model::{AcsApiQueryParams, AcsGeoidQuery};
+    ///
+    /// let mut variables = (1..=120).map(|i| format!("B01001_{:03}E", i)).collect::<Vec<_>>();
+    /// variables.push(String::from("group(B19001)"));
+    /// let geoid_query = AcsGeoidQuery::new(None, Some(GeoidType::State)).unwrap();
+    /// let query = AcsApiQueryParams::new(None, 2022, AcsType::FiveYear, variables, geoid_query, None);
+    /// let chunks = query.chunks();
+    /// let sizes = chunks.iter().map(|c| c.get_query.len()).collect::<Vec<_>>();
+    /// assert_eq!(sizes, vec![50, 50, 20, 1]);
+    /// assert_eq!(
+    ///     chunks[3].build_url().unwrap(),
+    ///     "https://api.census.gov/data/2022/acs/acs5?get=group(B19001)&for=state:*"
+    /// );
+    /// ```
+    pub fn chunks(&self) -> Vec<AcsApiQueryParams> {
+        let (groups, variables): (Vec<&String>, Vec<&String>) =
+            self.get_query.iter().partition(|v| group_name(v).is_some());
+        let variable_chunks = variables
+            .chunks(AcsApiQueryParams::MAX_GET_VARIABLES)
+            .map(|chunk| chunk.iter().map(|v| (*v).clone()).collect_vec());
+        let group_chunks = groups.into_iter().map(|g| vec![g.clone()]);
+        variable_chunks
+            .chain(group_chunks)
+            .map(|get_query| AcsApiQueryParams {
+                get_query,
+                ..self.clone()
+            })
+            .collect_vec()
+    }
+
     /// in order to deconstruct an API response, we need the list of
     /// column names in the order that they would appear in the array-
-    /// shaped ACS response object.
+    /// shaped ACS response object. the columns of `group(...)` queries are
+    /// only known from the response.
     pub fn column_names(&self) -> Vec<String> {
         let mut cols = self
             .get_query
@@ -120,7 +241,23 @@ impl AcsApiQueryParams {
 
         format!(
             "{}-{}-{}-{}.csv",
-            self.acs_type, self.year, get_query, for_query
+            self.dataset, self.year, get_query, for_query
         )
     }
 }
+
+/// the table group of a `group(...)` query entry, such as `B01001` for `group(B01001)`.
+///
+/// # Examples
+///
+/// ```rust
+/// use us_census_acs::model::acs_api_query_params::group_name;
+///
+/// assert_eq!(group_name("group(B01001)"), Some("B01001"));
+/// assert_eq!(group_name("B01001_001E"), None);
+/// ```
+pub fn group_name(entry: &str) -> Option<&str> {
+    entry
+        .strip_prefix("group(")
+        .and_then(|rest| rest.strip_suffix(')'))
+}
@@ -0,0 +1,219 @@
+use super::acs_api_query_params::group_name;
+use super::acs_variable::{AcsGroup, AcsVariable};
+use itertools::Itertools;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::path::Path;
+use us_census_core::error::CensusError;
+use us_census_core::model::acs::{AcsMeasure, AcsPredicateType, AcsStatistic};
+
+/// names of the geography predicates listed in `variables.json`, which are not
+/// requested with `get`.
+const PREDICATES: [&str; 3] = ["for", "in", "ucgid"];
+
+/// catalog of the variables and table groups of an ACS dataset, read from the dataset's
+/// `variables.json` and `groups.json` metadata. used to validate a query before it is
+/// sent and to look up labels, concepts, universes and predicate types.
+///
+/// # Examples
+///
+/// ```rust
+/// use us_census_acs::model::AcsCatalog;
+/// use us_census_core::model::acs::AcsPredicateType;
+///
+/// let variables = r#"{"variables": {
+///     "NAME": {"label": "Geographic Area Name", "predicateType": "string", "group": "N/A"},
+///     "B01001_001E": {"label": "Estimate!!Total:", "concept": "Sex by Age", "predicateType": "int", "group": "B01001"},
+///     "B01001_002E": {"label": "Estimate!!Total:!!Male:", "concept": "Sex by Age", "predicateType": "int", "group": "B01001"},
+///     "for": {"label": "Census API FIPS 'for' clause", "predicateType": "fips-for", "group": "N/A"}
+/// }}"#;
+/// let groups = r#"{"groups": [
+///     {"name": "B01001", "description": "Sex by Age", "universe ": "Total population"}
+/// ]}"#;
+/// let catalog = AcsCatalog::from_json(variables, Some(groups)).unwrap();
+///
+/// let total = catalog.variable("B01001_001E").unwrap();
+/// assert_eq!(total.predicate_type.as_deref(), Some("int"));
+/// assert_eq!(total.value_type(), Some(AcsPredicateType::Int));
+/// assert_eq!(catalog.universe("B01001_002E"), Some("Total population"));
+///
+/// let query = vec![String::from("NAME"), String::from("B01001_001E")];
+/// assert!(catalog.validate(&query).is_ok());
+/// let query = vec![String::from("B01001_01E")];
+/// assert!(catalog.validate(&query).is_err());
+/// let query = vec![String::from("group(B01001)")];
+/// assert!(catalog.validate(&query).is_ok());
+/// assert_eq!(catalog.predicate_types(&query).len(), 2);
+///
+/// let found = catalog.search(&["male"]);
+/// assert_eq!(found.len(), 1);
+/// assert_eq!(found[0].name, "B01001_002E");
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct AcsCatalog {
+    variables: HashMap<String, AcsVariable>,
+    groups: HashMap<String, AcsGroup>,
+}
+
+#[derive(Deserialize)]
+struct VariablesJson {
+    variables: HashMap<String, AcsVariable>,
+}
+
+#[derive(Deserialize)]
+struct GroupsJson {
+    groups: Vec<AcsGroup>,
+}
+
+impl AcsCatalog {
+    /// builds a catalog from the contents of `variables.json` and, optionally, `groups.json`.
+    pub fn from_json(variables: &str, groups: Option<&str>) -> Result<AcsCatalog, CensusError> {
+        let variables: VariablesJson = serde_json::from_str(variables).map_err(|e| {
+            CensusError::ResponseSchema(format!("failure reading ACS variables.json: {}", e))
+        })?;
+        let variables = variables
+            .variables
+            .into_iter()
+            .filter(|(name, _)| !PREDICATES.contains(&name.as_str()))
+            .map(|(name, mut variable)| {
+                variable.name.clone_from(&name);
+                (name, variable)
+            })
+            .collect();
+        let groups = match groups {
+            None => HashMap::new(),
+            Some(json) => {
+                let groups: GroupsJson = serde_json::from_str(json).map_err(|e| {
+                    CensusError::ResponseSchema(format!("failure reading ACS groups.json: {}", e))
+                })?;
+                groups
+                    .groups
+                    .into_iter()
+                    .map(|g| (g.name.clone(), g))
+                    .collect()
+            }
+        };
+        Ok(AcsCatalog { variables, groups })
+    }
+
+    /// reads a catalog from local copies of `variables.json` and, optionally, `groups.json`.
+    pub fn from_paths(variables: &Path, groups: Option<&Path>) -> Result<AcsCatalog, CensusError> {
+        let read = |path: &Path| {
+            std::fs::read_to_string(path).map_err(|e| {
+                CensusError::InvalidQuery(format!(
+                    "failure reading ACS metadata file {}: {}",
+                    path.display(),
+                    e
+                ))
+            })
+        };
+        let variables = read(variables)?;
+        let groups = groups.map(read).transpose()?;
+        AcsCatalog::from_json(&variables, groups.as_deref())
+    }
+
+    pub fn len(&self) -> usize {
+        self.variables.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.variables.is_empty()
+    }
+
+    pub fn variable(&self, name: &str) -> Option<&AcsVariable> {
+        self.variables.get(name)
+    }
+
+    pub fn group(&self, name: &str) -> Option<&AcsGroup> {
+        self.groups.get(name)
+    }
+
+    /// true if a table group is in this catalog, either listed in `groups.json` or
+    /// referenced by a variable.
+    pub fn has_group(&self, group: &str) -> bool {
+        self.groups.contains_key(group)
+            || self
+                .variables
+                .values()
+                .any(|v| v.group_name() == Some(group))
+    }
+
+    /// the variables of a table group, sorted by name.
+    pub fn group_variables(&self, group: &str) -> Vec<&AcsVariable> {
+        self.variables
+            .values()
+            .filter(|v| v.group_name() == Some(group))
+            .sorted_by(|a, b| a.name.cmp(&b.name))
+            .collect_vec()
+    }
+
+    /// the universe of a variable, from the table group it belongs to.
+    pub fn universe(&self, variable: &str) -> Option<&str> {
+        let group = self.variable(variable)?.group_name()?;
+        self.group(group)?.universe.as_deref()
+    }
+
+    /// the variables with each keyword in their name, label or concept, sorted by name.
+    pub fn search(&self, keywords: &[&str]) -> Vec<&AcsVariable> {
+        self.variables
+            .values()
+            .filter(|v| v.matches(keywords))
+            .sorted_by(|a, b| a.name.cmp(&b.name))
+            .collect_vec()
+    }
+
+    /// the value types of the variables of a `get` query, including the variables of its
+    /// `group(...)` entries. variables without a known type are left out.
+    pub fn predicate_types(&self, get_query: &[String]) -> HashMap<String, AcsPredicateType> {
+        self.query_variables(get_query)
+            .filter_map(|v| Some((v.name.clone(), v.value_type()?)))
+            .collect()
+    }
+
+    /// what the table variables of a `get` query measure, including the variables of its
+    /// `group(...)` entries, see [`AcsVariable::measure`].
+    pub fn measures(&self, get_query: &[String]) -> HashMap<String, AcsMeasure> {
+        self.query_variables(get_query)
+            .filter_map(|v| Some((v.name.clone(), v.measure()?)))
+            .collect()
+    }
+
+    /// the statistics the table variables of a `get` query report, including the variables
+    /// of its `group(...)` entries, see [`AcsVariable::statistic`].
+    pub fn statistics(&self, get_query: &[String]) -> HashMap<String, AcsStatistic> {
+        self.query_variables(get_query)
+            .filter(|v| v.measure().is_some())
+            .map(|v| (v.name.clone(), v.statistic()))
+            .collect()
+    }
+
+    /// the catalog variables of a `get` query, expanding its `group(...)` entries.
+    fn query_variables<'a>(
+        &'a self,
+        get_query: &'a [String],
+    ) -> impl Iterator<Item = &'a AcsVariable> + 'a {
+        get_query.iter().flat_map(|name| match group_name(name) {
+            Some(group) => self.group_variables(group),
+            None => self.variable(name).into_iter().collect_vec(),
+        })
+    }
+
+    /// checks that each variable and `group(...)` of a `get` query is in this catalog.
+    pub fn validate(&self, get_query: &[String]) -> Result<(), CensusError> {
+        let unknown = get_query
+            .iter()
+            .filter(|name| match group_name(name) {
+                Some(group) => !self.has_group(group),
+                None => !self.variables.contains_key(name.as_str()),
+            })
+            .collect_vec();
+        if unknown.is_empty() {
+            Ok(())
+        } else {
+            Err(CensusError::InvalidQuery(format!(
+                "unknown ACS variables: [{}]",
+                unknown.iter().join(", ")
+            )))
+        }
+    }
+}
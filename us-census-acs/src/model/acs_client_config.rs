@@ -0,0 +1,85 @@
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use std::time::Duration;
+
+/// settings for calls to the ACS API: the number of requests in flight at once, and the
+/// retries of requests that fail with a rate limit (HTTP 429), a server error (HTTP 5xx)
+/// or a connection error. retries wait with an exponential backoff, doubling from the
+/// initial backoff up to the max backoff. the variable catalogs that type the values of
+/// responses are cached in the catalog directory, which defaults to a directory in the
+/// user's cache directory, see [`AcsClientConfig::default_catalog_dir`]. without a catalog
+/// directory, catalogs are downloaded on every run.
+///
+/// # Examples
+///
+/// ```rust
+/// use std::time::Duration;
+/// use us_census_acs::model::AcsClientConfig;
+///
+/// let config = AcsClientConfig::default();
+/// assert_eq!(config.backoff(0), Duration::from_millis(500));
+/// assert_eq!(config.backoff(3), Duration::from_millis(4000));
+/// assert_eq!(config.backoff(10), Duration::from_millis(30000));
+/// assert_eq!(config.catalog_dir, AcsClientConfig::default_catalog_dir());
+/// ```
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct AcsClientConfig {
+    /// maximum number of ACS requests in flight at once
+    pub concurrency: usize,
+    /// maximum number of retries of a failed request
+    pub max_retries: u32,
+    /// wait before the first retry, in milliseconds
+    pub initial_backoff_ms: u64,
+    /// upper bound on the wait between retries, in milliseconds
+    pub max_backoff_ms: u64,
+    /// directory caching the variable catalogs of the queried datasets
+    #[serde(default = "AcsClientConfig::default_catalog_dir")]
+    pub catalog_dir: Option<PathBuf>,
+}
+
+impl Default for AcsClientConfig {
+    fn default() -> Self {
+        AcsClientConfig {
+            concurrency: 8,
+            max_retries: 5,
+            initial_backoff_ms: 500,
+            max_backoff_ms: 30000,
+            catalog_dir: AcsClientConfig::default_catalog_dir(),
+        }
+    }
+}
+
+impl AcsClientConfig {
+    pub fn new(concurrency: usize, max_retries: u32) -> AcsClientConfig {
+        AcsClientConfig {
+            concurrency,
+            max_retries,
+            ..Default::default()
+        }
+    }
+
+    pub fn with_catalog_dir(self, catalog_dir: Option<PathBuf>) -> AcsClientConfig {
+        AcsClientConfig {
+            catalog_dir,
+            ..self
+        }
+    }
+
+    /// the `us-census/catalogs` directory of the user's cache directory, which is
+    /// `$XDG_CACHE_HOME`, `%LOCALAPPDATA%` or else `$HOME/.cache`. None if none are set.
+    pub fn default_catalog_dir() -> Option<PathBuf> {
+        let cache_dir = std::env::var_os("XDG_CACHE_HOME")
+            .map(PathBuf::from)
+            .filter(|dir| dir.is_absolute())
+            .or_else(|| std::env::var_os("LOCALAPPDATA").map(PathBuf::from))
+            .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".cache")))?;
+        Some(cache_dir.join("us-census").join("catalogs"))
+    }
+
+    /// the wait before retry number `attempt`, counting from zero.
+    pub fn backoff(&self, attempt: u32) -> Duration {
+        let factor = 2_u64.saturating_pow(attempt);
+        let ms = self.initial_backoff_ms.saturating_mul(factor);
+        Duration::from_millis(ms.min(self.max_backoff_ms))
+    }
+}
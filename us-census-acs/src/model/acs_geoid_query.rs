@@ -1,11 +1,13 @@
 use std::rc::Rc;
+use us_census_core::error::CensusError;
 use us_census_core::model::identifier::{
-    fips, geoid::Geoid, geoid_type::GeoidType, has_geoid_string::HasGeoidString,
+    fips, geoid::Geoid, geoid_type::GeoidType, has_geoid_string::HasGeoidString, SummaryLevel,
 };
 
-pub type DeserializeGeoidFn = Rc<dyn Fn(Vec<serde_json::Value>) -> Result<Geoid, String>>;
+pub type DeserializeGeoidFn = Rc<dyn Fn(Vec<serde_json::Value>) -> Result<Geoid, CensusError>>;
 
-/// enumeration representing the scopes of various ACS queries.
+/// enumeration representing the scopes of various ACS and decennial queries. block
+/// queries are only supported by decennial datasets.
 ///
 /// when running an ACS query at a given GEOID hierarchical level, there are a set
 /// of required (aka, not `Option`al) components which can be coupled with `Option`al
@@ -27,6 +29,22 @@ pub enum AcsGeoidQuery {
         Option<fips::CensusTract>,
         Option<fips::BlockGroup>,
     ),
+    CongressionalDistrict(Option<fips::State>, Option<fips::CongressionalDistrict>),
+    StateLegislativeDistrictUpperChamber(
+        fips::State,
+        Option<fips::StateLegislativeDistrictUpperChamber>,
+    ),
+    StateLegislativeDistrictLowerChamber(
+        fips::State,
+        Option<fips::StateLegislativeDistrictLowerChamber>,
+    ),
+    ZipCodeTabulationArea(Option<fips::ZipCodeTabulationArea>),
+    Block(
+        fips::State,
+        fips::County,
+        Option<fips::CensusTract>,
+        Option<fips::Block>,
+    ),
 }
 
 impl AcsGeoidQuery {
@@ -71,84 +89,119 @@ impl AcsGeoidQuery {
     /// assert_eq!(key, String::from("&for=tract:000001&in=state:08"));
     /// ```
     ///
+    /// districts are queried within a state, while ZCTAs are national.
+    /// ```rust
+    /// use us_census_core::model::identifier::{fips, geoid::Geoid, geoid_type::GeoidType};
+    /// use us_census_acs::model::acs_geoid_query::AcsGeoidQuery;;
+    ///
+    /// let geoid = Geoid::State(fips::State(8));
+    /// let query = AcsGeoidQuery::new(Some(geoid.clone()), Some(GeoidType::StateLegislativeDistrictUpperChamber)).unwrap();
+    /// let key = query.to_query_key();
+    /// assert_eq!(key, String::from("&for=state%20legislative%20district%20(upper%20chamber):*&in=state:08"));
+    ///
+    /// let query = AcsGeoidQuery::new(None, Some(GeoidType::ZipCodeTabulationArea)).unwrap();
+    /// assert_eq!(query.to_query_key(), String::from("&for=zip%20code%20tabulation%20area:*"));
+    /// assert!(AcsGeoidQuery::new(Some(geoid), Some(GeoidType::ZipCodeTabulationArea)).is_err());
+    /// ```
+    ///
     /// # Returns
     ///
     /// URL query string for calls to the US Census ACS API "for" section, which set the
     /// spatial scope and granularity of the query result.
-    pub fn new(geoid: Option<Geoid>, wildcard: Option<GeoidType>) -> Result<AcsGeoidQuery, String> {
+    pub fn new(
+        geoid: Option<Geoid>,
+        wildcard: Option<GeoidType>,
+    ) -> Result<AcsGeoidQuery, CensusError> {
         use Geoid as G;
         use GeoidType as GT;
 
         match (geoid, wildcard) {
             // ~~ errors ~~
             // - invalid combinations of geoid/wildcard values
-            (None, None) => Err(String::from(
+            (None, None) => Err(CensusError::InvalidQuery(String::from(
                 "cannot create query without at least a geoid or wildcard",
-            )),
-            (None, Some(GT::CountySubdivision)) => Err(String::from(
+            ))),
+            (None, Some(GT::CountySubdivision)) => Err(CensusError::InvalidQuery(String::from(
                 "cannot create county subdivision query without State Geoid",
-            )),
-            (None, Some(GT::CensusTract)) => Err(String::from(
+            ))),
+            (None, Some(GT::CensusTract)) => Err(CensusError::InvalidQuery(String::from(
                 "cannot create census tract query without State Geoid",
-            )),
-            (None, Some(GT::BlockGroup)) => Err(String::from(
+            ))),
+            (None, Some(GT::BlockGroup)) => Err(CensusError::InvalidQuery(String::from(
                 "cannot create block group query without State + County Geoids",
-            )),
-            (_, Some(GT::Block)) => Err(String::from("acs does not support block-level queries")),
-            (Some(G::Block(_, _, _, _)), _) => {
-                Err(String::from("acs does not support block-level queries"))
-            }
+            ))),
+            (None, Some(GT::Block)) => Err(CensusError::InvalidQuery(String::from(
+                "cannot create block query without State + County Geoids",
+            ))),
+            (Some(Geoid::State(_)), Some(GT::Block)) => Err(CensusError::InvalidQuery(String::from(
+                "cannot create block query without County Geoid",
+            ))),
 
-            (Some(Geoid::State(_)), Some(GT::BlockGroup)) => Err(String::from(
+            (Some(Geoid::State(_)), Some(GT::BlockGroup)) => Err(CensusError::InvalidQuery(String::from(
                 "cannot create block group query without County Geoid",
-            )),
-            (Some(Geoid::County(_, _)), Some(GT::Place)) => Err(String::from(
+            ))),
+            (Some(Geoid::County(_, _)), Some(GT::Place)) => Err(CensusError::InvalidQuery(String::from(
                 "cannot append a 'Place' wildcard to a County Geoid",
-            )),
-            (Some(G::CountySubdivision(_, _, _)), Some(GT::Place)) => Err(String::from(
+            ))),
+            (Some(G::CountySubdivision(_, _, _)), Some(GT::Place)) => Err(CensusError::InvalidQuery(String::from(
                 "cannot append a 'Place' wildcard to a CountySubdivision Geoid",
-            )),
-            (Some(G::CountySubdivision(_, _, _)), Some(GT::CensusTract)) => Err(String::from(
+            ))),
+            (Some(G::CountySubdivision(_, _, _)), Some(GT::CensusTract)) => Err(CensusError::InvalidQuery(String::from(
                 "cannot append a 'CensusTract' wildcard to a CountySubdivision Geoid",
-            )),
-            (Some(G::CountySubdivision(_, _, _)), Some(GT::BlockGroup)) => Err(String::from(
+            ))),
+            (Some(G::CountySubdivision(_, _, _)), Some(GT::BlockGroup)) => Err(CensusError::InvalidQuery(String::from(
                 "cannot append a 'BlockGroup' wildcard to a CountySubdivision Geoid",
-            )),
-            (Some(Geoid::Place(_, _)), Some(GT::County)) => Err(String::from(
+            ))),
+            (Some(Geoid::Place(_, _)), Some(GT::County)) => Err(CensusError::InvalidQuery(String::from(
                 "cannot append a 'County' wildcard to a Place Geoid",
-            )),
-            (Some(Geoid::Place(_, _)), Some(GT::CountySubdivision)) => Err(String::from(
+            ))),
+            (Some(Geoid::Place(_, _)), Some(GT::CountySubdivision)) => Err(CensusError::InvalidQuery(String::from(
                 "cannot append a 'CountySubdivision' wildcard to a Place Geoid",
-            )),
-            (Some(Geoid::Place(_, _)), Some(GT::CensusTract)) => Err(String::from(
+            ))),
+            (Some(Geoid::Place(_, _)), Some(GT::CensusTract)) => Err(CensusError::InvalidQuery(String::from(
                 "cannot append a 'CensusTract' wildcard to a Place Geoid",
-            )),
-            (Some(Geoid::Place(_, _)), Some(GT::BlockGroup)) => Err(String::from(
+            ))),
+            (Some(Geoid::Place(_, _)), Some(GT::BlockGroup)) => Err(CensusError::InvalidQuery(String::from(
                 "cannot append a 'BlockGroup' wildcard to a Place Geoid",
-            )),
-            (Some(Geoid::CensusTract(_, _, _)), Some(GT::State)) => Err(String::from(
+            ))),
+            (Some(Geoid::CensusTract(_, _, _)), Some(GT::State)) => Err(CensusError::InvalidQuery(String::from(
                 "cannot append a 'State' wildcard to a CensusTract Geoid",
-            )),
-            (Some(Geoid::CensusTract(_, _, _)), Some(GT::CountySubdivision)) => Err(String::from(
+            ))),
+            (Some(Geoid::CensusTract(_, _, _)), Some(GT::CountySubdivision)) => Err(CensusError::InvalidQuery(String::from(
                 "cannot append a 'CountySubdivision' wildcard to a CensusTract Geoid",
-            )),
-            (Some(Geoid::CensusTract(_, _, _)), Some(GT::Place)) => Err(String::from(
+            ))),
+            (Some(Geoid::CensusTract(_, _, _)), Some(GT::Place)) => Err(CensusError::InvalidQuery(String::from(
                 "cannot append a 'Place' wildcard to a CensusTract Geoid",
-            )),
-            (Some(Geoid::BlockGroup(_, _, _, _)), Some(GT::State)) => Err(String::from(
+            ))),
+            (Some(Geoid::BlockGroup(_, _, _, _)), Some(GT::State)) => Err(CensusError::InvalidQuery(String::from(
                 "cannot append a 'State' wildcard to a BlockGroup Geoid",
-            )),
+            ))),
             (Some(Geoid::BlockGroup(_, _, _, _)), Some(GT::CountySubdivision)) => Err(
-                String::from("cannot append a 'CountySubdivision' wildcard to a BlockGroup Geoid"),
+                CensusError::InvalidQuery(String::from("cannot append a 'CountySubdivision' wildcard to a BlockGroup Geoid")),
             ),
-            (Some(Geoid::BlockGroup(_, _, _, _)), Some(GT::Place)) => Err(String::from(
+            (Some(Geoid::BlockGroup(_, _, _, _)), Some(GT::Place)) => Err(CensusError::InvalidQuery(String::from(
                 "cannot append a 'Place' wildcard to a BlockGroup Geoid",
-            )),
+            ))),
+            (None, Some(GT::StateLegislativeDistrictUpperChamber)) => Err(CensusError::InvalidQuery(String::from(
+                "cannot create state legislative district (upper chamber) query without State Geoid",
+            ))),
+            (None, Some(GT::StateLegislativeDistrictLowerChamber)) => Err(CensusError::InvalidQuery(String::from(
+                "cannot create state legislative district (lower chamber) query without State Geoid",
+            ))),
+            (Some(Geoid::State(_)), Some(GT::ZipCodeTabulationArea)) => Err(CensusError::InvalidQuery(String::from(
+                "zip code tabulation areas do not nest within states, cannot append a 'ZipCodeTabulationArea' wildcard to a State Geoid",
+            ))),
 
             // ~~ wildcard-only queries for different GEOID levels ~~
             (None, Some(GT::State)) => Ok(AcsGeoidQuery::State(None)),
             (None, Some(GT::County)) => Ok(AcsGeoidQuery::County(None, None)),
             (None, Some(GT::Place)) => Ok(AcsGeoidQuery::Place(None, None)),
+            (None, Some(GT::CongressionalDistrict)) => {
+                Ok(AcsGeoidQuery::CongressionalDistrict(None, None))
+            }
+            (None, Some(GT::ZipCodeTabulationArea)) => {
+                Ok(AcsGeoidQuery::ZipCodeTabulationArea(None))
+            }
 
             // ~~ queries for wildcards inserted into specific geoids ~~
             // - STATE -
@@ -161,6 +214,15 @@ impl AcsGeoidQuery {
             (Some(Geoid::State(s)), Some(GT::CensusTract)) => {
                 Ok(AcsGeoidQuery::CensusTract(s, None, None))
             }
+            (Some(Geoid::State(s)), Some(GT::CongressionalDistrict)) => {
+                Ok(AcsGeoidQuery::CongressionalDistrict(Some(s), None))
+            }
+            (Some(Geoid::State(s)), Some(GT::StateLegislativeDistrictUpperChamber)) => {
+                Ok(AcsGeoidQuery::StateLegislativeDistrictUpperChamber(s, None))
+            }
+            (Some(Geoid::State(s)), Some(GT::StateLegislativeDistrictLowerChamber)) => {
+                Ok(AcsGeoidQuery::StateLegislativeDistrictLowerChamber(s, None))
+            }
 
             // - COUNTY -
             (Some(Geoid::County(_, c)), Some(GT::State)) => {
@@ -216,6 +278,33 @@ impl AcsGeoidQuery {
                 Ok(AcsGeoidQuery::BlockGroup(s, Some(c), Some(t), None))
             }
 
+            // - BLOCK -
+            (Some(Geoid::County(s, c)), Some(GT::Block)) => {
+                Ok(AcsGeoidQuery::Block(s, c, None, None))
+            }
+            (Some(Geoid::CensusTract(s, c, t)), Some(GT::Block)) => {
+                Ok(AcsGeoidQuery::Block(s, c, Some(t), None))
+            }
+            (Some(Geoid::Block(s, c, t, _)), Some(GT::Block)) => {
+                Ok(AcsGeoidQuery::Block(s, c, Some(t), None))
+            }
+
+            // - DISTRICTS + ZCTA -
+            (Some(G::CongressionalDistrict(s, _)), Some(GT::CongressionalDistrict)) => {
+                Ok(AcsGeoidQuery::CongressionalDistrict(Some(s), None))
+            }
+            (
+                Some(G::StateLegislativeDistrictUpperChamber(s, _)),
+                Some(GT::StateLegislativeDistrictUpperChamber),
+            ) => Ok(AcsGeoidQuery::StateLegislativeDistrictUpperChamber(s, None)),
+            (
+                Some(G::StateLegislativeDistrictLowerChamber(s, _)),
+                Some(GT::StateLegislativeDistrictLowerChamber),
+            ) => Ok(AcsGeoidQuery::StateLegislativeDistrictLowerChamber(s, None)),
+            (Some(G::ZipCodeTabulationArea(_)), Some(GT::ZipCodeTabulationArea)) => {
+                Ok(AcsGeoidQuery::ZipCodeTabulationArea(None))
+            }
+
             // ~~ queries for specific geoids (no wildcards) ~~
             (Some(Geoid::State(s)), None) => Ok(AcsGeoidQuery::State(Some(s))),
             (Some(Geoid::County(s, c)), None) => Ok(AcsGeoidQuery::County(Some(s), Some(c))),
@@ -229,6 +318,26 @@ impl AcsGeoidQuery {
             (Some(Geoid::BlockGroup(s, c, t, b)), None) => {
                 Ok(AcsGeoidQuery::BlockGroup(s, Some(c), Some(t), Some(b)))
             }
+            (Some(G::CongressionalDistrict(s, d)), None) => {
+                Ok(AcsGeoidQuery::CongressionalDistrict(Some(s), Some(d)))
+            }
+            (Some(G::StateLegislativeDistrictUpperChamber(s, d)), None) => Ok(
+                AcsGeoidQuery::StateLegislativeDistrictUpperChamber(s, Some(d)),
+            ),
+            (Some(G::StateLegislativeDistrictLowerChamber(s, d)), None) => Ok(
+                AcsGeoidQuery::StateLegislativeDistrictLowerChamber(s, Some(d)),
+            ),
+            (Some(G::ZipCodeTabulationArea(z)), None) => {
+                Ok(AcsGeoidQuery::ZipCodeTabulationArea(Some(z)))
+            }
+            (Some(G::Block(s, c, t, b)), None) => Ok(AcsGeoidQuery::Block(s, c, Some(t), Some(b))),
+
+            // ~~ remaining combinations between districts/ZCTAs and other types ~~
+            (Some(g), Some(w)) => Err(CensusError::InvalidQuery(format!(
+                "cannot append a '{:?}' wildcard to a {:?} Geoid",
+                w,
+                g.geoid_type()
+            ))),
         }
     }
 
@@ -350,6 +459,65 @@ impl AcsGeoidQuery {
                     ),
                 }
             }
+            G::CongressionalDistrict(state, district) => match (state, district) {
+                (None, None) => String::from("&for=congressional%20district:*"),
+                (None, Some(d)) => format!(
+                    "&for=congressional%20district:{}&in=state:*",
+                    d.geoid_string()
+                ),
+                (Some(s), None) => format!(
+                    "&for=congressional%20district:*&in=state:{}",
+                    s.geoid_string()
+                ),
+                (Some(s), Some(d)) => format!(
+                    "&for=congressional%20district:{}&in=state:{}",
+                    d.geoid_string(),
+                    s.geoid_string()
+                ),
+            },
+            G::StateLegislativeDistrictUpperChamber(state, district) => match district {
+                None => format!(
+                    "&for=state%20legislative%20district%20(upper%20chamber):*&in=state:{}",
+                    state.geoid_string()
+                ),
+                Some(d) => format!(
+                    "&for=state%20legislative%20district%20(upper%20chamber):{}&in=state:{}",
+                    d.geoid_string(),
+                    state.geoid_string()
+                ),
+            },
+            G::StateLegislativeDistrictLowerChamber(state, district) => match district {
+                None => format!(
+                    "&for=state%20legislative%20district%20(lower%20chamber):*&in=state:{}",
+                    state.geoid_string()
+                ),
+                Some(d) => format!(
+                    "&for=state%20legislative%20district%20(lower%20chamber):{}&in=state:{}",
+                    d.geoid_string(),
+                    state.geoid_string()
+                ),
+            },
+            G::ZipCodeTabulationArea(zcta) => match zcta {
+                None => String::from("&for=zip%20code%20tabulation%20area:*"),
+                Some(z) => format!("&for=zip%20code%20tabulation%20area:{}", z.geoid_string()),
+            },
+            G::Block(state, county, tract, block) => {
+                let tract = tract
+                    .as_ref()
+                    .map(|t| t.geoid_string())
+                    .unwrap_or(String::from("*"));
+                let block = block
+                    .as_ref()
+                    .map(|b| b.geoid_string())
+                    .unwrap_or(String::from("*"));
+                format!(
+                    "&for=block:{}&in=state:{}&in=county:{}&in=tract:{}",
+                    block,
+                    state.geoid_string(),
+                    county.geoid_string(),
+                    tract
+                )
+            }
         }
     }
 
@@ -362,6 +530,15 @@ impl AcsGeoidQuery {
             G::Place(_, _) => GeoidType::Place,
             G::CensusTract(_, _, _) => GeoidType::CensusTract,
             G::BlockGroup(_, _, _, _) => GeoidType::BlockGroup,
+            G::CongressionalDistrict(_, _) => GeoidType::CongressionalDistrict,
+            G::StateLegislativeDistrictUpperChamber(_, _) => {
+                GeoidType::StateLegislativeDistrictUpperChamber
+            }
+            G::StateLegislativeDistrictLowerChamber(_, _) => {
+                GeoidType::StateLegislativeDistrictLowerChamber
+            }
+            G::ZipCodeTabulationArea(_) => GeoidType::ZipCodeTabulationArea,
+            G::Block(_, _, _, _) => GeoidType::Block,
         }
     }
 
@@ -387,6 +564,25 @@ impl AcsGeoidQuery {
                 String::from("tract"),
                 String::from("block group"),
             ],
+            G::CongressionalDistrict(_, _) => vec![
+                String::from("state"),
+                String::from("congressional district"),
+            ],
+            G::StateLegislativeDistrictUpperChamber(_, _) => vec![
+                String::from("state"),
+                String::from("state legislative district (upper chamber)"),
+            ],
+            G::StateLegislativeDistrictLowerChamber(_, _) => vec![
+                String::from("state"),
+                String::from("state legislative district (lower chamber)"),
+            ],
+            G::ZipCodeTabulationArea(_) => vec![String::from("zip code tabulation area")],
+            G::Block(_, _, _, _) => vec![
+                String::from("state"),
+                String::from("county"),
+                String::from("tract"),
+                String::from("block"),
+            ],
         }
     }
 
@@ -398,9 +594,41 @@ impl AcsGeoidQuery {
             AcsGeoidQuery::Place(_, _) => 2,
             AcsGeoidQuery::CensusTract(_, _, _) => 3,
             AcsGeoidQuery::BlockGroup(_, _, _, _) => 4,
+            AcsGeoidQuery::CongressionalDistrict(_, _) => 2,
+            AcsGeoidQuery::StateLegislativeDistrictUpperChamber(_, _) => 2,
+            AcsGeoidQuery::StateLegislativeDistrictLowerChamber(_, _) => 2,
+            AcsGeoidQuery::ZipCodeTabulationArea(_) => 1,
+            AcsGeoidQuery::Block(_, _, _, _) => 4,
+        }
+    }
+
+    /// the state or county GEOID that limits the scope of this query, if any.
+    pub fn scope_geoid(&self) -> Option<Geoid> {
+        use AcsGeoidQuery as Q;
+        match self {
+            Q::County(Some(st), Some(ct))
+            | Q::CountySubdivision(st, Some(ct), _)
+            | Q::CensusTract(st, Some(ct), _)
+            | Q::BlockGroup(st, Some(ct), _, _)
+            | Q::Block(st, ct, _, _) => Some(Geoid::County(*st, *ct)),
+            Q::State(Some(st))
+            | Q::County(Some(st), None)
+            | Q::CountySubdivision(st, None, _)
+            | Q::Place(Some(st), _)
+            | Q::CensusTract(st, None, _)
+            | Q::BlockGroup(st, None, _, _)
+            | Q::CongressionalDistrict(Some(st), _)
+            | Q::StateLegislativeDistrictUpperChamber(st, _)
+            | Q::StateLegislativeDistrictLowerChamber(st, _) => Some(Geoid::State(*st)),
+            _ => None,
         }
     }
 
+    /// the summary level (SUMLEV) of the rows returned by this query.
+    pub fn summary_level(&self) -> SummaryLevel {
+        self.get_geoid_type().summary_level()
+    }
+
     pub fn get_geoid_type(&self) -> GeoidType {
         match self {
             AcsGeoidQuery::State(_) => GeoidType::State,
@@ -409,6 +637,15 @@ impl AcsGeoidQuery {
             AcsGeoidQuery::Place(_, _) => GeoidType::Place,
             AcsGeoidQuery::CensusTract(_, _, _) => GeoidType::CensusTract,
             AcsGeoidQuery::BlockGroup(_, _, _, _) => GeoidType::BlockGroup,
+            AcsGeoidQuery::CongressionalDistrict(_, _) => GeoidType::CongressionalDistrict,
+            AcsGeoidQuery::StateLegislativeDistrictUpperChamber(_, _) => {
+                GeoidType::StateLegislativeDistrictUpperChamber
+            }
+            AcsGeoidQuery::StateLegislativeDistrictLowerChamber(_, _) => {
+                GeoidType::StateLegislativeDistrictLowerChamber
+            }
+            AcsGeoidQuery::ZipCodeTabulationArea(_) => GeoidType::ZipCodeTabulationArea,
+            AcsGeoidQuery::Block(_, _, _, _) => GeoidType::Block,
         }
     }
 
@@ -426,12 +663,17 @@ impl AcsGeoidQuery {
 }
 
 /// helper function to convert a vec of JSON values to their expected String values.
-fn as_strings(arr: &[serde_json::Value]) -> Result<Vec<String>, String> {
+fn as_strings(arr: &[serde_json::Value]) -> Result<Vec<String>, CensusError> {
     arr.iter()
         .map(|v| {
             v.as_str()
-                .ok_or_else(|| format!("raw geoid value should be string, found {}", v))
+                .ok_or_else(|| {
+                    CensusError::ResponseSchema(format!(
+                        "raw geoid value should be string, found {}",
+                        v
+                    ))
+                })
                 .map(String::from)
         })
-        .collect::<Result<Vec<_>, String>>()
+        .collect::<Result<Vec<_>, CensusError>>()
 }
@@ -0,0 +1,28 @@
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
+use std::fmt::Display;
+
+/// how aggregation treats ACS jam values, the sentinels that the ACS API publishes in place
+/// of estimates and margins of error that could not be computed (see
+/// [`us_census_core::model::acs::AcsJamValue`]). controlled margins of error are aggregated
+/// as zero under either policy.
+#[derive(Serialize, Deserialize, ValueEnum, Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum AcsJamPolicy {
+    /// aggregating a jam value is an error
+    #[default]
+    Error,
+    /// jam values are left out of aggregation, along with the margins of error of jammed
+    /// estimates (and vice versa) and the values weighted by a jammed value. variables
+    /// left out of every row of a group are reported as missing jam values
+    Skip,
+}
+
+impl Display for AcsJamPolicy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AcsJamPolicy::Error => write!(f, "error"),
+            AcsJamPolicy::Skip => write!(f, "skip"),
+        }
+    }
+}
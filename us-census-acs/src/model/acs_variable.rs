@@ -0,0 +1,87 @@
+use serde::{Deserialize, Serialize};
+use std::fmt::Display;
+use us_census_core::model::acs::{AcsMeasure, AcsPredicateType, AcsStatistic};
+
+/// metadata of an ACS variable from the `variables.json` of an ACS dataset, such as
+/// `B01001_001E` with label "Estimate!!Total:" and concept "Sex by Age".
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct AcsVariable {
+    #[serde(default)]
+    pub name: String,
+    pub label: String,
+    pub concept: Option<String>,
+    #[serde(rename = "predicateType")]
+    pub predicate_type: Option<String>,
+    pub group: Option<String>,
+    /// comma-delimited attribute variables, such as the margin of error and annotations
+    pub attributes: Option<String>,
+}
+
+impl AcsVariable {
+    /// the table group of this variable. variables outside of a table, such as `NAME`,
+    /// are in the group "N/A".
+    pub fn group_name(&self) -> Option<&str> {
+        self.group.as_deref().filter(|g| *g != "N/A")
+    }
+
+    /// the type of the values of this variable. None for the geography predicates, such as
+    /// `for`, which have their own predicate types.
+    pub fn value_type(&self) -> Option<AcsPredicateType> {
+        self.predicate_type.as_deref()?.parse().ok()
+    }
+
+    /// what this variable measures, from its name and label, see [`AcsMeasure::from_label`].
+    pub fn measure(&self) -> Option<AcsMeasure> {
+        AcsMeasure::from_label(&self.name, &self.label)
+    }
+
+    /// the statistic this variable reports, from its label, see [`AcsStatistic::from_label`].
+    pub fn statistic(&self) -> AcsStatistic {
+        AcsStatistic::from_label(&self.label)
+    }
+
+    /// true if each keyword appears in the name, label or concept, ignoring case.
+    pub fn matches(&self, keywords: &[&str]) -> bool {
+        let text = format!(
+            "{} {} {}",
+            self.name,
+            self.label,
+            self.concept.as_deref().unwrap_or_default()
+        )
+        .to_lowercase();
+        keywords
+            .iter()
+            .all(|keyword| text.contains(&keyword.to_lowercase()))
+    }
+}
+
+impl Display for AcsVariable {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {}", self.name, self.label)?;
+        if let Some(concept) = &self.concept {
+            write!(f, " ({})", concept)?;
+        }
+        Ok(())
+    }
+}
+
+/// metadata of an ACS table group from the `groups.json` of an ACS dataset, such as
+/// `B01001` "Sex by Age" with universe "Total population".
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct AcsGroup {
+    pub name: String,
+    pub description: String,
+    /// the API publishes this key as "universe " with a trailing space
+    #[serde(alias = "universe ")]
+    pub universe: Option<String>,
+}
+
+impl Display for AcsGroup {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {}", self.name, self.description)?;
+        if let Some(universe) = &self.universe {
+            write!(f, " (universe: {})", universe)?;
+        }
+        Ok(())
+    }
+}
@@ -1,5 +1,15 @@
+pub mod acs_aggregation_rule;
 pub mod acs_api_query_params;
+pub mod acs_catalog;
+pub mod acs_client_config;
 pub mod acs_geoid_query;
+pub mod acs_jam_policy;
+pub mod acs_variable;
 
+pub use acs_aggregation_rule::{AcsAggregationRule, AcsAggregationRules, AcsDistributionBin};
 pub use acs_api_query_params::AcsApiQueryParams;
+pub use acs_catalog::AcsCatalog;
+pub use acs_client_config::AcsClientConfig;
 pub use acs_geoid_query::AcsGeoidQuery;
+pub use acs_jam_policy::AcsJamPolicy;
+pub use acs_variable::{AcsGroup, AcsVariable};
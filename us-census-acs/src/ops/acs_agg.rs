@@ -1,20 +1,51 @@
+use std::collections::{BTreeMap, HashSet};
+use std::hash::Hash;
+
+use crate::model::acs_aggregation_rule::{pareto_median, AcsAggregationRule, AcsAggregationRules};
+use crate::model::AcsJamPolicy;
 use itertools::Itertools;
-use serde_json::json;
-use us_census_core::model::acs::AcsValue;
+use us_census_core::model::acs::acs_estimate::{
+    estimate_variable, is_moe_variable, pair_margins_of_error,
+};
+use us_census_core::model::acs::acs_jam_value::is_annotation_variable;
+use us_census_core::model::acs::{AcsJamValue, AcsValue, AcsVariableValue};
+use us_census_core::model::crosswalk::GeographyCrosswalk;
+use us_census_core::model::dataset::census_dataset::is_table_variable;
+use us_census_core::model::identifier::geography_vintage::dataset_vintage;
 use us_census_core::{
-    model::identifier::{Geoid, GeoidType},
-    ops::agg::aggregation_function::NumericAggregation,
+    error::CensusError,
+    model::identifier::{Geoid, GeoidType, PackedGeoid},
+    model::zone::ZoneMapping,
+    ops::agg::{self, aggregation_function::NumericAggregation, KeyedValues},
 };
 
 /// groups rows to the target Geoid hierarchy level and then
-/// applies the provided aggregation function to the grouped WacValues.
+/// applies the provided aggregation function to the grouped AcsValues.
+/// rows must share a single geography vintage, which is carried over to the
+/// aggregated values. weighted aggregation functions weight each row's values
+/// by that row's value for the weight variable. see [`agg::aggregate`].
+///
+/// variables are aggregated following the default [`AcsAggregationRules`]: the
+/// aggregation function and weight only apply to additive variables, averages are
+/// weighted by their universe variable, medians are interpolated from their
+/// distribution table, and queries with non-aggregatable variables fail.
+///
+/// margin of error variables (such as `B01001_001M`) are not aggregated like their
+/// estimates: they are combined by root-sum-of-squares for Sum, Mean and WeightedMean
+/// aggregations and dropped for all others, as they cannot be propagated. aggregated
+/// estimates are paired with their margins of error and reliability.
+///
+/// annotation variables (such as `B01001_001EA`) and variables outside of tables (such as
+/// the `NAME` and `GEO_ID` of `group(...)` queries) are not aggregated. jam values, the
+/// sentinels published for estimates and margins of error that could not be computed,
+/// fail the aggregation, see [`aggregate_acs_with_rules`] to skip them instead.
 ///
 /// # Example
 ///
 /// ```rust
 /// use us_census_core::model::identifier::{Geoid, GeoidType, fips};
 /// use us_census_core::ops::agg::NumericAggregation;
-/// use us_census_core::model::acs::AcsValue;
+/// use us_census_core::model::acs::{AcsMeasure, AcsStatistic, AcsValue};
 /// use us_census_acs::ops::acs_agg;
 /// use serde_json::json;
 ///
@@ -31,7 +62,7 @@ use us_census_core::{
 /// ];
 /// let target = GeoidType::State;
 /// let agg = NumericAggregation::Sum;
-/// let result = acs_agg::aggregate_acs(&rows, target, agg).unwrap();
+/// let result = acs_agg::aggregate_acs(&rows, target, agg, None).unwrap();
 /// let expected_cnt = json![150000.0];
 /// let expected = vec![
 ///   (
@@ -46,52 +77,585 @@ use us_census_core::{
 ///     assert_eq!(v_a.value, v_b.value);
 ///   }
 /// }
+///
+/// // per capita income is weighted by total population, even when summing
+/// let rows = vec![
+///   (
+///     Geoid::County(fips::State(08), fips::County(213)),
+///     vec![
+///       AcsValue::new(String::from("B19301_001E"), json![50000.0]),
+///       AcsValue::new(String::from("B01003_001E"), json![1000.0]),
+///     ]
+///   ),
+///   (
+///     Geoid::County(fips::State(08), fips::County(215)),
+///     vec![
+///       AcsValue::new(String::from("B19301_001E"), json![80000.0]),
+///       AcsValue::new(String::from("B01003_001E"), json![3000.0]),
+///     ]
+///   )
+/// ];
+/// let result = acs_agg::aggregate_acs(&rows, target, agg, None).unwrap();
+/// let income = result[0].1.iter().find(|v| v.name == "B19301_001E").unwrap();
+/// assert_eq!(income.as_number(), Some(72500.0));
+/// let population = result[0].1.iter().find(|v| v.name == "B01003_001E").unwrap();
+/// assert_eq!(population.as_number(), Some(4000.0));
+///
+/// // median household income cannot be aggregated without its distribution table
+/// let rows = vec![
+///   (
+///     Geoid::County(fips::State(08), fips::County(213)),
+///     vec![AcsValue::new(String::from("B19013_001E"), json![50000.0])]
+///   )
+/// ];
+/// assert!(acs_agg::aggregate_acs(&rows, target, agg, None).is_err());
+///
+/// // medians without a rule, typed from their label in the variable catalog, cannot be aggregated
+/// let rows = vec![
+///   (
+///     Geoid::County(fips::State(08), fips::County(213)),
+///     vec![
+///       AcsValue::new(String::from("B19113_001E"), json!["98000"])
+///         .with_statistic(Some(AcsStatistic::Median))
+///     ]
+///   )
+/// ];
+/// assert!(acs_agg::aggregate_acs(&rows, target, agg, None).is_err());
+///
+/// // subject table percents, typed from their label in the variable catalog, cannot be aggregated
+/// let rows = vec![
+///   (
+///     Geoid::County(fips::State(08), fips::County(213)),
+///     vec![
+///       AcsValue::new(String::from("S1701_C03_001E"), json!["9.5"])
+///         .with_measure(Some(AcsMeasure::Percent))
+///     ]
+///   )
+/// ];
+/// assert!(acs_agg::aggregate_acs(&rows, target, agg, None).is_err());
+///
+/// // the labels returned by group(...) queries are not aggregated
+/// let rows = vec![
+///   (
+///     Geoid::County(fips::State(08), fips::County(213)),
+///     vec![
+///       AcsValue::new(String::from("GEO_ID"), json!["0500000US08213"]),
+///       AcsValue::new(String::from("NAME"), json!["Some County, Colorado"]),
+///       AcsValue::new(String::from("B01001_001E"), json!["2000"]),
+///     ]
+///   )
+/// ];
+/// let result = acs_agg::aggregate_acs(&rows, target, agg, None).unwrap();
+/// assert_eq!(result[0].1.len(), 1);
+/// assert_eq!(result[0].1[0].name, "B01001_001E");
+///
+/// // margins of error are combined by root-sum-of-squares
+/// let rows = vec![
+///   (
+///     Geoid::County(fips::State(08), fips::County(213)),
+///     vec![
+///       AcsValue::new(String::from("B01001_001E"), json!["2000"]),
+///       AcsValue::new(String::from("B01001_001M"), json!["30"]),
+///     ]
+///   ),
+///   (
+///     Geoid::County(fips::State(08), fips::County(215)),
+///     vec![
+///       AcsValue::new(String::from("B01001_001E"), json!["1000"]),
+///       AcsValue::new(String::from("B01001_001M"), json!["40"]),
+///     ]
+///   )
+/// ];
+/// let result = acs_agg::aggregate_acs(&rows, target, NumericAggregation::Sum, None).unwrap();
+/// let (estimate, moe) = (&result[0].1[0], &result[0].1[1]);
+/// assert_eq!(estimate.as_number(), Some(3000.0));
+/// assert_eq!(estimate.moe, Some(50.0));
+/// assert_eq!(moe.as_number(), Some(50.0));
+/// assert!(estimate.reliability.is_some());
+///
+/// // suppressed estimates cannot be summed
+/// let rows = vec![
+///   (
+///     Geoid::County(fips::State(08), fips::County(213)),
+///     vec![AcsValue::new(String::from("B01001_001E"), json!["-666666666"])]
+///   )
+/// ];
+/// assert!(acs_agg::aggregate_acs(&rows, target, NumericAggregation::Sum, None).is_err());
 /// ```
 pub fn aggregate_acs(
     rows: &[(Geoid, Vec<AcsValue>)],
     target: GeoidType,
     agg: NumericAggregation,
-) -> Result<Vec<(Geoid, Vec<AcsValue>)>, String> {
-    // aggregate Geoids
-    let (geoid_oks, geoid_errs): (Vec<(Geoid, &Vec<AcsValue>)>, Vec<String>) = rows
+    weight: Option<&str>,
+) -> Result<Vec<(Geoid, Vec<AcsValue>)>, CensusError> {
+    let rules = AcsAggregationRules::default();
+    aggregate_acs_with_rules(rows, target, agg, weight, &rules)
+}
+
+/// [`aggregate_acs`] with a custom table of aggregation rules, including the policy for
+/// jam values. when jam values are skipped, variables that are skipped in every row of a
+/// group are reported as [`AcsJamValue::Missing`] for that group.
+///
+/// # Example
+///
+/// ```rust
+/// use us_census_core::model::identifier::{Geoid, GeoidType, fips};
+/// use us_census_core::ops::agg::NumericAggregation;
+/// use us_census_core::model::acs::{AcsJamValue, AcsValue};
+/// use us_census_acs::model::{AcsAggregationRule, AcsAggregationRules, AcsDistributionBin, AcsJamPolicy};
+/// use us_census_acs::ops::acs_agg;
+/// use serde_json::json;
+///
+/// // a household income distribution with two bins over two counties
+/// let rows = vec![
+///   (
+///     Geoid::County(fips::State(08), fips::County(213)),
+///     vec![
+///       AcsValue::new(String::from("B19013_001E"), json![9000.0]),
+///       AcsValue::new(String::from("B19001_002E"), json![60.0]),
+///       AcsValue::new(String::from("B19001_003E"), json![20.0]),
+///     ]
+///   ),
+///   (
+///     Geoid::County(fips::State(08), fips::County(215)),
+///     vec![
+///       AcsValue::new(String::from("B19013_001E"), json![14000.0]),
+///       AcsValue::new(String::from("B19001_002E"), json![10.0]),
+///       AcsValue::new(String::from("B19001_003E"), json![10.0]),
+///     ]
+///   )
+/// ];
+/// let (target, agg) = (GeoidType::State, NumericAggregation::Sum);
+///
+/// // interpolated from the 100 households, 70 of which earn less than $10,000
+/// let bins = vec![
+///   AcsDistributionBin::new("B19001_002E", 0.0, Some(10000.0)),
+///   AcsDistributionBin::new("B19001_003E", 10000.0, None),
+/// ];
+/// let rules = AcsAggregationRules::additive()
+///   .with_rule("B19013_001E", AcsAggregationRule::ParetoMedian { bins });
+/// let result = acs_agg::aggregate_acs_with_rules(&rows, target, agg, None, &rules).unwrap();
+/// assert_eq!(result[0].1[0].name, "B19013_001E");
+/// assert!((result[0].1[0].as_number().unwrap() - 7142.857).abs() < 1e-3);
+///
+/// // without rules, medians are aggregated like counts
+/// let rules = AcsAggregationRules::additive();
+/// let result = acs_agg::aggregate_acs_with_rules(&rows, target, agg, None, &rules).unwrap();
+/// assert_eq!(result[0].1[0].as_number(), Some(23000.0));
+///
+/// // skipping jam values leaves the suppressed estimate and its margin of error out of the sum
+/// let rows = vec![
+///   (
+///     Geoid::County(fips::State(08), fips::County(213)),
+///     vec![
+///       AcsValue::new(String::from("B01001_001E"), json!["-666666666"]),
+///       AcsValue::new(String::from("B01001_001M"), json!["-999999999"]),
+///     ]
+///   ),
+///   (
+///     Geoid::County(fips::State(08), fips::County(215)),
+///     vec![
+///       AcsValue::new(String::from("B01001_001E"), json!["1000"]),
+///       AcsValue::new(String::from("B01001_001M"), json!["-555555555"]),
+///     ]
+///   )
+/// ];
+/// let rules = AcsAggregationRules::default();
+/// assert!(acs_agg::aggregate_acs_with_rules(&rows, target, agg, None, &rules).is_err());
+/// let rules = rules.with_jam_policy(AcsJamPolicy::Skip);
+/// let result = acs_agg::aggregate_acs_with_rules(&rows, target, agg, None, &rules).unwrap();
+/// assert_eq!(result[0].1[0].as_number(), Some(1000.0));
+/// assert_eq!(result[0].1[0].moe, Some(0.0));
+///
+/// // an estimate that is jammed in every county of the state is missing
+/// let rows = vec![(
+///   Geoid::County(fips::State(08), fips::County(213)),
+///   vec![AcsValue::new(String::from("B01001_001E"), json!["-666666666"])]
+/// )];
+/// let result = acs_agg::aggregate_acs_with_rules(&rows, target, agg, None, &rules).unwrap();
+/// assert_eq!(result[0].1[0].name, "B01001_001E");
+/// assert_eq!(result[0].1[0].jam(), Some(AcsJamValue::Missing));
+/// ```
+pub fn aggregate_acs_with_rules(
+    rows: &[(Geoid, Vec<AcsValue>)],
+    target: GeoidType,
+    agg: NumericAggregation,
+    weight: Option<&str>,
+    rules: &AcsAggregationRules,
+) -> Result<Vec<(Geoid, Vec<AcsValue>)>, CensusError> {
+    let group = |geoid: &Geoid| {
+        let trunc_geoid = PackedGeoid::try_from(geoid)?.truncate_to_type(&target)?;
+        Ok(vec![(trunc_geoid, 1.0)])
+    };
+    let aggregated = aggregate_acs_by(rows, group, agg, weight, rules)?;
+    let result = aggregated
+        .into_iter()
+        .map(|(geoid, values)| (Geoid::from(geoid), values))
+        .collect();
+    Ok(result)
+}
+
+/// aggregates ACS rows to the zones of a [`ZoneMapping`] following the default
+/// [`AcsAggregationRules`], see [`aggregate_acs`] and [`agg::aggregate_zones`]. the
+/// values of GEOIDs split between zones are allocated to each zone by fraction: counts
+/// are scaled by the fraction and averages are weighted by it.
+///
+/// # Example
+///
+/// ```rust
+/// use us_census_core::model::identifier::Geoid;
+/// use us_census_core::model::zone::ZoneMapping;
+/// use us_census_core::ops::agg::NumericAggregation;
+/// use us_census_core::model::acs::AcsValue;
+/// use us_census_acs::ops::acs_agg;
+/// use serde_json::json;
+///
+/// let contents = "\
+/// geoid,zone,fraction
+/// 08059012000,downtown,1.0
+/// 08059012100,downtown,0.5
+/// 08059012100,north,0.5
+/// ";
+/// let zones = ZoneMapping::read_csv(contents.as_bytes()).unwrap();
+/// let tract = |geoid: &str, population: f64, moe: f64| {
+///   (
+///     geoid.parse::<Geoid>().unwrap(),
+///     vec![
+///       AcsValue::new(String::from("B01001_001E"), json![population]),
+///       AcsValue::new(String::from("B01001_001M"), json![moe]),
+///     ]
+///   )
+/// };
+/// let rows = vec![tract("08059012000", 3000.0, 300.0), tract("08059012100", 2000.0, 800.0)];
+/// let result = acs_agg::aggregate_acs_to_zones(&rows, &zones, NumericAggregation::Sum, None).unwrap();
+/// assert_eq!(result[0].0, "downtown");
+/// assert_eq!(result[0].1[0].as_number(), Some(4000.0));
+/// assert_eq!(result[0].1[0].moe, Some(500.0));
+/// assert_eq!(result[1].0, "north");
+/// assert_eq!(result[1].1[0].as_number(), Some(1000.0));
+/// ```
+pub fn aggregate_acs_to_zones(
+    rows: &[(Geoid, Vec<AcsValue>)],
+    zones: &ZoneMapping,
+    agg: NumericAggregation,
+    weight: Option<&str>,
+) -> Result<Vec<(String, Vec<AcsValue>)>, CensusError> {
+    let rules = AcsAggregationRules::default();
+    aggregate_acs_to_zones_with_rules(rows, zones, agg, weight, &rules)
+}
+
+/// [`aggregate_acs_to_zones`] with a custom table of aggregation rules, including the
+/// policy for jam values.
+pub fn aggregate_acs_to_zones_with_rules(
+    rows: &[(Geoid, Vec<AcsValue>)],
+    zones: &ZoneMapping,
+    agg: NumericAggregation,
+    weight: Option<&str>,
+    rules: &AcsAggregationRules,
+) -> Result<Vec<(String, Vec<AcsValue>)>, CensusError> {
+    aggregate_acs_by(rows, |geoid| zones.allocate(geoid), agg, weight, rules)
+}
+
+/// reallocates ACS rows between 2010 and 2020 census geographies with a crosswalk, see
+/// [`GeographyCrosswalk::reallocate`]. crosswalk weights only apportion counts, so all
+/// variables must be additive following the aggregation rules and the statistics and
+/// measures of the values typed from the variable catalog: rows with medians, averages or
+/// percentages fail. annotation variables and variables outside of tables are
+/// not reallocated, and margins of error are dropped, as they cannot be apportioned.
+///
+/// # Example
+///
+/// ```rust
+/// use us_census_core::model::acs::AcsValue;
+/// use us_census_core::model::crosswalk::{GeographyCrosswalk, RelationshipFile};
+/// use us_census_core::model::identifier::{GeographyVintage, Geoid, GeoidType};
+/// use us_census_acs::model::AcsAggregationRules;
+/// use us_census_acs::ops::acs_agg;
+/// use serde_json::json;
+///
+/// // 2010 tract 011900 was split into 2020 tracts 012000 and 012100
+/// let contents = "\
+/// GEOID_TRACT_20|GEOID_TRACT_10|AREALAND_PART|AREAWATER_PART
+/// 08059012000|08059011900|300|0
+/// 08059012100|08059011900|100|0
+/// ";
+/// let records = RelationshipFile::CensusTract.read(contents.as_bytes()).unwrap();
+/// let crosswalk = GeographyCrosswalk::area_weighted(
+///     &records, GeographyVintage::Census2010, GeoidType::CensusTract
+/// ).unwrap();
+/// let tract: Geoid = "08059011900".parse().unwrap();
+/// let rules = AcsAggregationRules::default();
+///
+/// let rows = vec![(
+///     tract.clone(),
+///     vec![
+///         AcsValue::new(String::from("B01001_001E"), json!["4000"]),
+///         AcsValue::new(String::from("B01001_001M"), json!["120"]),
+///     ],
+/// )];
+/// let result = acs_agg::reallocate_acs(&crosswalk, &rows, &rules).unwrap();
+/// assert_eq!(result[0].1.len(), 1);
+/// assert_eq!(result[0].1[0].as_number(), Some(3000.0));
+///
+/// // median household income cannot be apportioned by area
+/// let rows = vec![(
+///     tract,
+///     vec![AcsValue::new(String::from("B19013_001E"), json!["65000"])],
+/// )];
+/// assert!(acs_agg::reallocate_acs(&crosswalk, &rows, &rules).is_err());
+/// ```
+pub fn reallocate_acs(
+    crosswalk: &GeographyCrosswalk,
+    rows: &[(Geoid, Vec<AcsValue>)],
+    rules: &AcsAggregationRules,
+) -> Result<Vec<(Geoid, Vec<AcsValue>)>, CensusError> {
+    let rows = rows
         .iter()
         .map(|(geoid, values)| {
-            let trunc_geoid = geoid.truncate_geoid_to_type(&target)?;
-            Ok((trunc_geoid, values))
+            let values = values
+                .iter()
+                .filter(|v| is_data_variable(&v.name) && !is_moe_variable(&v.name))
+                .cloned()
+                .collect_vec();
+            (geoid.clone(), values)
         })
-        .partition_result();
-
-    if !geoid_errs.is_empty() {
-        let msg = geoid_errs.into_iter().unique().take(5).join("\n");
-        return Err(format!(
-            "errors during aggregation. first 5 unique errors: \n{}",
-            msg
-        ));
+        .collect_vec();
+    let rules = with_value_labels(rules, &rows);
+    let errors = rows
+        .iter()
+        .flat_map(|(_, values)| values.iter().map(|v| v.name.as_str()))
+        .unique()
+        .sorted()
+        .filter_map(|name| match rules.rule(name) {
+            AcsAggregationRule::Additive => None,
+            rule => Some(format!("{} is {}", name, rule)),
+        })
+        .collect_vec();
+    if !errors.is_empty() {
+        return Err(CensusError::Aggregation(format!(
+            "only additive variables can be reallocated between geography vintages:\n{}",
+            errors.join("\n")
+        )));
     }
+    crosswalk.reallocate(&rows)
+}
 
-    let mut geoids_grouped = vec![];
-    let grouping_iter = geoid_oks.into_iter().chunk_by(|(g, _)| g.clone());
-    for (geoid, grouped) in &grouping_iter {
-        let vs = grouped.into_iter().flat_map(|(_, v)| v).collect_vec();
-        geoids_grouped.push((geoid, vs));
-    }
+/// aggregates ACS rows to the groups assigned by `group` following the aggregation rules,
+/// see [`agg::aggregate_by`].
+fn aggregate_acs_by<K, G>(
+    rows: &[(Geoid, Vec<AcsValue>)],
+    group: G,
+    agg: NumericAggregation,
+    weight: Option<&str>,
+    rules: &AcsAggregationRules,
+) -> Result<Vec<(K, Vec<AcsValue>)>, CensusError>
+where
+    K: Clone + Eq + Hash + Ord + Send + Sync,
+    G: Fn(&Geoid) -> Result<Vec<(K, f64)>, CensusError> + Sync,
+{
+    let weight = match weight {
+        Some(name) if agg.is_weighted() => Some(String::from(name)),
+        None if agg.is_weighted() => {
+            return Err(CensusError::InvalidQuery(format!(
+                "{} aggregation requires a weight",
+                agg
+            )))
+        }
+        _ => None,
+    };
+    let variables = rows
+        .iter()
+        .flat_map(|(_, values)| values.iter().map(|v| v.name.as_str()))
+        .filter(|name| is_data_variable(name))
+        .collect::<HashSet<_>>();
+    let rules = &with_value_labels(rules, rows);
+    rules.validate(&variables)?;
+
+    // distribution bins are only weighted by allocation, so that they sum to counts
+    let bins = rules.distribution_bins();
+    let weight_of = |name: &String| match rules.rule(name) {
+        AcsAggregationRule::Additive if bins.contains(name.as_str()) => None,
+        AcsAggregationRule::Additive => weight.clone(),
+        AcsAggregationRule::WeightedBy { universe } => Some(universe.clone()),
+        _ => None,
+    };
+    let kept = apply_jam_policy(rows, rules.jam_policy(), weight_of)?;
+    let skipped = skipped_variables(rows, &kept, &group)?;
+    let aggregated = agg::aggregate_by(&kept, &group, weight_of, |keyed| {
+        Ok(reduce_acs(keyed, agg, rules))
+    })?;
 
-    // reduce by key
-    let reduced = geoids_grouped
+    // variables skipped in every row of a group are reported as missing
+    let vintage = dataset_vintage(rows)?;
+    let mut groups = aggregated.into_iter().collect::<BTreeMap<_, _>>();
+    for (group_key, name) in skipped.into_iter() {
+        let missing = AcsValue::new(name, AcsVariableValue::Jam(AcsJamValue::Missing));
+        groups
+            .entry(group_key)
+            .or_default()
+            .push(missing.with_vintage(vintage));
+    }
+    let result = groups
         .into_iter()
-        .map(|(geoid, values)| {
-            let xs = values.into_iter().chunk_by(|v| v.name.clone());
-            let mut agg_values = vec![];
-            for (name, values) in &xs {
-                let values = values.map(|v| {
-                  v.value.as_f64().ok_or_else(|| format!("ACS value for {} is not numeric (found {}) but user requested aggregation", name, v.value))
-                })
-                .collect::<Result<Vec<_>, _>>()?;
-                let aggregated = agg.aggregate(&mut values.into_iter());
-                agg_values.push(AcsValue::new(name, json![aggregated]));
+        .map(|(group_key, values)| (group_key, pair_margins_of_error(values)))
+        .collect();
+    Ok(result)
+}
+
+/// the variables of each group that are left out by the jam policy in all of the rows of
+/// the group, in group order.
+fn skipped_variables<K, G>(
+    rows: &[(Geoid, Vec<AcsValue>)],
+    kept: &[(Geoid, Vec<AcsValue>)],
+    group: &G,
+) -> Result<Vec<(K, String)>, CensusError>
+where
+    K: Clone + Eq + Hash + Ord,
+    G: Fn(&Geoid) -> Result<Vec<(K, f64)>, CensusError>,
+{
+    let grouped_variables = |rows: &[(Geoid, Vec<AcsValue>)]| {
+        let mut result = HashSet::new();
+        for (geoid, values) in rows.iter() {
+            for (group_key, _) in group(geoid)?.into_iter() {
+                for v in values.iter().filter(|v| is_data_variable(&v.name)) {
+                    result.insert((group_key.clone(), v.name.clone()));
+                }
             }
-            Ok((geoid, agg_values))
+        }
+        Ok::<_, CensusError>(result)
+    };
+    let before = grouped_variables(rows)?;
+    let after = grouped_variables(kept)?;
+    let skipped = before.difference(&after).cloned().sorted().collect_vec();
+    Ok(skipped)
+}
+
+/// the rules with the variables of values measured as percents or reporting medians and
+/// means, such as the percent columns of subject tables and median family income typed
+/// from the variable catalog, set to non-aggregatable.
+fn with_value_labels(
+    rules: &AcsAggregationRules,
+    rows: &[(Geoid, Vec<AcsValue>)],
+) -> AcsAggregationRules {
+    let values = rows.iter().flat_map(|(_, values)| values.iter());
+    let percents = values
+        .clone()
+        .filter(|v| v.measure().is_some_and(|m| m.is_percent()))
+        .map(|v| v.name.as_str())
+        .collect::<HashSet<_>>();
+    let statistics = values
+        .filter_map(|v| Some((v.name.as_str(), v.statistic()?)))
+        .collect::<HashSet<_>>();
+    rules
+        .clone()
+        .with_percents(percents)
+        .with_statistics(statistics)
+}
+
+/// true for the table variables that hold data, as opposed to annotations and the
+/// geography and label variables returned alongside them.
+fn is_data_variable(name: &str) -> bool {
+    is_table_variable(name) && !is_annotation_variable(name)
+}
+
+/// removes the annotation variables and variables outside of tables of each row and
+/// applies the jam policy to its jam values. skipping a jam value also skips its estimate
+/// or margin of error and the values that it weights.
+fn apply_jam_policy<W>(
+    rows: &[(Geoid, Vec<AcsValue>)],
+    policy: AcsJamPolicy,
+    weight_of: W,
+) -> Result<Vec<(Geoid, Vec<AcsValue>)>, CensusError>
+where
+    W: Fn(&String) -> Option<String>,
+{
+    let estimate_of = |name: &str| estimate_variable(name).unwrap_or_else(|| String::from(name));
+    let mut jammed_values = vec![];
+    let mut result = vec![];
+    for (geoid, values) in rows.iter() {
+        let values = values
+            .iter()
+            .filter(|v| is_data_variable(&v.name))
+            .collect_vec();
+        let jammed = values
+            .iter()
+            .filter(|v| v.is_jammed())
+            .map(|v| estimate_of(&v.name))
+            .collect::<HashSet<_>>();
+        if policy == AcsJamPolicy::Error {
+            jammed_values.extend(values.iter().filter(|v| v.is_jammed()).map(|v| (geoid, *v)));
+        }
+        let kept = values
+            .into_iter()
+            .filter(|v| !jammed.contains(&estimate_of(&v.name)))
+            .filter(|v| weight_of(&v.name).is_none_or(|w| !jammed.contains(&estimate_of(&w))))
+            .cloned()
+            .collect_vec();
+        result.push((geoid.clone(), kept));
+    }
+    if jammed_values.is_empty() {
+        return Ok(result);
+    }
+    let examples = jammed_values
+        .iter()
+        .take(5)
+        .filter_map(|(geoid, v)| {
+            let jam = v.jam()?;
+            let annotation = v.annotation.as_deref().unwrap_or(jam.annotation());
+            Some(format!(
+                "{} of geoid {}: {} ({})",
+                v.name, geoid, jam, annotation
+            ))
+        })
+        .join("\n");
+    Err(CensusError::Aggregation(format!(
+        "found {} ACS jam values that cannot be aggregated, such as:\n{}\nuse the skip jam policy to leave them out of aggregation",
+        jammed_values.len(),
+        examples
+    )))
+}
+
+/// reduces the values of each variable of a group following its aggregation rule.
+/// non-aggregatable variables are rejected before aggregation and are dropped here.
+fn reduce_acs(
+    keyed: KeyedValues<String>,
+    agg: NumericAggregation,
+    rules: &AcsAggregationRules,
+) -> Vec<(String, f64)> {
+    let weighted_mean = NumericAggregation::WeightedMean;
+    keyed
+        .iter()
+        .filter_map(|(name, pairs)| {
+            let mut pairs_iter = pairs.iter().copied();
+            let value = match (rules.rule(name), is_moe_variable(name)) {
+                (AcsAggregationRule::Additive, false) => {
+                    Some(agg.aggregate_allocated(&mut pairs_iter))
+                }
+                (AcsAggregationRule::Additive, true) => {
+                    agg.aggregate_margin_of_error(&mut pairs_iter)
+                }
+                (AcsAggregationRule::WeightedBy { .. }, false) => {
+                    Some(weighted_mean.aggregate_weighted(&mut pairs_iter))
+                }
+                (AcsAggregationRule::WeightedBy { .. }, true) => {
+                    weighted_mean.aggregate_margin_of_error(&mut pairs_iter)
+                }
+                (AcsAggregationRule::ParetoMedian { bins }, false) => {
+                    let counts = bins
+                        .iter()
+                        .map(|bin| {
+                            keyed
+                                .get(&bin.variable)
+                                .map(|bin_pairs| bin_pairs.iter().map(|(v, w)| v * w).sum())
+                                .unwrap_or(0.0)
+                        })
+                        .collect_vec();
+                    pareto_median(bins, &counts)
+                }
+                (AcsAggregationRule::ParetoMedian { .. }, true) => None,
+                (AcsAggregationRule::NonAggregatable, _) => None,
+            };
+            value.map(|v| (name.clone(), v))
         })
-        .collect::<Result<Vec<_>, String>>()?;
-    Ok(reduced)
+        .collect_vec()
 }
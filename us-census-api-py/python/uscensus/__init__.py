@@ -2,6 +2,20 @@ import us_census_api_py as api
 import pandas as pd
 import geopandas as gpd
 from shapely import wkt
+from us_census_api_py import (
+    CensusError,
+    IdentifierError,
+    InvalidQueryError,
+    HttpError,
+    HttpStatusError,
+    ResponseSchemaError,
+    ArchiveError,
+    ShapefileError,
+    AggregationError,
+    VintageMismatchError,
+    JoinError,
+    InternalError,
+)
 
 
 def run_wac_tiger(year: int, **kwds):
@@ -21,4 +35,19 @@ def run_acs_tiger(year: int, **kwds):
     return df
 
 
-__all__ = ("run_wac_tiger", "run_acs_tiger")
+__all__ = (
+    "run_wac_tiger",
+    "run_acs_tiger",
+    "CensusError",
+    "IdentifierError",
+    "InvalidQueryError",
+    "HttpError",
+    "HttpStatusError",
+    "ResponseSchemaError",
+    "ArchiveError",
+    "ShapefileError",
+    "AggregationError",
+    "VintageMismatchError",
+    "JoinError",
+    "InternalError",
+)
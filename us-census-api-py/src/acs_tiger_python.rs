@@ -1,14 +1,21 @@
-use itertools::Itertools;
+use crate::errors;
 use pyo3::types::IntoPyDict;
 use pyo3::types::PyDict;
 use pyo3::types::PyNone;
 use pyo3::{exceptions::PyException, prelude::*};
 use serde::de;
+use std::path::PathBuf;
 use us_census_acs::model::AcsApiQueryParams;
+use us_census_acs::model::AcsClientConfig;
 use us_census_acs::model::AcsGeoidQuery;
+use us_census_acs::model::AcsJamPolicy;
 use us_census_app::app::acs_tiger;
-use us_census_core::model::acs::AcsType;
-use us_census_core::model::identifier::Geoid;
+use us_census_core::model::acs::{AcsTable, AcsType, AcsVariableValue};
+use us_census_core::model::dataset::CensusDataset;
+use us_census_core::model::decennial::DecennialType;
+use us_census_core::model::derived::DerivedVariable;
+use us_census_core::model::identifier::{Geoid, GeoidType};
+use us_census_core::ops::agg::NumericAggregation;
 use wkt::ToWkt;
 
 #[pyfunction]
@@ -25,6 +32,24 @@ pub fn run_acs_tiger_python<'a>(
             Ok(AcsType::FiveYear)
         }
     })?;
+    let decennial: Option<DecennialType> = kwds.map_or(Ok(None), |m| {
+        if m.contains("decennial")? {
+            get_string_deserializable("decennial", m).map(Some)
+        } else {
+            Ok(None)
+        }
+    })?;
+    let acs_table: AcsTable = kwds.map_or(Ok(AcsTable::default()), |m| {
+        if m.contains("acs_table")? {
+            get_string_deserializable("acs_table", m)
+        } else {
+            Ok(AcsTable::default())
+        }
+    })?;
+    let dataset = match decennial {
+        Some(dec_type) => CensusDataset::from(dec_type),
+        None => CensusDataset::Acs(acs_type, acs_table),
+    };
     // default: populations total
     let acs_get_query = kwds.map_or(Ok(vec![String::from("B01001_001E")]), |m| {
         if m.contains("wac_segments")? {
@@ -35,11 +60,7 @@ pub fn run_acs_tiger_python<'a>(
     })?;
 
     let geoids_string: String = kwds.map_or(Ok(String::from("")), |m| get_string("geoids", m))?;
-    let geoids = geoids_string
-        .split(',')
-        .map(Geoid::try_from)
-        .collect::<Result<Vec<_>, String>>()
-        .map_err(|e| PyException::new_err(format!("failure decoding geoids argument: {}", e)))?;
+    let geoids = Geoid::parse_list(&geoids_string).map_err(errors::to_py_err)?;
 
     let wildcard = kwds.map_or(Ok(None), |m| {
         if m.contains("wildcard")? {
@@ -56,6 +77,77 @@ pub fn run_acs_tiger_python<'a>(
         }
     })?;
 
+    let agg_geoid_type: Option<GeoidType> = kwds.map_or(Ok(None), |m| {
+        if m.contains("agg_geoid_type")? {
+            get_string_deserializable("agg_geoid_type", m).map(Some)
+        } else {
+            Ok(None)
+        }
+    })?;
+    let agg_fn: NumericAggregation = kwds.map_or(Ok(NumericAggregation::default()), |m| {
+        if m.contains("agg_fn")? {
+            get_string_deserializable("agg_fn", m)
+        } else {
+            Ok(NumericAggregation::default())
+        }
+    })?;
+    let agg_weight = kwds.map_or(Ok(None), |m| {
+        if m.contains("agg_weight")? {
+            get_string("agg_weight", m).map(Some)
+        } else {
+            Ok(None)
+        }
+    })?;
+    let agg = agg_geoid_type.map(|g| (g, agg_fn, agg_weight));
+    let derived = kwds.map_or(Ok(vec![]), |m| {
+        if m.contains("derived")? {
+            DerivedVariable::parse_list(&get_string("derived", m)?).map_err(errors::to_py_err)
+        } else {
+            Ok(vec![])
+        }
+    })?;
+
+    let default_config = AcsClientConfig::default();
+    let concurrency = kwds.map_or(Ok(default_config.concurrency), |m| {
+        if m.contains("concurrency")? {
+            get_string_deserializable("concurrency", m)
+        } else {
+            Ok(default_config.concurrency)
+        }
+    })?;
+    let max_retries = kwds.map_or(Ok(default_config.max_retries), |m| {
+        if m.contains("max_retries")? {
+            get_string_deserializable("max_retries", m)
+        } else {
+            Ok(default_config.max_retries)
+        }
+    })?;
+    let jam_policy: AcsJamPolicy = kwds.map_or(Ok(AcsJamPolicy::default()), |m| {
+        if m.contains("jam_policy")? {
+            get_string_deserializable("jam_policy", m)
+        } else {
+            Ok(AcsJamPolicy::default())
+        }
+    })?;
+
+    let validate: bool = kwds.map_or(Ok(false), |m| {
+        if m.contains("validate")? {
+            get_string_deserializable("validate", m)
+        } else {
+            Ok(false)
+        }
+    })?;
+    let catalog_dir: Option<PathBuf> =
+        kwds.map_or(Ok(default_config.catalog_dir.clone()), |m| {
+            if m.contains("catalog_dir")? {
+                get_string("catalog_dir", m).map(|s| Some(PathBuf::from(s)))
+            } else {
+                Ok(default_config.catalog_dir.clone())
+            }
+        })?;
+
+    let config = AcsClientConfig::new(concurrency, max_retries).with_catalog_dir(catalog_dir);
+
     let runtime = tokio::runtime::Builder::new_current_thread()
         .enable_all()
         .build()
@@ -65,37 +157,60 @@ pub fn run_acs_tiger_python<'a>(
 
     // if no geoids are supplied we can run a query across the entire ACS dataset
     let queries = if geoids.is_empty() {
-        vec![AcsGeoidQuery::new(None, wildcard).unwrap()]
+        vec![AcsGeoidQuery::new(None, wildcard).map_err(errors::to_py_err)?]
     } else {
         geoids
             .into_iter()
-            .map(|g| AcsGeoidQuery::new(Some(g), wildcard).unwrap())
-            .collect_vec()
+            .map(|g| AcsGeoidQuery::new(Some(g), wildcard))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(errors::to_py_err)?
     };
 
-    // run ACS queries and collect ACS/TIGER joined Rows
-    let results = queries
+    let queries = queries
         .into_iter()
         .map(|q| {
-            let query_params = AcsApiQueryParams::new(
+            AcsApiQueryParams::new(
                 None,
                 year,
-                acs_type,
+                dataset,
                 acs_get_query.clone(),
                 q,
                 acs_api_token.clone(),
-            );
-            let future = acs_tiger::run(query_params);
-            let result = runtime.block_on(future).map_err(|e| {
-                PyException::new_err(format!("failure running LODES WAC + TIGER workflow: {}", e))
-            })?;
+            )
+        })
+        .collect::<Vec<_>>();
+
+    // validate variables against the ACS variable catalog before running queries
+    let queries = if validate {
+        let future = acs_tiger::validate_batch(queries, &config);
+        runtime.block_on(future).map_err(errors::to_py_err)?
+    } else {
+        queries
+    };
+
+    // run ACS queries and collect ACS/TIGER joined Rows
+    let results = queries
+        .into_iter()
+        .map(|query_params| {
+            let future = acs_tiger::run(query_params, agg.clone(), &derived, &config, jam_policy);
+            let result = runtime.block_on(future).map_err(errors::to_py_err)?;
+            if !result.query_errors.is_empty() {
+                return Err(errors::collection_to_py_err(
+                    "acs errors",
+                    &result.query_errors,
+                ));
+            }
             if !result.tiger_errors.is_empty() {
-                let msg = result.tiger_errors.iter().join(",");
-                return Err(PyException::new_err(format!("tiger errors: {}", msg)));
+                return Err(errors::collection_to_py_err(
+                    "tiger errors",
+                    &result.tiger_errors,
+                ));
             }
             if !result.join_errors.is_empty() {
-                let msg = result.join_errors.iter().join(",");
-                return Err(PyException::new_err(format!("join errors: {}", msg)));
+                return Err(errors::collection_to_py_err(
+                    "join errors",
+                    &result.join_errors,
+                ));
             }
 
             Ok(result.join_dataset)
@@ -108,30 +223,28 @@ pub fn run_acs_tiger_python<'a>(
         .flatten()
         .map(|row| {
             let dict: Bound<'_, PyDict> = PyDict::new_bound(py);
-            let value_json = row.acs_value.value;
+            let measure = row.acs_value.measure().map(|m| m.to_string());
+            let jam = row.acs_value.jam().map(|j| j.to_string());
 
             dict.set_item("geoid", row.geoid.to_string())?;
             dict.set_item("name", row.acs_value.name)?;
-            // dict.set_item("value", value_json.to_object(py)) <-- doesn't work, hence
-            // we unpack each JSON value and serialize via ToPyObject on it's underlying
-            // standard rust data structure implementation
-            match value_json {
-                serde_json::Value::Null => dict.set_item("value", PyNone::get_bound(py)),
-                serde_json::Value::Bool(b) => dict.set_item("value", b.to_object(py)),
-                serde_json::Value::Number(n) => match (n.as_f64(), n.as_i64()) {
-                    (None, Some(i)) => dict.set_item("value", i.to_object(py)),
-                    (Some(f), None) => dict.set_item("value", f.to_object(py)),
-                    _ => Err(PyException::new_err(format!(
-                        "cannot convert JSON Number to Python representation: {}",
-                        n
-                    ))),
-                },
-                serde_json::Value::String(s) => dict.set_item("value", s.to_object(py)),
-                _ => {
-                    // naive implementation - string serialize the value
-                    dict.set_item("value", value_json.to_string())
+            // jam values are reported in "jam", and have no value
+            match row.acs_value.value {
+                AcsVariableValue::Integer(i) => dict.set_item("value", i.to_object(py)),
+                AcsVariableValue::Float(f) => dict.set_item("value", f.to_object(py)),
+                AcsVariableValue::Label(s) => dict.set_item("value", s.to_object(py)),
+                AcsVariableValue::Jam(_) | AcsVariableValue::Null => {
+                    dict.set_item("value", PyNone::get_bound(py))
                 }
             }?;
+            dict.set_item("measure", measure)?;
+            dict.set_item("jam", jam)?;
+            dict.set_item("annotation", row.acs_value.annotation)?;
+            dict.set_item("moe", row.acs_value.moe)?;
+            dict.set_item(
+                "reliability",
+                row.acs_value.reliability.map(|r| r.to_string()),
+            )?;
             dict.set_item("geometry", row.geometry.to_wkt().to_string())?;
             Ok((row.geoid.to_string(), dict.to_object(py)))
         })
@@ -0,0 +1,79 @@
+// pyo3 0.22 create_exception! expansion checks the `gil-refs` feature of the calling crate
+#![allow(unexpected_cfgs)]
+use itertools::Itertools;
+use pyo3::{create_exception, exceptions::PyException, prelude::*};
+use us_census_core::error::CensusError as RustCensusError;
+
+// python exception hierarchy mirroring the variants of us_census_core::error::CensusError.
+// all exceptions share the CensusError base class so callers may catch everything at once.
+create_exception!(uscensus, CensusError, PyException);
+create_exception!(uscensus, IdentifierError, CensusError);
+create_exception!(uscensus, InvalidQueryError, CensusError);
+create_exception!(uscensus, HttpError, CensusError);
+create_exception!(uscensus, HttpStatusError, HttpError);
+create_exception!(uscensus, ResponseSchemaError, CensusError);
+create_exception!(uscensus, ArchiveError, CensusError);
+create_exception!(uscensus, ShapefileError, CensusError);
+create_exception!(uscensus, AggregationError, CensusError);
+create_exception!(uscensus, VintageMismatchError, CensusError);
+create_exception!(uscensus, JoinError, CensusError);
+create_exception!(uscensus, InternalError, CensusError);
+
+/// adds the exception classes to the python module.
+pub fn register(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    let py = m.py();
+    m.add("CensusError", py.get_type_bound::<CensusError>())?;
+    m.add("IdentifierError", py.get_type_bound::<IdentifierError>())?;
+    m.add(
+        "InvalidQueryError",
+        py.get_type_bound::<InvalidQueryError>(),
+    )?;
+    m.add("HttpError", py.get_type_bound::<HttpError>())?;
+    m.add("HttpStatusError", py.get_type_bound::<HttpStatusError>())?;
+    m.add(
+        "ResponseSchemaError",
+        py.get_type_bound::<ResponseSchemaError>(),
+    )?;
+    m.add("ArchiveError", py.get_type_bound::<ArchiveError>())?;
+    m.add("ShapefileError", py.get_type_bound::<ShapefileError>())?;
+    m.add("AggregationError", py.get_type_bound::<AggregationError>())?;
+    m.add(
+        "VintageMismatchError",
+        py.get_type_bound::<VintageMismatchError>(),
+    )?;
+    m.add("JoinError", py.get_type_bound::<JoinError>())?;
+    m.add("InternalError", py.get_type_bound::<InternalError>())?;
+    Ok(())
+}
+
+/// maps a rust CensusError to the matching python exception class.
+pub fn to_py_err(error: RustCensusError) -> PyErr {
+    let msg = error.to_string();
+    new_err(&error, msg)
+}
+
+/// collapses a collection of errors into a single python exception. the
+/// class of the first error is used and all messages are reported.
+pub fn collection_to_py_err(prefix: &str, errors: &[RustCensusError]) -> PyErr {
+    let msg = format!("{}: {}", prefix, errors.iter().join(","));
+    match errors.first() {
+        Some(first) => new_err(first, msg),
+        None => CensusError::new_err(msg),
+    }
+}
+
+fn new_err(error: &RustCensusError, msg: String) -> PyErr {
+    match error {
+        RustCensusError::Identifier(_) => IdentifierError::new_err(msg),
+        RustCensusError::InvalidQuery(_) => InvalidQueryError::new_err(msg),
+        RustCensusError::Http { .. } => HttpError::new_err(msg),
+        RustCensusError::HttpStatus { .. } => HttpStatusError::new_err(msg),
+        RustCensusError::ResponseSchema(_) => ResponseSchemaError::new_err(msg),
+        RustCensusError::Archive { .. } => ArchiveError::new_err(msg),
+        RustCensusError::Shapefile { .. } => ShapefileError::new_err(msg),
+        RustCensusError::Aggregation(_) => AggregationError::new_err(msg),
+        RustCensusError::VintageMismatch(_) => VintageMismatchError::new_err(msg),
+        RustCensusError::Join(_) => JoinError::new_err(msg),
+        RustCensusError::Internal(_) => InternalError::new_err(msg),
+    }
+}
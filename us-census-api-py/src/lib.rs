@@ -1,5 +1,8 @@
+// pyo3 0.22 #[pyfunction] expansion triggers this lint on PyResult return types
+#![allow(clippy::useless_conversion)]
 use pyo3::prelude::*;
 mod acs_tiger_python;
+mod errors;
 mod wac_tiger_python;
 
 #[pymodule]
@@ -7,5 +10,6 @@ mod wac_tiger_python;
 fn us_census_api_py(m: &Bound<'_, PyModule>) -> PyResult<()> {
     m.add_function(wrap_pyfunction!(wac_tiger_python::run_wac_tiger_python, m)?)?;
     m.add_function(wrap_pyfunction!(acs_tiger_python::run_acs_tiger_python, m)?)?;
+    errors::register(m)?;
     Ok(())
 }
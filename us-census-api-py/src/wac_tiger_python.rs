@@ -1,13 +1,16 @@
-use itertools::Itertools;
+use crate::errors;
 use pyo3::types::IntoPyDict;
 use pyo3::types::PyDict;
 use pyo3::{exceptions::PyException, prelude::*};
 use serde::de;
 use us_census_app::app::lodes_tiger;
+use us_census_core::model::derived::DerivedVariable;
 use us_census_core::model::identifier::Geoid;
 use us_census_core::model::lodes::{
     LodesDataset, LodesEdition, LodesJobType, WacSegment, WorkplaceSegment,
 };
+use us_census_core::ops::agg::NumericAggregation;
+use us_census_tiger::model::geometry_source::GeometrySource;
 use wkt::ToWkt;
 
 /// kwds example: https://pyo3.rs/main/function/signature#using-pyo3signature--
@@ -39,11 +42,7 @@ pub fn run_wac_tiger_python<'a>(
     let dataset = dataset_result?;
 
     let geoids_string: String = kwds.map_or(Ok(String::from("")), |m| get_string("geoids", m))?;
-    let geoids = geoids_string
-        .split(',')
-        .map(Geoid::try_from)
-        .collect::<Result<Vec<_>, String>>()
-        .map_err(|e| PyException::new_err(format!("failure decoding geoids argument: {}", e)))?;
+    let geoids = Geoid::parse_list(&geoids_string).map_err(errors::to_py_err)?;
     let wac_segments = kwds.map_or(Ok(vec![WacSegment::C000]), |m| {
         if m.contains("wac_segments")? {
             get_comma_separated("wac_segments", m)
@@ -59,24 +58,66 @@ pub fn run_wac_tiger_python<'a>(
         }
     })?;
 
-    let future = lodes_tiger::run(geoids, &wildcard, &wac_segments, dataset);
+    let agg_fn: NumericAggregation = kwds.map_or(Ok(NumericAggregation::default()), |m| {
+        if m.contains("agg_fn")? {
+            get_string_deserializable("agg_fn", m)
+        } else {
+            Ok(NumericAggregation::default())
+        }
+    })?;
+    let agg_weight = kwds.map_or(Ok(None), |m| {
+        if m.contains("agg_weight")? {
+            get_string_deserializable("agg_weight", m).map(Some)
+        } else {
+            Ok(None)
+        }
+    })?;
+
+    let derived = kwds.map_or(Ok(vec![]), |m| {
+        if m.contains("derived")? {
+            DerivedVariable::parse_list(&get_string("derived", m)?).map_err(errors::to_py_err)
+        } else {
+            Ok(vec![])
+        }
+    })?;
+
+    let geometry_source: GeometrySource = kwds.map_or(Ok(GeometrySource::default()), |m| {
+        if m.contains("geometry_source")? {
+            get_string_deserializable("geometry_source", m)
+        } else {
+            Ok(GeometrySource::default())
+        }
+    })?;
+
+    let future = lodes_tiger::run(
+        geoids,
+        &wildcard,
+        agg_fn,
+        agg_weight,
+        &wac_segments,
+        &derived,
+        geometry_source,
+        dataset,
+    );
     let runtime = tokio::runtime::Builder::new_current_thread()
         .enable_all()
         .build()
         .map_err(|e| {
             PyException::new_err(format!("failure creating async rust tokio runtime: {}", e))
         })?;
-    let result = runtime.block_on(future).map_err(|e| {
-        PyException::new_err(format!("failure running LODES WAC + TIGER workflow: {}", e))
-    })?;
+    let result = runtime.block_on(future).map_err(errors::to_py_err)?;
 
     if !result.tiger_errors.is_empty() {
-        let msg = result.tiger_errors.iter().join(",");
-        return Err(PyException::new_err(format!("tiger errors: {}", msg)));
+        return Err(errors::collection_to_py_err(
+            "tiger errors",
+            &result.tiger_errors,
+        ));
     }
     if !result.join_errors.is_empty() {
-        let msg = result.join_errors.iter().join(",");
-        return Err(PyException::new_err(format!("join errors: {}", msg)));
+        return Err(errors::collection_to_py_err(
+            "join errors",
+            &result.join_errors,
+        ));
     }
 
     let vals = result
@@ -90,6 +131,18 @@ pub fn run_wac_tiger_python<'a>(
             Ok((row.geoid.to_string(), dict.to_object(py)))
         })
         .collect::<PyResult<Vec<_>>>()?;
+    let derived_vals = result
+        .derived_dataset
+        .into_iter()
+        .map(|row| {
+            let dict = PyDict::new_bound(py);
+            dict.set_item("segment", row.value.name)?;
+            dict.set_item("value", row.value.value)?;
+            dict.set_item("geometry", row.geometry.to_wkt().to_string())?;
+            Ok((row.geoid.to_string(), dict.to_object(py)))
+        })
+        .collect::<PyResult<Vec<_>>>()?;
+    let vals = vals.into_iter().chain(derived_vals).collect::<Vec<_>>();
     let out_dict = vals.into_py_dict_bound(py);
     Ok(out_dict)
 }
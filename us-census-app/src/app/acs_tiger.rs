@@ -1,23 +1,49 @@
+use super::zone_tiger::{self, ZoneTigerResponse};
 use crate::model::acs_tiger_row::AcsTigerRow;
 use geo::Geometry;
 use itertools::Itertools;
 use reqwest::Client;
-use us_census_acs::api::acs_api;
+use std::collections::HashMap;
+use us_census_acs::api::{acs_api, acs_catalog_api};
 use us_census_acs::model::acs_api_query_params::AcsApiQueryParams;
+use us_census_acs::model::acs_client_config::AcsClientConfig;
+use us_census_acs::model::{AcsAggregationRules, AcsCatalog, AcsJamPolicy};
+use us_census_acs::ops::acs_agg;
+use us_census_core::error::CensusError;
+use us_census_core::model::acs::AcsValue;
+use us_census_core::model::dataset::CensusDataset;
+use us_census_core::model::derived::DerivedVariable;
+use us_census_core::model::fips::CountyRegistry;
 use us_census_core::model::identifier::geoid::Geoid;
+use us_census_core::model::identifier::geoid_type::GeoidType;
+use us_census_core::model::zone::ZoneMapping;
+use us_census_core::ops::agg::NumericAggregation;
+use us_census_core::ops::derive;
 use us_census_tiger::model::tiger_uri_builder::TigerUriBuilder;
 use us_census_tiger::ops::tiger_api;
 
 pub struct AcsTigerResponse {
     pub join_dataset: Vec<AcsTigerRow>,
-    pub tiger_errors: Vec<String>,
-    pub join_errors: Vec<String>,
+    pub query_errors: Vec<CensusError>,
+    pub tiger_errors: Vec<CensusError>,
+    pub join_errors: Vec<CensusError>,
+}
+
+/// the zones of an ACS zone query, along with the ACS queries that failed.
+pub struct AcsZoneTigerResponse {
+    pub zone_dataset: ZoneTigerResponse<AcsValue>,
+    pub query_errors: Vec<CensusError>,
 }
 
 /// runs a query to ACS. the result will include a list of GEOIDs alongside
 /// ACS data. all GEOIDs are used to run a set of downloads from the TIGER/Lines
 /// datasets. the geometries from TIGER are combined with the ACS data producing
-/// AcsTigerRows.
+/// AcsTigerRows. if an aggregation is provided, the ACS data is aggregated to its
+/// GeoidType with its aggregation function, weighted by the optional weight variable,
+/// before downloading geometries, treating ACS jam values following the jam policy.
+/// derived variables are evaluated after aggregation and
+/// appended to the ACS values of each GEOID. ACS queries that fail after the retries of
+/// the client config are reported as query errors.
 ///
 /// # Example
 ///
@@ -44,7 +70,7 @@ pub struct AcsTigerResponse {
 ///     None,
 /// );
 /// # tokio_test::block_on(async {
-///     let res = acs_tiger::run(&query_params).await.unwrap();
+///     let res = acs_tiger::run(&query_params, None, &[], &AcsClientConfig::default(), AcsJamPolicy::Error).await.unwrap();
 ///     println!(
 ///         "found {} responses, {}/{} errors",
 ///         res.join_dataset.len(),
@@ -57,35 +83,75 @@ pub struct AcsTigerResponse {
 /// # })
 ///
 /// ```
-pub async fn run(query: AcsApiQueryParams) -> Result<AcsTigerResponse, String> {
-    run_batch(vec![query]).await
+pub async fn run(
+    query: AcsApiQueryParams,
+    agg: Option<(GeoidType, NumericAggregation, Option<String>)>,
+    derived: &[DerivedVariable],
+    config: &AcsClientConfig,
+    jam_policy: AcsJamPolicy,
+) -> Result<AcsTigerResponse, CensusError> {
+    run_batch(vec![query], agg, derived, config, jam_policy).await
 }
 
-pub async fn run_batch(queries: Vec<AcsApiQueryParams>) -> Result<AcsTigerResponse, String> {
+pub async fn run_batch(
+    queries: Vec<AcsApiQueryParams>,
+    agg: Option<(GeoidType, NumericAggregation, Option<String>)>,
+    derived: &[DerivedVariable],
+    config: &AcsClientConfig,
+    jam_policy: AcsJamPolicy,
+) -> Result<AcsTigerResponse, CensusError> {
     let client: Client = Client::new();
 
-    // todo: run tiger downloads for all requested years
-    let year = match &queries.iter().map(|q| q.year).unique().collect_vec()[..] {
-        [one_year] => Ok(*one_year),
-        years => Err(format!(
-            "acs.run_batch with queries should be run with one matching year for optimal geometry downloads, but found the following years: [{}]",
-            years.iter().map(|y| format!("{}", y)).join(",")
-        )),
-    }?;
+    let year = batch_year(&queries)?;
 
-    let acs_rows = acs_api::batch_run(&client, queries).await?;
+    // reject unknown states and counties before calling the ACS API
+    let registry = CountyRegistry::embedded();
+    for geoid in queries.iter().filter_map(|q| q.for_query.scope_geoid()) {
+        registry.validate(&geoid, Some(year))?;
+    }
+
+    let queries = with_catalogs(&client, queries, config).await;
+    let (acs_response, query_errors) = acs_api::batch_run(&client, queries, config).await?;
+    let rules = AcsAggregationRules::default().with_jam_policy(jam_policy);
+    let acs_rows = match agg {
+        Some((geoid_type, agg_fn, weight)) => acs_agg::aggregate_acs_with_rules(
+            &acs_response,
+            geoid_type,
+            agg_fn,
+            weight.as_deref(),
+            &rules,
+        )?,
+        None => acs_response,
+    };
+    let acs_rows = match derived.is_empty() {
+        true => acs_rows,
+        false => {
+            let derived_rows = derive::derive(&acs_rows, derived)?;
+            acs_rows
+                .into_iter()
+                .zip(derived_rows)
+                .map(|((geoid, mut values), (_, derived_values))| {
+                    values.extend(derived_values.into_iter().map(AcsValue::from));
+                    (geoid, values)
+                })
+                .collect_vec()
+        }
+    };
 
     // execute TIGER/Lines downloads
     let tiger_uri_builder = TigerUriBuilder::new(year)?;
     let geoids = &acs_rows.iter().map(|(geoid, _)| geoid).collect_vec();
     let tiger_response = tiger_api::run(&client, &tiger_uri_builder, geoids).await?;
 
-    type NestedResult = (Vec<Vec<(Geoid, Geometry<f64>)>>, Vec<String>);
+    type NestedResult = (Vec<Vec<(Geoid, Geometry<f64>)>>, Vec<CensusError>);
     let (tiger_rows_nested, tiger_errors): NestedResult =
         tiger_response.into_iter().partition_result();
 
-    let (join_dataset, join_errors) =
-        crate::ops::join::dataset_with_geometries(acs_rows, tiger_rows_nested)?;
+    let (join_dataset, join_errors) = crate::ops::join::dataset_with_geometries(
+        acs_rows,
+        tiger_rows_nested,
+        Some(tiger_uri_builder.geography_vintage()),
+    )?;
     let output_dataset = join_dataset
         .into_iter()
         .flat_map(|(geoid, geometry, acs_values)| {
@@ -97,8 +163,146 @@ pub async fn run_batch(queries: Vec<AcsApiQueryParams>) -> Result<AcsTigerRespon
 
     let result = AcsTigerResponse {
         join_dataset: output_dataset,
+        query_errors,
         tiger_errors,
         join_errors,
     };
     Ok(result)
 }
+
+/// runs a batch of ACS queries aggregated to the user-defined zones of a zone mapping.
+/// the queries should cover the mapped GEOIDs at or below the mapping GeoidType. values
+/// of GEOIDs that are split between zones are allocated by their allocation fractions,
+/// and the TIGER/Lines geometries of the mapped GEOIDs are dissolved into zone polygons.
+/// ACS jam values are treated following the jam policy, and ACS queries that fail after
+/// the retries of the client config are reported as query errors.
+pub async fn run_batch_zones(
+    queries: Vec<AcsApiQueryParams>,
+    zones: &ZoneMapping,
+    agg_fn: NumericAggregation,
+    weight: Option<String>,
+    derived: &[DerivedVariable],
+    config: &AcsClientConfig,
+    jam_policy: AcsJamPolicy,
+) -> Result<AcsZoneTigerResponse, CensusError> {
+    let client: Client = Client::new();
+    let year = batch_year(&queries)?;
+
+    let registry = CountyRegistry::embedded();
+    for geoid in zones.geoids() {
+        registry.validate(geoid, Some(year))?;
+    }
+
+    let queries = with_catalogs(&client, queries, config).await;
+    let (acs_response, query_errors) = acs_api::batch_run(&client, queries, config).await?;
+    let rules = AcsAggregationRules::default().with_jam_policy(jam_policy);
+    let zone_rows = acs_agg::aggregate_acs_to_zones_with_rules(
+        &acs_response,
+        zones,
+        agg_fn,
+        weight.as_deref(),
+        &rules,
+    )?;
+
+    let zone_dataset = zone_tiger::join_zones(&client, year, zone_rows, zones, derived).await?;
+    let result = AcsZoneTigerResponse {
+        zone_dataset,
+        query_errors,
+    };
+    Ok(result)
+}
+
+// todo: run tiger downloads for all requested years
+fn batch_year(queries: &[AcsApiQueryParams]) -> Result<u64, CensusError> {
+    match &queries.iter().map(|q| q.year).unique().collect_vec()[..] {
+        [one_year] => Ok(*one_year),
+        years => Err(CensusError::InvalidQuery(format!(
+            "acs.run_batch with queries should be run with one matching year for optimal geometry downloads, but found the following years: [{}]",
+            years.iter().map(|y| format!("{}", y)).join(",")
+        ))),
+    }
+}
+
+/// validates the variables of a batch of ACS queries against the variable catalog of their
+/// datasets before any queries are sent, and returns the queries with the value types of
+/// their variables, which are used to parse responses. catalogs are cached in the catalog
+/// directory of the client config, and failing to load one is an error.
+pub async fn validate_batch(
+    queries: Vec<AcsApiQueryParams>,
+    config: &AcsClientConfig,
+) -> Result<Vec<AcsApiQueryParams>, CensusError> {
+    let client: Client = Client::new();
+    let catalogs = batch_catalogs(&client, &queries, config)
+        .await
+        .into_iter()
+        .map(|(key, catalog)| Ok((key, catalog?)))
+        .collect::<Result<HashMap<_, _>, CensusError>>()?;
+    let mut result = vec![];
+    for query in queries.into_iter() {
+        let catalog = &catalogs[&catalog_key(&query)];
+        catalog.validate(&query.get_query)?;
+        result.push(query.with_catalog(catalog));
+    }
+    Ok(result)
+}
+
+/// sets the value types of the variables of each query from the variable catalog of its
+/// dataset, unless they are already set, such as by [`validate_batch`]. values of variables
+/// missing from the catalog are inferred, as are all values of a dataset whose catalog
+/// cannot be loaded, which is logged as a warning.
+async fn with_catalogs(
+    client: &Client,
+    queries: Vec<AcsApiQueryParams>,
+    config: &AcsClientConfig,
+) -> Vec<AcsApiQueryParams> {
+    let untyped = queries
+        .iter()
+        .filter(|q| q.predicate_types.is_empty())
+        .cloned()
+        .collect_vec();
+    let catalogs = batch_catalogs(client, &untyped, config)
+        .await
+        .into_iter()
+        .filter_map(|((base_url, year, dataset), catalog)| match catalog {
+            Ok(catalog) => Some(((base_url, year, dataset), catalog)),
+            Err(e) => {
+                log::warn!(
+                    "failure loading the variable catalog of {} {}, inferring value types: {}",
+                    year,
+                    dataset,
+                    e
+                );
+                None
+            }
+        })
+        .collect::<HashMap<_, _>>();
+    queries
+        .into_iter()
+        .map(|query| match catalogs.get(&catalog_key(&query)) {
+            Some(catalog) if query.predicate_types.is_empty() => query.with_catalog(catalog),
+            _ => query,
+        })
+        .collect_vec()
+}
+
+type CatalogKey = (Option<String>, u64, CensusDataset);
+
+fn catalog_key(query: &AcsApiQueryParams) -> CatalogKey {
+    (query.base_url.clone(), query.year, query.dataset)
+}
+
+/// loads the variable catalog of each dataset of a batch of queries once.
+async fn batch_catalogs(
+    client: &Client,
+    queries: &[AcsApiQueryParams],
+    config: &AcsClientConfig,
+) -> Vec<(CatalogKey, Result<AcsCatalog, CensusError>)> {
+    let mut catalogs = vec![];
+    for key in queries.iter().map(catalog_key).unique() {
+        let (base_url, year, dataset) = &key;
+        let catalog =
+            acs_catalog_api::run(client, base_url.as_deref(), *year, *dataset, config).await;
+        catalogs.push((key, catalog));
+    }
+    catalogs
+}
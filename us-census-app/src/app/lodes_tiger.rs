@@ -1,26 +1,39 @@
+use super::zone_tiger::{self, ZoneTigerResponse};
+use crate::model::derived_tiger_row::DerivedTigerRow;
 use crate::model::lodes_wac_tiger_row::LodesWacTigerRow;
 use geo::Geometry;
 use itertools::Itertools;
 use reqwest::Client;
-use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use us_census_core::error::CensusError;
+use us_census_core::model::derived::{DerivedValue, DerivedVariable};
+use us_census_core::model::fips::{CountyRegistry, StateScope};
 use us_census_core::model::identifier::geoid::Geoid;
 use us_census_core::model::identifier::geoid_type::GeoidType;
-use us_census_core::model::lodes::{LodesDataset, WacSegment};
+use us_census_core::model::identifier::{GeoidSet, PackedGeoid};
+use us_census_core::model::lodes::{LodesDataset, WacSegment, WacValue};
+use us_census_core::model::zone::ZoneMapping;
+use us_census_core::ops::agg::NumericAggregation;
+use us_census_core::ops::derive;
 use us_census_lehd::api::lodes_api;
+use us_census_lehd::ops::lodes_agg;
+use us_census_tiger::model::geometry_source::GeometrySource;
 use us_census_tiger::model::tiger_uri_builder::TigerUriBuilder;
-use us_census_tiger::ops::tiger_api;
+use us_census_tiger::ops::{dissolve, tiger_api};
 
-#[derive(Serialize, Deserialize)]
 pub struct LodesTigerResponse {
     pub join_dataset: Vec<LodesWacTigerRow>,
-    pub tiger_errors: Vec<String>,
-    pub join_errors: Vec<String>,
+    pub derived_dataset: Vec<DerivedTigerRow>,
+    pub tiger_errors: Vec<CensusError>,
+    pub join_errors: Vec<CensusError>,
 }
 
 /// runs a query to ACS. the result will include a list of GEOIDs alongside
 /// ACS data. all GEOIDs are used to run a set of downloads from the TIGER/Lines
 /// datasets. the geometries from TIGER are combined with the ACS data producing
-/// AcsTigerRows.
+/// AcsTigerRows. when aggregating with GeometrySource::Dissolve, the geometries of all
+/// blocks in each aggregated GEOID are downloaded and dissolved into its geometry,
+/// including the blocks without jobs, which are missing from the WAC files.
 ///
 /// # Example
 ///
@@ -51,56 +64,133 @@ pub struct LodesTigerResponse {
 /// # })
 ///
 /// ```
+#[allow(clippy::too_many_arguments)]
 pub async fn run(
     geoids: Vec<Geoid>,
     agg_geoid_type: &Option<GeoidType>,
+    agg_fn: NumericAggregation,
+    agg_weight: Option<WacSegment>,
     wac_segments: &[WacSegment],
+    derived: &[DerivedVariable],
+    geometry_source: GeometrySource,
     dataset: LodesDataset,
-) -> Result<LodesTigerResponse, String> {
+) -> Result<LodesTigerResponse, CensusError> {
     // input: i have a set of geoids that describe a region. i want to download
     // lodes data and aggregate it to some GeoidType.
-    // use the LODES dataset argument to build URIs for all LODES downloads
-    // if the user did not provide geoids, use all states
-    let geoids = match geoids.len() {
-        0 => Geoid::all_states(),
-        _ => geoids
-            .into_iter()
+    // use the LODES dataset argument to build URIs for all LODES downloads.
+    // LODES files are published by state, so we download the states containing the
+    // input geoids. if the user did not provide geoids, use all states
+    let registry = CountyRegistry::embedded();
+    for geoid in geoids.iter() {
+        registry.validate(geoid, Some(dataset.tiger_year()))?;
+    }
+    let filter = GeoidSet::try_from(geoids)?;
+    let states = match filter.len() {
+        0 => Geoid::all_states(&StateScope::default()),
+        _ => filter
+            .iter()
             .map(|g| g.to_state())
+            .collect::<Result<Vec<_>, _>>()?
+            .into_iter()
             .unique()
             .collect_vec(),
     };
-    let lodes_queries = geoids
+    let lodes_queries = states
         .iter()
         .map(|geoid| dataset.create_uri(geoid))
         .collect::<Result<Vec<_>, _>>()?;
 
-    let agg_fn = us_census_core::ops::agg::NumericAggregation::Sum;
-    let agg = agg_geoid_type.map(|g| (g, agg_fn));
-
     // execute LODES downloads
 
     let client: Client = Client::new();
-    let lodes_rows = lodes_api::run_wac(&client, &lodes_queries, wac_segments, agg).await?;
+    let lodes_rows = lodes_api::run_wac(
+        &client,
+        &lodes_queries,
+        wac_segments,
+        Some(dataset.geography_vintage()?),
+        None,
+    )
+    .await?;
 
-    // filter result. LODES collects by State. here we only accept rows where the
-    // input geoids are the (FIPS hierarchical) parent.
-    let lodes_filtered = lodes_rows
-        .into_iter()
-        .filter(|(c, _)| geoids.iter().any(|p| p.is_parent_of(c)))
-        .collect_vec();
+    // filter result. LODES collects by State. here we only accept block rows that are
+    // covered by the input geoids, before aggregating to the requested GeoidType.
+    let lodes_blocks = match filter.is_empty() {
+        true => lodes_rows,
+        false => lodes_rows
+            .into_iter()
+            .filter(|(c, _)| filter.covers(c))
+            .collect_vec(),
+    };
+    // when dissolving, geometries are downloaded for the blocks instead of the aggregates
+    let dissolve_type = match (agg_geoid_type, geometry_source) {
+        (Some(g), GeometrySource::Dissolve) => Some(*g),
+        _ => None,
+    };
+    let block_geoids = match dissolve_type {
+        Some(_) => lodes_blocks
+            .iter()
+            .map(|(geoid, _)| geoid.clone())
+            .collect_vec(),
+        None => vec![],
+    };
+    let lodes_filtered = match agg_geoid_type {
+        Some(g) => lodes_agg::aggregate_lodes_wac(&lodes_blocks, *g, agg_fn, agg_weight)?,
+        None => lodes_blocks,
+    };
+
+    // evaluate derived variables over the WAC segments of each GEOID
+    let mut derived_lookup: HashMap<Geoid, Vec<DerivedValue>> = match derived.is_empty() {
+        true => HashMap::new(),
+        false => derive::derive(&lodes_filtered, derived)?
+            .into_iter()
+            .collect(),
+    };
 
     // execute TIGER/Lines downloads selecting a data vintage based on the LODES edition chosen
     let tiger_year = dataset.tiger_year();
     let tiger_uri_builder = TigerUriBuilder::new(tiger_year)?;
-    let lodes_geoids = &lodes_filtered.iter().map(|(geoid, _)| geoid).collect_vec();
-    let tiger_response = tiger_api::run(&client, &tiger_uri_builder, lodes_geoids).await?;
+    let lodes_geoids = match dissolve_type {
+        Some(_) => block_geoids.iter().collect_vec(),
+        None => lodes_filtered.iter().map(|(geoid, _)| geoid).collect_vec(),
+    };
+    let tiger_response = match dissolve_type {
+        Some(_) => {
+            let parents = lodes_filtered
+                .iter()
+                .map(|(geoid, _)| PackedGeoid::try_from(geoid))
+                .collect::<Result<GeoidSet, _>>()?;
+            tiger_api::run_within(&client, &tiger_uri_builder, &lodes_geoids, &parents).await?
+        }
+        None => tiger_api::run(&client, &tiger_uri_builder, &lodes_geoids).await?,
+    };
 
-    type NestedResult = (Vec<Vec<(Geoid, Geometry<f64>)>>, Vec<String>);
-    let (tiger_rows_nested, tiger_errors): NestedResult =
+    type NestedResult = (Vec<Vec<(Geoid, Geometry<f64>)>>, Vec<CensusError>);
+    let (tiger_rows_nested, mut tiger_errors): NestedResult =
         tiger_response.into_iter().partition_result();
+    let tiger_rows_nested = match dissolve_type {
+        Some(geoid_type) => {
+            let blocks = tiger_rows_nested.into_iter().flatten().collect_vec();
+            let (dissolved, dissolve_errors) = dissolve::dissolve_to_type(blocks, geoid_type);
+            tiger_errors.extend(dissolve_errors);
+            vec![dissolved]
+        }
+        None => tiger_rows_nested,
+    };
 
-    let (join_dataset, join_errors) =
-        crate::ops::join::dataset_with_geometries(lodes_filtered, tiger_rows_nested)?;
+    let (join_dataset, join_errors) = crate::ops::join::dataset_with_geometries(
+        lodes_filtered,
+        tiger_rows_nested,
+        Some(tiger_uri_builder.geography_vintage()),
+    )?;
+    let derived_dataset = join_dataset
+        .iter()
+        .flat_map(|(geoid, geometry, _)| {
+            let derived_values = derived_lookup.remove(geoid).unwrap_or_default();
+            derived_values
+                .into_iter()
+                .map(|value| DerivedTigerRow::new(geoid.clone(), value, geometry.clone()))
+        })
+        .collect_vec();
     let output_dataset = join_dataset
         .into_iter()
         .flat_map(|(geoid, geometry, lodes_values)| {
@@ -112,8 +202,52 @@ pub async fn run(
 
     let result = LodesTigerResponse {
         join_dataset: output_dataset,
+        derived_dataset,
         tiger_errors,
         join_errors,
     };
     Ok(result)
 }
+
+/// runs a LODES WAC query aggregated to the user-defined zones of a zone mapping. the
+/// states containing the mapped GEOIDs are downloaded, blocks outside of all zones are
+/// dropped, and the TIGER/Lines geometries of the mapped GEOIDs are dissolved into
+/// zone polygons.
+pub async fn run_zones(
+    zones: &ZoneMapping,
+    agg_fn: NumericAggregation,
+    agg_weight: Option<WacSegment>,
+    wac_segments: &[WacSegment],
+    derived: &[DerivedVariable],
+    dataset: LodesDataset,
+) -> Result<ZoneTigerResponse<WacValue>, CensusError> {
+    let registry = CountyRegistry::embedded();
+    for geoid in zones.geoids() {
+        registry.validate(geoid, Some(dataset.tiger_year()))?;
+    }
+    let states = zones
+        .geoids()
+        .map(|g| g.to_state())
+        .collect::<Result<Vec<_>, _>>()?
+        .into_iter()
+        .unique()
+        .collect_vec();
+    let lodes_queries = states
+        .iter()
+        .map(|geoid| dataset.create_uri(geoid))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let client: Client = Client::new();
+    let lodes_rows = lodes_api::run_wac(
+        &client,
+        &lodes_queries,
+        wac_segments,
+        Some(dataset.geography_vintage()?),
+        None,
+    )
+    .await?;
+    let zone_rows =
+        lodes_agg::aggregate_lodes_wac_to_zones(&lodes_rows, zones, agg_fn, agg_weight)?;
+
+    zone_tiger::join_zones(&client, dataset.tiger_year(), zone_rows, zones, derived).await
+}
@@ -1,12 +1,19 @@
 use super::lodes_tiger;
 use crate::model::lodes_tiger_output_row::LodesTigerOutputRow;
-use clap::{Args, Parser, Subcommand};
-use itertools::Itertools;
+use crate::model::zone_tiger_output_row::ZoneTigerOutputRow;
+use clap::error::ErrorKind;
+use clap::{Args, CommandFactory, Parser, Subcommand};
+use std::fmt::Display;
+use std::path::PathBuf;
+use us_census_core::model::derived::DerivedVariable;
 use us_census_core::model::identifier::geoid_type::GeoidType;
 use us_census_core::model::lodes::{
     LodesDataset, LodesEdition, LodesJobType, WacSegment, WorkplaceSegment,
 };
-use us_census_core::model::{fips::state_code::StateCode, identifier::geoid::Geoid};
+use us_census_core::model::zone::ZoneMapping;
+use us_census_core::model::{fips::StateScope, identifier::geoid::Geoid};
+use us_census_core::ops::agg::NumericAggregation;
+use us_census_tiger::model::geometry_source::GeometrySource;
 
 #[derive(Parser)]
 #[command(version, about, long_about = None)]
@@ -25,10 +32,14 @@ pub enum LodesDatasetCli {
 
 #[derive(Args)]
 pub struct LodesWacTigerAppCli {
+    /// comma-delimited GEOIDs, states, Census divisions or Census regions to include
     #[arg(short, long)]
     pub geoids: Option<String>,
     #[arg(short, long)]
     pub wildcard: Option<GeoidType>,
+    /// when geoids are omitted, the set of states and territories to download.
+    #[arg(long)]
+    pub state_scope: Option<StateScope>,
     #[arg(long)]
     pub year: u64,
     #[arg(long)]
@@ -41,6 +52,22 @@ pub struct LodesWacTigerAppCli {
     /// WAC job type defined in LODES schema documentation
     #[arg(long)]
     jobtype: Option<LodesJobType>,
+    /// function to aggregate values to the wildcard GEOID type, sum by default
+    #[arg(long)]
+    agg_fn: Option<NumericAggregation>,
+    /// WAC segment used to weight values when agg_fn is a weighted function
+    #[arg(long)]
+    agg_weight: Option<WacSegment>,
+    /// semicolon-delimited derived variables computed after aggregation, such as 'retail_share = CNS07 / C000'
+    #[arg(long)]
+    derived: Option<String>,
+    /// how geometries of aggregated GEOIDs are found, downloaded from TIGER/Lines by default
+    #[arg(long)]
+    geometry_source: Option<GeometrySource>,
+    /// CSV file assigning GEOIDs to zones, with geoid, zone and optional fraction columns.
+    /// when provided, values are aggregated to zones and geoids and wildcard are ignored
+    #[arg(long)]
+    zones: Option<PathBuf>,
 }
 
 impl LodesTigerArgs {
@@ -55,17 +82,8 @@ impl LodesTigerArgs {
 
 async fn run_wac(args: &LodesWacTigerAppCli) {
     let geoids = match &args.geoids {
-        Some(s) => s
-            .split(',')
-            .map(|g| Geoid::try_from(g).unwrap())
-            .collect_vec(),
-        None => StateCode::ALL
-            .iter()
-            .map(|sc| {
-                let fips = sc.to_fips_string();
-                Geoid::try_from(fips.as_str()).unwrap()
-            })
-            .collect_vec(),
+        Some(s) => Geoid::parse_list(s).unwrap_or_else(|e| usage_error(e)),
+        None => Geoid::all_states(&args.state_scope.unwrap_or_default()),
     };
     let dataset = LodesDataset::WAC {
         edition: args.edition.unwrap_or_default(),
@@ -79,11 +97,29 @@ async fn run_wac(args: &LodesWacTigerAppCli) {
         .split(',')
         .map(WacSegment::try_from)
         .collect::<Result<Vec<_>, _>>()
-        .unwrap();
+        .unwrap_or_else(|e| usage_error(e));
+    let derived = match &args.derived {
+        Some(s) => DerivedVariable::parse_list(s).unwrap_or_else(|e| usage_error(e)),
+        None => vec![],
+    };
+    if let Some(path) = &args.zones {
+        let zones = ZoneMapping::from_csv_path(path).unwrap_or_else(|e| usage_error(e));
+        run_wac_zones(args, &zones, &wac_segments, &derived, dataset).await;
+        return;
+    }
 
-    let res = lodes_tiger::run(geoids, &wildcard, &wac_segments, dataset)
-        .await
-        .unwrap();
+    let res = lodes_tiger::run(
+        geoids,
+        &wildcard,
+        args.agg_fn.unwrap_or_default(),
+        args.agg_weight,
+        &wac_segments,
+        &derived,
+        args.geometry_source.unwrap_or_default(),
+        dataset,
+    )
+    .await
+    .unwrap();
     println!(
         "found {} responses, {} errors",
         res.join_dataset.len(),
@@ -109,4 +145,62 @@ async fn run_wac(args: &LodesWacTigerAppCli) {
         let out_row = LodesTigerOutputRow::from(row);
         writer.serialize(out_row).unwrap();
     }
+    for row in res.derived_dataset {
+        let out_row = LodesTigerOutputRow::from(row);
+        writer.serialize(out_row).unwrap();
+    }
+}
+
+async fn run_wac_zones(
+    args: &LodesWacTigerAppCli,
+    zones: &ZoneMapping,
+    wac_segments: &[WacSegment],
+    derived: &[DerivedVariable],
+    dataset: LodesDataset,
+) {
+    let res = lodes_tiger::run_zones(
+        zones,
+        args.agg_fn.unwrap_or_default(),
+        args.agg_weight,
+        wac_segments,
+        derived,
+        dataset,
+    )
+    .await
+    .unwrap();
+    println!(
+        "found {} zone responses, {} errors",
+        res.join_dataset.len(),
+        res.tiger_errors.len() + res.join_errors.len(),
+    );
+
+    if !res.tiger_errors.is_empty() {
+        println!("TIGER ERRORS");
+        for row in res.tiger_errors.into_iter() {
+            println!("{}", row)
+        }
+    }
+    if !res.join_errors.is_empty() {
+        println!("DATASET JOIN ERRORS");
+        for row in res.join_errors.into_iter() {
+            println!("{}", row)
+        }
+    }
+    let filename = dataset
+        .output_filename(&zones.geoid_type())
+        .replace(".csv", "_zones.csv");
+    let mut writer = csv::WriterBuilder::new().from_path(filename).unwrap();
+    for row in res.join_dataset {
+        writer.serialize(ZoneTigerOutputRow::from(row)).unwrap();
+    }
+    for row in res.derived_dataset {
+        writer.serialize(ZoneTigerOutputRow::from(row)).unwrap();
+    }
+}
+
+/// prints an invalid argument error with the CLI usage and exits with a non-zero status.
+fn usage_error(message: impl Display) -> ! {
+    LodesTigerArgs::command()
+        .error(ErrorKind::ValueValidation, message)
+        .exit()
 }
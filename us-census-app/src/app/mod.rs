@@ -1,3 +1,4 @@
 pub mod acs_tiger;
 pub mod lodes_tiger;
 pub mod lodes_tiger_args;
+pub mod zone_tiger;
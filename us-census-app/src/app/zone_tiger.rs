@@ -0,0 +1,86 @@
+use crate::model::zone_tiger_row::ZoneTigerRow;
+use crate::ops::join;
+use geo::Geometry;
+use itertools::Itertools;
+use reqwest::Client;
+use us_census_core::error::CensusError;
+use us_census_core::model::derived::{DerivedValue, DerivedVariable};
+use us_census_core::model::identifier::geoid::Geoid;
+use us_census_core::model::zone::ZoneMapping;
+use us_census_core::ops::agg::CanAggregateAsNumber;
+use us_census_core::ops::derive;
+use us_census_tiger::model::tiger_uri_builder::TigerUriBuilder;
+use us_census_tiger::ops::{dissolve, tiger_api};
+
+pub struct ZoneTigerResponse<T> {
+    pub join_dataset: Vec<ZoneTigerRow<T>>,
+    pub derived_dataset: Vec<ZoneTigerRow<DerivedValue>>,
+    pub tiger_errors: Vec<CensusError>,
+    pub join_errors: Vec<CensusError>,
+}
+
+/// downloads the TIGER/Lines geometries of the GEOIDs of a zone mapping and dissolves
+/// them into zone polygons. download failures and mapped GEOIDs without a geometry are
+/// returned alongside the zone polygons.
+pub async fn zone_geometries(
+    client: &Client,
+    tiger_uri_builder: &TigerUriBuilder,
+    zones: &ZoneMapping,
+) -> Result<(Vec<(String, Geometry)>, Vec<CensusError>), CensusError> {
+    let geoids = zones.geoids().collect_vec();
+    let tiger_response = tiger_api::run(client, tiger_uri_builder, &geoids).await?;
+
+    type NestedResult = (Vec<Vec<(Geoid, Geometry<f64>)>>, Vec<CensusError>);
+    let (tiger_rows_nested, mut tiger_errors): NestedResult =
+        tiger_response.into_iter().partition_result();
+    let geometries = tiger_rows_nested.into_iter().flatten().collect_vec();
+    let (dissolved, dissolve_errors) = dissolve::dissolve_zones(geometries, zones);
+    tiger_errors.extend(dissolve_errors);
+    Ok((dissolved, tiger_errors))
+}
+
+/// joins a dataset aggregated to zones with the dissolved geometries of the zones for the
+/// TIGER/Lines year and evaluates derived variables over the values of each zone.
+pub async fn join_zones<T>(
+    client: &Client,
+    tiger_year: u64,
+    zone_rows: Vec<(String, Vec<T>)>,
+    zones: &ZoneMapping,
+    derived: &[DerivedVariable],
+) -> Result<ZoneTigerResponse<T>, CensusError>
+where
+    T: CanAggregateAsNumber + Sync,
+{
+    let derived_rows = match derived.is_empty() {
+        true => vec![],
+        false => derive::derive(&zone_rows, derived)?,
+    };
+
+    let tiger_uri_builder = TigerUriBuilder::new(tiger_year)?;
+    let tiger_vintage = Some(tiger_uri_builder.geography_vintage());
+    let (zone_geometries, tiger_errors) =
+        zone_geometries(client, &tiger_uri_builder, zones).await?;
+
+    let (derived_join, _) =
+        join::dataset_with_zone_geometries(derived_rows, zone_geometries.clone(), tiger_vintage)?;
+    let (join_dataset, join_errors) =
+        join::dataset_with_zone_geometries(zone_rows, zone_geometries, tiger_vintage)?;
+
+    let result = ZoneTigerResponse {
+        join_dataset: flatten_rows(join_dataset),
+        derived_dataset: flatten_rows(derived_join),
+        tiger_errors,
+        join_errors,
+    };
+    Ok(result)
+}
+
+fn flatten_rows<T>(rows: Vec<(String, Geometry, Vec<T>)>) -> Vec<ZoneTigerRow<T>> {
+    rows.into_iter()
+        .flat_map(|(zone, geometry, values)| {
+            values
+                .into_iter()
+                .map(move |value| ZoneTigerRow::new(zone.clone(), value, geometry.clone()))
+        })
+        .collect_vec()
+}
@@ -1,15 +1,25 @@
-use clap::Parser;
+use clap::error::ErrorKind;
+use clap::{CommandFactory, Parser};
 use itertools::Itertools;
-use us_census_acs::model::{AcsApiQueryParams, AcsGeoidQuery};
+use std::fmt::Display;
+use std::path::PathBuf;
+use us_census_acs::model::{AcsApiQueryParams, AcsClientConfig, AcsGeoidQuery, AcsJamPolicy};
 use us_census_app::app::acs_tiger;
 use us_census_app::model::acs_tiger_output_row::AcsTigerOutputRow;
-use us_census_core::model::acs::AcsType;
+use us_census_app::model::zone_tiger_output_row::ZoneTigerOutputRow;
+use us_census_core::model::acs::{AcsTable, AcsType};
+use us_census_core::model::dataset::CensusDataset;
+use us_census_core::model::decennial::DecennialType;
+use us_census_core::model::derived::DerivedVariable;
 use us_census_core::model::identifier::geoid::Geoid;
 use us_census_core::model::identifier::geoid_type::GeoidType;
+use us_census_core::model::zone::ZoneMapping;
+use us_census_core::ops::agg::NumericAggregation;
 
 #[derive(Parser, Debug)]
 #[command(author, version, about, long_about = None)]
 pub struct AcsTigerAppCli {
+    /// comma-delimited GEOIDs, states, Census divisions or Census regions to query
     #[arg(short, long)]
     pub geoid: String,
     #[arg(short, long)]
@@ -18,35 +28,159 @@ pub struct AcsTigerAppCli {
     pub year: u64,
     #[arg(long)]
     pub acs_query: String,
-    #[arg(short, long)]
-    pub acs_type: AcsType,
-    #[arg(short, long)]
+    #[arg(short, long, required_unless_present = "decennial")]
+    pub acs_type: Option<AcsType>,
+    /// ACS table family to query, the detailed tables by default
+    #[arg(long, conflicts_with = "decennial")]
+    pub acs_table: Option<AcsTable>,
+    /// query a Decennial Census summary file instead of the ACS, which supports block queries
+    #[arg(long, conflicts_with = "acs_type")]
+    pub decennial: Option<DecennialType>,
+    #[arg(long)]
     pub acs_token: Option<String>,
+    /// level to aggregate the ACS values to before joining geometries
+    #[arg(long)]
+    pub agg_geoid_type: Option<GeoidType>,
+    /// function to aggregate values with when agg_geoid_type is provided, sum by default
+    #[arg(long)]
+    pub agg_fn: Option<NumericAggregation>,
+    /// ACS variable used to weight values when agg_fn is a weighted function
+    #[arg(long)]
+    pub agg_weight: Option<String>,
+    /// semicolon-delimited derived variables computed after aggregation, such as 'male_share = B01001_002E / B01001_001E'
+    #[arg(long)]
+    pub derived: Option<String>,
+    /// CSV file assigning the queried GEOIDs to zones, with geoid, zone and optional fraction
+    /// columns. when provided, values are aggregated to zones instead of agg_geoid_type
+    #[arg(long)]
+    pub zones: Option<PathBuf>,
+    /// validate the ACS query variables against the dataset's variable catalog before querying.
+    /// values are parsed following the catalog's variable types with or without validation
+    #[arg(long)]
+    pub validate: bool,
+    /// directory to cache ACS variable catalogs in, a directory in the user's cache
+    /// directory by default
+    #[arg(long)]
+    pub catalog_dir: Option<PathBuf>,
+    /// maximum number of ACS requests in flight at once
+    #[arg(long)]
+    pub concurrency: Option<usize>,
+    /// maximum number of retries of ACS requests that are rate limited or fail
+    #[arg(long)]
+    pub max_retries: Option<u32>,
+    /// how aggregation treats ACS jam values (estimates and margins of error that could not
+    /// be computed), error by default
+    #[arg(long)]
+    pub jam_policy: Option<AcsJamPolicy>,
 }
 
 #[tokio::main]
 async fn main() {
     let args = AcsTigerAppCli::parse();
     let acs_get_query = args.acs_query.split(',').map(String::from).collect_vec();
-    let geoid = Geoid::try_from(args.geoid.as_str()).unwrap();
-    let query: AcsGeoidQuery = AcsGeoidQuery::new(Some(geoid), args.wildcard).unwrap();
-    let query_params = AcsApiQueryParams::new(
-        None,
-        args.year,
-        args.acs_type,
-        acs_get_query,
-        query,
-        args.acs_token,
-    );
+    let geoids = Geoid::parse_list(&args.geoid).unwrap_or_else(|e| usage_error(e));
+    if geoids.is_empty() {
+        usage_error("at least one geoid must be provided");
+    }
+    let dataset = match (args.acs_type, args.decennial) {
+        (_, Some(decennial)) => CensusDataset::from(decennial),
+        (Some(acs_type), None) => CensusDataset::Acs(acs_type, args.acs_table.unwrap_or_default()),
+        (None, None) => panic!("one of acs_type or decennial must be provided"),
+    };
+    let queries = geoids
+        .into_iter()
+        .map(|geoid| {
+            let query =
+                AcsGeoidQuery::new(Some(geoid), args.wildcard).unwrap_or_else(|e| usage_error(e));
+            AcsApiQueryParams::new(
+                None,
+                args.year,
+                dataset,
+                acs_get_query.clone(),
+                query,
+                args.acs_token.clone(),
+            )
+        })
+        .collect_vec();
+
+    let default_config = AcsClientConfig::default();
+    let catalog_dir = args.catalog_dir.clone().or(default_config.catalog_dir);
+    let config = AcsClientConfig::new(
+        args.concurrency.unwrap_or(default_config.concurrency),
+        args.max_retries.unwrap_or(default_config.max_retries),
+    )
+    .with_catalog_dir(catalog_dir);
+
+    let queries = if args.validate {
+        acs_tiger::validate_batch(queries, &config).await.unwrap()
+    } else {
+        queries
+    };
+
+    let filename = &queries[0].output_filename();
+    let derived = match &args.derived {
+        Some(s) => DerivedVariable::parse_list(s).unwrap_or_else(|e| usage_error(e)),
+        None => vec![],
+    };
+    let jam_policy = args.jam_policy.unwrap_or_default();
+    if let Some(path) = &args.zones {
+        let zones = ZoneMapping::from_csv_path(path).unwrap_or_else(|e| usage_error(e));
+        let agg_fn = args.agg_fn.unwrap_or_default();
+        let weight = args.agg_weight;
+        let acs_res = acs_tiger::run_batch_zones(
+            queries, &zones, agg_fn, weight, &derived, &config, jam_policy,
+        )
+        .await
+        .unwrap();
+        let res = acs_res.zone_dataset;
+        println!(
+            "found {} zone responses, {}/{}/{} errors",
+            res.join_dataset.len(),
+            acs_res.query_errors.len(),
+            res.tiger_errors.len(),
+            res.join_errors.len(),
+        );
+        println!("ACS ERRORS");
+        for row in acs_res.query_errors.into_iter() {
+            println!("{}", row)
+        }
+        println!("TIGER ERRORS");
+        for row in res.tiger_errors.into_iter() {
+            println!("{}", row)
+        }
+        println!("JOIN ERRORS");
+        for row in res.join_errors.into_iter() {
+            println!("{}", row)
+        }
 
-    let filename = &query_params.output_filename();
-    let res = acs_tiger::run(query_params).await.unwrap();
+        let zone_filename = filename.replace(".csv", "_zones.csv");
+        let mut writer = csv::WriterBuilder::new().from_path(zone_filename).unwrap();
+        for row in res.join_dataset {
+            writer.serialize(ZoneTigerOutputRow::from(row)).unwrap();
+        }
+        for row in res.derived_dataset {
+            writer.serialize(ZoneTigerOutputRow::from(row)).unwrap();
+        }
+        return;
+    }
+
+    let agg = args
+        .agg_geoid_type
+        .map(|g| (g, args.agg_fn.unwrap_or_default(), args.agg_weight.clone()));
+    let res = acs_tiger::run_batch(queries, agg, &derived, &config, jam_policy)
+        .await
+        .unwrap();
     println!(
-        "found {} responses, {}/{} errors",
+        "found {} responses, {}/{}/{} errors",
         res.join_dataset.len(),
+        res.query_errors.len(),
         res.tiger_errors.len(),
         res.join_errors.len(),
     );
+    println!("ACS ERRORS");
+    for row in res.query_errors.into_iter() {
+        println!("{}", row)
+    }
     println!("TIGER ERRORS");
     for row in res.tiger_errors.into_iter() {
         println!("{}", row)
@@ -62,3 +196,10 @@ async fn main() {
         writer.serialize(out_row).unwrap();
     }
 }
+
+/// prints an invalid argument error with the CLI usage and exits with a non-zero status.
+fn usage_error(message: impl Display) -> ! {
+    AcsTigerAppCli::command()
+        .error(ErrorKind::ValueValidation, message)
+        .exit()
+}
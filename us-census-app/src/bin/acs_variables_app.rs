@@ -0,0 +1,86 @@
+use clap::Parser;
+use reqwest::Client;
+use std::path::PathBuf;
+use us_census_acs::api::acs_catalog_api;
+use us_census_acs::model::{AcsCatalog, AcsClientConfig};
+use us_census_core::model::acs::{AcsTable, AcsType};
+use us_census_core::model::dataset::CensusDataset;
+use us_census_core::model::decennial::DecennialType;
+
+/// search the variables of an ACS or decennial dataset by keyword, or list the variables of a table group.
+#[derive(Parser, Debug)]
+#[command(author, version, about, long_about = None)]
+pub struct AcsVariablesAppCli {
+    /// keywords that must all appear in a variable's name, label or concept
+    pub keywords: Vec<String>,
+    #[arg(long)]
+    pub year: u64,
+    #[arg(short, long, required_unless_present = "decennial")]
+    pub acs_type: Option<AcsType>,
+    /// ACS table family to query, the detailed tables by default
+    #[arg(long, conflicts_with = "decennial")]
+    pub acs_table: Option<AcsTable>,
+    /// search a Decennial Census summary file instead of the ACS
+    #[arg(long, conflicts_with = "acs_type")]
+    pub decennial: Option<DecennialType>,
+    /// list the variables of a table group, such as B01001
+    #[arg(long)]
+    pub group: Option<String>,
+    /// directory to cache ACS variable catalogs in, a directory in the user's cache
+    /// directory by default
+    #[arg(long)]
+    pub catalog_dir: Option<PathBuf>,
+    /// local copy of the dataset's variables.json, read instead of downloading the catalog
+    #[arg(long)]
+    pub variables_json: Option<PathBuf>,
+    /// local copy of the dataset's groups.json, read with variables_json
+    #[arg(long)]
+    pub groups_json: Option<PathBuf>,
+}
+
+#[tokio::main]
+async fn main() {
+    let args = AcsVariablesAppCli::parse();
+    let catalog = match &args.variables_json {
+        Some(path) => AcsCatalog::from_paths(path, args.groups_json.as_deref()).unwrap(),
+        None => {
+            let client = Client::new();
+            let default_config = AcsClientConfig::default();
+            let catalog_dir = args
+                .catalog_dir
+                .clone()
+                .or(default_config.catalog_dir.clone());
+            let config = default_config.with_catalog_dir(catalog_dir);
+            let dataset = match (args.acs_type, args.decennial) {
+                (_, Some(decennial)) => CensusDataset::from(decennial),
+                (Some(acs_type), None) => {
+                    CensusDataset::Acs(acs_type, args.acs_table.unwrap_or_default())
+                }
+                (None, None) => panic!("one of acs_type or decennial must be provided"),
+            };
+            acs_catalog_api::run(&client, None, args.year, dataset, &config)
+                .await
+                .unwrap()
+        }
+    };
+
+    if let Some(group) = &args.group {
+        match catalog.group(group) {
+            Some(g) => println!("{}", g),
+            None => println!("group {} not found", group),
+        }
+    }
+    let keywords = args.keywords.iter().map(|k| k.as_str()).collect::<Vec<_>>();
+    let variables = match &args.group {
+        Some(group) => catalog
+            .group_variables(group)
+            .into_iter()
+            .filter(|v| v.matches(&keywords))
+            .collect(),
+        None => catalog.search(&keywords),
+    };
+    for variable in variables.iter() {
+        println!("{}", variable);
+    }
+    println!("found {} of {} variables", variables.len(), catalog.len());
+}
@@ -1,5 +1,6 @@
 use super::acs_tiger_row::AcsTigerRow;
 use serde::{Deserialize, Serialize};
+use us_census_core::model::acs::{AcsJamValue, AcsMeasure, AcsReliability, AcsVariableValue};
 use us_census_core::model::identifier::has_geoid_string::HasGeoidString;
 use wkt::ToWkt;
 
@@ -7,7 +8,12 @@ use wkt::ToWkt;
 pub struct AcsTigerOutputRow {
     geoid: String,
     acs_field: String,
-    acs_value: serde_json::Value,
+    acs_value: AcsVariableValue,
+    acs_measure: Option<AcsMeasure>,
+    acs_moe: Option<f64>,
+    acs_reliability: Option<AcsReliability>,
+    acs_jam: Option<AcsJamValue>,
+    acs_annotation: Option<String>,
     geometry: String,
 }
 
@@ -15,12 +21,22 @@ impl From<AcsTigerRow> for AcsTigerOutputRow {
     fn from(row: AcsTigerRow) -> Self {
         let geoid = row.geoid.geoid_string();
         let acs_field = row.acs_value.name.clone();
-        let acs_value = row.acs_value.value.clone();
+        let acs_value = row.acs_value.value.clone().without_jam();
+        let acs_measure = row.acs_value.measure();
+        let acs_moe = row.acs_value.moe;
+        let acs_reliability = row.acs_value.reliability;
+        let acs_jam = row.acs_value.jam();
+        let acs_annotation = row.acs_value.annotation.clone();
         let geometry = row.geometry.to_wkt().to_string();
         Self {
             geoid,
             acs_field,
             acs_value,
+            acs_measure,
+            acs_moe,
+            acs_reliability,
+            acs_jam,
+            acs_annotation,
             geometry,
         }
     }
@@ -0,0 +1,36 @@
+use std::fmt::Display;
+
+use geo::Geometry;
+use serde::{Deserialize, Serialize};
+use us_census_core::model::derived::DerivedValue;
+use us_census_core::model::identifier::{geoid::Geoid, has_geoid_string::HasGeoidString};
+use wkt::ToWkt;
+
+#[derive(Deserialize, Serialize)]
+pub struct DerivedTigerRow {
+    pub geoid: Geoid,
+    pub value: DerivedValue,
+    pub geometry: Geometry,
+}
+
+impl DerivedTigerRow {
+    pub fn new(geoid: Geoid, value: DerivedValue, geometry: Geometry) -> DerivedTigerRow {
+        DerivedTigerRow {
+            geoid,
+            value,
+            geometry,
+        }
+    }
+}
+
+impl Display for DerivedTigerRow {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} - {} - {}",
+            self.geoid.geoid_string(),
+            self.value,
+            self.geometry.to_wkt()
+        )
+    }
+}
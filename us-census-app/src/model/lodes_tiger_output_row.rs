@@ -1,3 +1,4 @@
+use super::derived_tiger_row::DerivedTigerRow;
 use super::lodes_wac_tiger_row::LodesWacTigerRow;
 use serde::{Deserialize, Serialize};
 use us_census_core::model::identifier::has_geoid_string::HasGeoidString;
@@ -25,3 +26,18 @@ impl From<LodesWacTigerRow> for LodesTigerOutputRow {
         }
     }
 }
+
+impl From<DerivedTigerRow> for LodesTigerOutputRow {
+    fn from(row: DerivedTigerRow) -> Self {
+        let geoid = row.geoid.geoid_string();
+        let lodes_field = row.value.name;
+        let lodes_value = serde_json::json![row.value.value];
+        let geometry = row.geometry.to_wkt().to_string();
+        Self {
+            geoid,
+            lodes_field,
+            lodes_value,
+            geometry,
+        }
+    }
+}
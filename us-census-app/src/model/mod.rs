@@ -1,4 +1,7 @@
 pub mod acs_tiger_output_row;
 pub mod acs_tiger_row;
+pub mod derived_tiger_row;
 pub mod lodes_tiger_output_row;
 pub mod lodes_wac_tiger_row;
+pub mod zone_tiger_output_row;
+pub mod zone_tiger_row;
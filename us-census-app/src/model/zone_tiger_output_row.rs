@@ -0,0 +1,53 @@
+use super::zone_tiger_row::ZoneTigerRow;
+use serde::{Deserialize, Serialize};
+use us_census_core::model::acs::AcsValue;
+use us_census_core::model::derived::DerivedValue;
+use us_census_core::model::lodes::WacValue;
+use wkt::ToWkt;
+
+#[derive(Serialize, Deserialize)]
+pub struct ZoneTigerOutputRow {
+    zone: String,
+    field: String,
+    value: serde_json::Value,
+    geometry: String,
+}
+
+impl From<ZoneTigerRow<WacValue>> for ZoneTigerOutputRow {
+    fn from(row: ZoneTigerRow<WacValue>) -> Self {
+        let field = row.value.segment.to_string();
+        let value = serde_json::json![row.value.value];
+        let geometry = row.geometry.to_wkt().to_string();
+        Self {
+            zone: row.zone,
+            field,
+            value,
+            geometry,
+        }
+    }
+}
+
+impl From<ZoneTigerRow<AcsValue>> for ZoneTigerOutputRow {
+    fn from(row: ZoneTigerRow<AcsValue>) -> Self {
+        let geometry = row.geometry.to_wkt().to_string();
+        Self {
+            zone: row.zone,
+            field: row.value.name,
+            value: row.value.value.without_jam().into(),
+            geometry,
+        }
+    }
+}
+
+impl From<ZoneTigerRow<DerivedValue>> for ZoneTigerOutputRow {
+    fn from(row: ZoneTigerRow<DerivedValue>) -> Self {
+        let value = serde_json::json![row.value.value];
+        let geometry = row.geometry.to_wkt().to_string();
+        Self {
+            zone: row.zone,
+            field: row.value.name,
+            value,
+            geometry,
+        }
+    }
+}
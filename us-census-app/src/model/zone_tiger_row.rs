@@ -0,0 +1,35 @@
+use std::fmt::Display;
+
+use geo::Geometry;
+use serde::{Deserialize, Serialize};
+use wkt::ToWkt;
+
+/// a value aggregated to a user-defined zone, with the dissolved geometry of the zone.
+#[derive(Deserialize, Serialize)]
+pub struct ZoneTigerRow<T> {
+    pub zone: String,
+    pub value: T,
+    pub geometry: Geometry,
+}
+
+impl<T> ZoneTigerRow<T> {
+    pub fn new(zone: String, value: T, geometry: Geometry) -> ZoneTigerRow<T> {
+        ZoneTigerRow {
+            zone,
+            value,
+            geometry,
+        }
+    }
+}
+
+impl<T: Display> Display for ZoneTigerRow<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} - {} - {}",
+            self.zone,
+            self.value,
+            self.geometry.to_wkt()
+        )
+    }
+}
@@ -2,38 +2,60 @@ use geo::Geometry;
 use itertools::Itertools;
 use kdam::BarExt;
 use std::collections::HashMap;
-use us_census_core::model::identifier::Geoid;
+use us_census_core::error::CensusError;
+use us_census_core::model::identifier::{
+    geography_vintage::dataset_vintage, GeographyVintage, Geoid, HasGeographyVintage, PackedGeoid,
+};
 
-type PartitionedJoinResult<T> = (Vec<(Geoid, Geometry, Vec<T>)>, Vec<String>);
+type PartitionedJoinResult<T> = (Vec<(Geoid, Geometry, Vec<T>)>, Vec<CensusError>);
+type PartitionedZoneJoinResult<T> = (Vec<(String, Geometry, Vec<T>)>, Vec<CensusError>);
 
 /// joins a dataset with a geometry dataset. it is assumed that all Geoids in the data rows
 /// are present in the tiger rows. this join builds an index over the geometries, steps through
 /// the data row iterator, and looks up the geometry in the index. the geometry value is cloned
-/// and added to a tuple with the original data.
-pub fn dataset_with_geometries<T>(
+/// and added to a tuple with the original data. the index is keyed by PackedGeoid to keep
+/// hashing cheap for block-level datasets.
+///
+/// the data rows and the geometries must come from the same decennial geography vintage,
+/// otherwise GEOIDs may match areas that were redrawn between censuses. a mismatch fails the
+/// join, while untagged data rows are joined with a warning.
+pub fn dataset_with_geometries<T: HasGeographyVintage>(
     data_rows: Vec<(Geoid, Vec<T>)>,
     tiger_rows: Vec<Vec<(Geoid, Geometry<f64>)>>,
-) -> Result<PartitionedJoinResult<T>, String> {
+    tiger_vintage: Option<GeographyVintage>,
+) -> Result<PartitionedJoinResult<T>, CensusError> {
+    check_vintage(
+        dataset_vintage(&data_rows)?,
+        tiger_vintage,
+        data_rows.is_empty(),
+    )?;
+
     let mut pb = kdam::Bar::builder()
         .total(data_rows.len())
         .desc("dataset join")
-        .build()?;
+        .build()
+        .map_err(|e| CensusError::Internal(format!("error building progress bar: {}", e)))?;
 
     let tiger_lookup = tiger_rows
         .into_iter()
         .flatten()
-        .collect::<HashMap<Geoid, Geometry>>();
+        .map(|(geoid, geometry)| Ok((PackedGeoid::try_from(&geoid)?, geometry)))
+        .collect::<Result<HashMap<PackedGeoid, Geometry>, CensusError>>()?;
 
     let (join_dataset, join_errors) = data_rows
         .into_iter()
         .map(|(geoid, lodes_values)| {
-            let row = match tiger_lookup.get(&geoid) {
-                Some(geometry) => Ok((geoid, geometry.clone(), lodes_values)),
-                None => Err(format!(
+            let row = match PackedGeoid::try_from(&geoid).map(|packed| tiger_lookup.get(&packed)) {
+                Err(e) => Err(CensusError::Join(format!(
+                    "cannot look up geometry for geoid {}: {}",
+                    geoid, e
+                ))),
+                Ok(Some(geometry)) => Ok((geoid, geometry.clone(), lodes_values)),
+                Ok(None) => Err(CensusError::Join(format!(
                     "geometry not found for geoid {}, has {} LODES values from API response",
                     geoid,
                     lodes_values.len()
-                )),
+                ))),
             };
             let _ = pb.update(1); // ignore progress failures
             row
@@ -43,3 +65,53 @@ pub fn dataset_with_geometries<T>(
     eprintln!(); // finish progress bar
     Ok((join_dataset, join_errors))
 }
+
+/// joins a dataset aggregated to zones with the dissolved zone geometries, see
+/// [`crate::ops::dissolve::dissolve_zones`]. as with [`dataset_with_geometries`], the
+/// dataset and the geometries must come from the same decennial geography vintage.
+pub fn dataset_with_zone_geometries<T: HasGeographyVintage>(
+    data_rows: Vec<(String, Vec<T>)>,
+    zone_geometries: Vec<(String, Geometry)>,
+    tiger_vintage: Option<GeographyVintage>,
+) -> Result<PartitionedZoneJoinResult<T>, CensusError> {
+    let data_vintage = dataset_vintage(&data_rows)?;
+    check_vintage(data_vintage, tiger_vintage, data_rows.is_empty())?;
+    let lookup: HashMap<String, Geometry> = zone_geometries.into_iter().collect();
+    let (join_dataset, join_errors) = data_rows
+        .into_iter()
+        .map(|(zone, values)| match lookup.get(&zone) {
+            Some(geometry) => Ok((zone, geometry.clone(), values)),
+            None => Err(CensusError::Join(format!(
+                "geometry not found for zone {}, has {} values",
+                zone,
+                values.len()
+            ))),
+        })
+        .partition_result();
+    Ok((join_dataset, join_errors))
+}
+
+/// rejects joins of data and geometries from different geography vintages, and warns
+/// when the vintage of the data is unknown.
+fn check_vintage(
+    data_vintage: Option<GeographyVintage>,
+    tiger_vintage: Option<GeographyVintage>,
+    is_empty: bool,
+) -> Result<(), CensusError> {
+    match (data_vintage, tiger_vintage) {
+        (Some(data), Some(tiger)) if data != tiger => {
+            return Err(CensusError::VintageMismatch(format!(
+                "cannot join data from {} geographies with TIGER/Lines geometries from {} geographies",
+                data, tiger
+            )));
+        }
+        (None, Some(tiger)) if !is_empty => {
+            log::warn!(
+                "dataset has no geography vintage, assuming it matches {} TIGER/Lines geometries",
+                tiger
+            );
+        }
+        _ => {}
+    }
+    Ok(())
+}
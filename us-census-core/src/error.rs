@@ -0,0 +1,85 @@
+use thiserror::Error;
+
+/// boxed error type used to chain the underlying cause of a [`CensusError`],
+/// such as a `reqwest`, `zip` or `shapefile` error, from crates that us-census-core
+/// does not depend on.
+pub type BoxError = Box<dyn std::error::Error + Send + Sync>;
+
+/// errors produced by the us-census crates.
+///
+/// the variants distinguish the failure categories that callers are expected to
+/// handle differently, for example, retrying an HTTP failure but reporting a
+/// malformed GEOID back to the user. where a failure was caused by an error from
+/// another library, that error is available via [`std::error::Error::source`].
+#[derive(Debug, Error)]
+pub enum CensusError {
+    /// a GEOID, FIPS code or GEOID hierarchy operation is invalid
+    #[error("{0}")]
+    Identifier(String),
+    /// the combination of request arguments is invalid or not supported
+    #[error("{0}")]
+    InvalidQuery(String),
+    /// the HTTP request could not be sent or its response body could not be read
+    #[error("failure calling {url}: {source}")]
+    Http {
+        url: String,
+        #[source]
+        source: BoxError,
+    },
+    /// the HTTP request returned an error status code
+    #[error("request to {url} failed with HTTP status {status}")]
+    HttpStatus { url: String, status: u16 },
+    /// the contents of a response did not match the expected schema
+    #[error("{0}")]
+    ResponseSchema(String),
+    /// failure writing, reading or unpacking a downloaded archive
+    #[error("{message}: {source}")]
+    Archive {
+        message: String,
+        #[source]
+        source: BoxError,
+    },
+    /// failure decoding the contents of a shapefile
+    #[error("{message}: {source}")]
+    Shapefile {
+        message: String,
+        #[source]
+        source: BoxError,
+    },
+    /// failure grouping or reducing values during aggregation
+    #[error("{0}")]
+    Aggregation(String),
+    /// datasets or GEOIDs from different decennial geography vintages were combined
+    #[error("{0}")]
+    VintageMismatch(String),
+    /// failure joining a dataset with its geometries
+    #[error("{0}")]
+    Join(String),
+    /// unexpected failure unrelated to the inputs, such as a poisoned lock
+    #[error("{0}")]
+    Internal(String),
+}
+
+impl CensusError {
+    /// true for failures that may succeed when retried: connection failures, rate
+    /// limits (HTTP 429) and server errors (HTTP 5xx).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use us_census_core::error::CensusError;
+    ///
+    /// let url = String::from("https://api.census.gov/data/2022/acs/acs5");
+    /// assert!(CensusError::HttpStatus { url: url.clone(), status: 429 }.is_retryable());
+    /// assert!(CensusError::HttpStatus { url: url.clone(), status: 503 }.is_retryable());
+    /// assert!(!CensusError::HttpStatus { url, status: 400 }.is_retryable());
+    /// assert!(!CensusError::InvalidQuery(String::from("bad query")).is_retryable());
+    /// ```
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            CensusError::Http { .. } => true,
+            CensusError::HttpStatus { status, .. } => *status == 429 || *status >= 500,
+            _ => false,
+        }
+    }
+}
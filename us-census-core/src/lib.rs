@@ -1,3 +1,6 @@
+pub mod error;
 pub mod model;
 pub mod ops;
 pub mod util;
+
+pub use error::CensusError;
@@ -0,0 +1,237 @@
+use std::collections::HashMap;
+use std::fmt::Display;
+
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
+
+use super::AcsValue;
+use crate::error::CensusError;
+
+/// z-score of the 90% confidence level that ACS margins of error are published at.
+pub const MOE_Z_SCORE: f64 = 1.645;
+
+/// reliability of an estimate based on its coefficient of variation (CV). estimates
+/// with a CV of up to 12% are highly reliable, up to 40% are of medium reliability,
+/// and above 40% (or with a zero estimate) are of low reliability.
+#[derive(Serialize, Deserialize, ValueEnum, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum AcsReliability {
+    High,
+    Medium,
+    Low,
+}
+
+impl AcsReliability {
+    /// classifies a coefficient of variation, given in percent.
+    pub fn from_coefficient_of_variation(cv: f64) -> AcsReliability {
+        match cv {
+            cv if cv <= 12.0 => AcsReliability::High,
+            cv if cv <= 40.0 => AcsReliability::Medium,
+            _ => AcsReliability::Low,
+        }
+    }
+}
+
+impl Display for AcsReliability {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AcsReliability::High => write!(f, "high"),
+            AcsReliability::Medium => write!(f, "medium"),
+            AcsReliability::Low => write!(f, "low"),
+        }
+    }
+}
+
+/// an ACS estimate paired with its 90% margin of error (MOE), with the approximations for
+/// the MOE of derived estimates from the Census Bureau's ACS handbook "Understanding and
+/// Using American Community Survey Data", chapter 8.
+///
+/// # Examples
+///
+/// ```rust
+/// use us_census_core::model::acs::{AcsEstimate, AcsReliability};
+///
+/// let women = AcsEstimate::new(2000.0, 100.0);
+/// let men = AcsEstimate::new(1800.0, 120.0);
+/// let total = AcsEstimate::sum(&[women, men]);
+/// assert_eq!(total.estimate, 3800.0);
+/// assert!((total.moe - 156.2050).abs() < 1e-4);
+///
+/// let share = women.proportion(&total).unwrap();
+/// assert!((share.estimate - 0.5263).abs() < 1e-4);
+/// assert!((share.moe - 0.0150).abs() < 1e-4);
+///
+/// let sex_ratio = men.ratio(&women).unwrap();
+/// assert_eq!(sex_ratio.estimate, 0.9);
+/// assert!((sex_ratio.moe - 0.0750).abs() < 1e-4);
+///
+/// assert_eq!(total.reliability(), AcsReliability::High);
+/// assert_eq!(AcsEstimate::new(50.0, 60.0).reliability(), AcsReliability::Low);
+/// ```
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq)]
+pub struct AcsEstimate {
+    pub estimate: f64,
+    pub moe: f64,
+}
+
+impl AcsEstimate {
+    pub fn new(estimate: f64, moe: f64) -> AcsEstimate {
+        AcsEstimate { estimate, moe }
+    }
+
+    pub fn standard_error(&self) -> f64 {
+        self.moe / MOE_Z_SCORE
+    }
+
+    /// the standard error as a percent of the estimate, or None for a zero estimate.
+    pub fn coefficient_of_variation(&self) -> Option<f64> {
+        if self.estimate == 0.0 {
+            None
+        } else {
+            Some(self.standard_error() / self.estimate.abs() * 100.0)
+        }
+    }
+
+    pub fn reliability(&self) -> AcsReliability {
+        match self.coefficient_of_variation() {
+            Some(cv) => AcsReliability::from_coefficient_of_variation(cv),
+            None => AcsReliability::Low,
+        }
+    }
+
+    /// sum of estimates, with the root-sum-of-squares of their MOEs.
+    pub fn sum(estimates: &[AcsEstimate]) -> AcsEstimate {
+        let estimate = estimates.iter().map(|e| e.estimate).sum();
+        let moe = estimates.iter().map(|e| e.moe.powi(2)).sum::<f64>().sqrt();
+        AcsEstimate { estimate, moe }
+    }
+
+    /// the proportion of this estimate in a denominator estimate that includes it, such as
+    /// the share of women in the total population. when the proportion approximation is
+    /// undefined (negative under the square root), the ratio approximation is used.
+    pub fn proportion(&self, denominator: &AcsEstimate) -> Result<AcsEstimate, CensusError> {
+        let p = divide(self.estimate, denominator.estimate)?;
+        let radicand = self.moe.powi(2) - p.powi(2) * denominator.moe.powi(2);
+        if radicand < 0.0 {
+            return self.ratio(denominator);
+        }
+        let moe = radicand.sqrt() / denominator.estimate;
+        Ok(AcsEstimate { estimate: p, moe })
+    }
+
+    /// the ratio of this estimate to a denominator estimate that does not include it.
+    pub fn ratio(&self, denominator: &AcsEstimate) -> Result<AcsEstimate, CensusError> {
+        let r = divide(self.estimate, denominator.estimate)?;
+        let moe = (self.moe.powi(2) + r.powi(2) * denominator.moe.powi(2)).sqrt()
+            / denominator.estimate.abs();
+        Ok(AcsEstimate { estimate: r, moe })
+    }
+
+    /// the product of two estimates.
+    pub fn product(&self, other: &AcsEstimate) -> AcsEstimate {
+        let estimate = self.estimate * other.estimate;
+        let moe = (self.estimate.powi(2) * other.moe.powi(2)
+            + other.estimate.powi(2) * self.moe.powi(2))
+        .sqrt();
+        AcsEstimate { estimate, moe }
+    }
+}
+
+impl Display for AcsEstimate {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} ± {}", self.estimate, self.moe)
+    }
+}
+
+fn divide(numerator: f64, denominator: f64) -> Result<f64, CensusError> {
+    if denominator == 0.0 {
+        Err(CensusError::Aggregation(String::from(
+            "cannot derive an ACS estimate with a zero denominator",
+        )))
+    } else {
+        Ok(numerator / denominator)
+    }
+}
+
+/// true for margin of error variables, such as `B01001_001M` or the percent
+/// margin of error `DP02_0001PM`.
+pub fn is_moe_variable(name: &str) -> bool {
+    name.contains('_') && name.ends_with('M')
+}
+
+/// the margin of error variable of an estimate variable, such as `B01001_001M` for
+/// `B01001_001E` or `DP02_0001PM` for `DP02_0001PE`. None if the name is not an estimate.
+///
+/// # Examples
+///
+/// ```rust
+/// use us_census_core::model::acs::acs_estimate::moe_variable;
+///
+/// assert_eq!(moe_variable("B01001_001E"), Some(String::from("B01001_001M")));
+/// assert_eq!(moe_variable("DP02_0001PE"), Some(String::from("DP02_0001PM")));
+/// assert_eq!(moe_variable("NAME"), None);
+/// assert_eq!(moe_variable("B01001_001EA"), None);
+/// ```
+pub fn moe_variable(name: &str) -> Option<String> {
+    if !name.contains('_') {
+        return None;
+    }
+    name.strip_suffix('E').map(|prefix| format!("{}M", prefix))
+}
+
+/// the estimate variable of a margin of error variable, the inverse of [`moe_variable`].
+/// None if the name is not a margin of error.
+///
+/// # Examples
+///
+/// ```rust
+/// use us_census_core::model::acs::acs_estimate::estimate_variable;
+///
+/// assert_eq!(estimate_variable("B01001_001M"), Some(String::from("B01001_001E")));
+/// assert_eq!(estimate_variable("DP02_0001PM"), Some(String::from("DP02_0001PE")));
+/// assert_eq!(estimate_variable("B01001_001E"), None);
+/// ```
+pub fn estimate_variable(name: &str) -> Option<String> {
+    if !is_moe_variable(name) {
+        return None;
+    }
+    name.strip_suffix('M').map(|prefix| format!("{}E", prefix))
+}
+
+/// attaches the value of each margin of error variable to its estimate variable, along
+/// with the estimate's reliability. all values are kept, in their original order.
+///
+/// # Examples
+///
+/// ```rust
+/// use us_census_core::model::acs::{acs_estimate::pair_margins_of_error, AcsReliability, AcsValue};
+/// use serde_json::json;
+///
+/// let values = vec![
+///     AcsValue::new(String::from("B01001_001E"), json!["3800"]),
+///     AcsValue::new(String::from("B01001_001M"), json!["156"]),
+///     AcsValue::new(String::from("NAME"), json!["Census Tract 1"]),
+/// ];
+/// let paired = pair_margins_of_error(values);
+/// assert_eq!(paired[0].moe, Some(156.0));
+/// assert_eq!(paired[0].reliability, Some(AcsReliability::High));
+/// assert_eq!(paired[1].moe, None);
+/// assert_eq!(paired.len(), 3);
+/// ```
+pub fn pair_margins_of_error(values: Vec<AcsValue>) -> Vec<AcsValue> {
+    let moes = values
+        .iter()
+        .filter(|v| is_moe_variable(&v.name))
+        .filter_map(|v| Some((v.name.clone(), v.as_number()?)))
+        .collect::<HashMap<_, _>>();
+    if moes.is_empty() {
+        return values;
+    }
+    values
+        .into_iter()
+        .map(|v| match moe_variable(&v.name).and_then(|m| moes.get(&m)) {
+            Some(moe) => v.with_moe(Some(*moe)),
+            None => v,
+        })
+        .collect()
+}
@@ -0,0 +1,189 @@
+use std::collections::HashMap;
+use std::fmt::Display;
+
+use serde::{Deserialize, Serialize};
+
+use super::AcsValue;
+
+/// special cases of ACS estimates and margins of error, which the ACS API encodes as
+/// sentinel ("jam") values in place of numbers and describes with the annotation variables
+/// of the estimate (`EA`) and margin of error (`MA`). see the Census Bureau's "Notes on
+/// ACS API Variable Types".
+///
+/// # Examples
+///
+/// ```rust
+/// use us_census_core::model::acs::AcsJamValue;
+/// use serde_json::json;
+///
+/// assert_eq!(AcsJamValue::from_sentinel(-666666666.0), Some(AcsJamValue::Suppressed));
+/// assert_eq!(AcsJamValue::from_value(&json!["-888888888"]), Some(AcsJamValue::Missing));
+/// assert_eq!(AcsJamValue::from_value(&json!["3889"]), None);
+/// assert_eq!(AcsJamValue::Controlled.annotation(), "*****");
+///
+/// // controlled margins of error have no sampling error
+/// assert_eq!(AcsJamValue::Controlled.as_number(), Some(0.0));
+/// assert_eq!(AcsJamValue::Suppressed.as_number(), None);
+/// ```
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum AcsJamValue {
+    /// `-888888888` ("(X)"): the estimate or margin of error is not applicable or not available
+    Missing,
+    /// `-666666666` ("-"): too few sample observations to compute the estimate
+    Suppressed,
+    /// `-999999999` ("**"): too few sample observations to compute the margin of error
+    MarginSuppressed,
+    /// `-222222222` ("***"): the margin of error of a median in the lowest or highest
+    /// interval of an open-ended distribution, which is not appropriate to compute
+    TopBottomCoded,
+    /// `-555555555` ("*****"): the margin of error of a controlled estimate, which has no
+    /// sampling error
+    Controlled,
+}
+
+impl AcsJamValue {
+    /// decodes a sentinel number of the ACS API.
+    pub fn from_sentinel(value: f64) -> Option<AcsJamValue> {
+        match value {
+            -888888888.0 => Some(AcsJamValue::Missing),
+            -666666666.0 => Some(AcsJamValue::Suppressed),
+            -999999999.0 => Some(AcsJamValue::MarginSuppressed),
+            -222222222.0 => Some(AcsJamValue::TopBottomCoded),
+            -555555555.0 => Some(AcsJamValue::Controlled),
+            _ => None,
+        }
+    }
+
+    /// decodes a sentinel from a JSON number or the numeric strings returned by the ACS API.
+    pub fn from_value(value: &serde_json::Value) -> Option<AcsJamValue> {
+        let number = match value {
+            serde_json::Value::Number(n) => n.as_f64(),
+            serde_json::Value::String(s) => s.trim().parse::<f64>().ok(),
+            _ => None,
+        }?;
+        AcsJamValue::from_sentinel(number)
+    }
+
+    /// the sentinel number of this jam value.
+    pub fn sentinel(&self) -> f64 {
+        match self {
+            AcsJamValue::Missing => -888888888.0,
+            AcsJamValue::Suppressed => -666666666.0,
+            AcsJamValue::MarginSuppressed => -999999999.0,
+            AcsJamValue::TopBottomCoded => -222222222.0,
+            AcsJamValue::Controlled => -555555555.0,
+        }
+    }
+
+    /// the annotation symbol published for this jam value.
+    pub fn annotation(&self) -> &'static str {
+        match self {
+            AcsJamValue::Missing => "(X)",
+            AcsJamValue::Suppressed => "-",
+            AcsJamValue::MarginSuppressed => "**",
+            AcsJamValue::TopBottomCoded => "***",
+            AcsJamValue::Controlled => "*****",
+        }
+    }
+
+    /// the number this jam value stands for, if any. controlled margins of error are zero,
+    /// all other jam values have no numeric value.
+    pub fn as_number(&self) -> Option<f64> {
+        match self {
+            AcsJamValue::Controlled => Some(0.0),
+            _ => None,
+        }
+    }
+}
+
+impl Display for AcsJamValue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AcsJamValue::Missing => write!(f, "missing"),
+            AcsJamValue::Suppressed => write!(f, "suppressed"),
+            AcsJamValue::MarginSuppressed => write!(f, "margin_suppressed"),
+            AcsJamValue::TopBottomCoded => write!(f, "top_bottom_coded"),
+            AcsJamValue::Controlled => write!(f, "controlled"),
+        }
+    }
+}
+
+/// true for the annotation variables of estimates and margins of error, such as
+/// `B01001_001EA`, `B01001_001MA` or the percent annotations `DP02_0001PEA`.
+pub fn is_annotation_variable(name: &str) -> bool {
+    annotated_variable(name).is_some()
+}
+
+/// the variable described by an annotation variable, such as `B01001_001E` for
+/// `B01001_001EA`. None if the name is not an annotation.
+///
+/// # Examples
+///
+/// ```rust
+/// use us_census_core::model::acs::acs_jam_value::annotated_variable;
+///
+/// assert_eq!(annotated_variable("B01001_001EA"), Some("B01001_001E"));
+/// assert_eq!(annotated_variable("DP02_0001PMA"), Some("DP02_0001PM"));
+/// assert_eq!(annotated_variable("B01001_001E"), None);
+/// assert_eq!(annotated_variable("NAME"), None);
+/// ```
+pub fn annotated_variable(name: &str) -> Option<&str> {
+    if !name.contains('_') {
+        return None;
+    }
+    let variable = name.strip_suffix('A')?;
+    if variable.ends_with('E') || variable.ends_with('M') {
+        Some(variable)
+    } else {
+        None
+    }
+}
+
+/// attaches the text of each annotation variable to the value it annotates. annotation
+/// variables without text (null or empty) are ignored. all values are kept, in their
+/// original order.
+///
+/// # Examples
+///
+/// ```rust
+/// use us_census_core::model::acs::{acs_jam_value::pair_annotations, AcsJamValue, AcsValue};
+/// use serde_json::{json, Value};
+///
+/// let values = vec![
+///     AcsValue::new(String::from("B19013_001E"), json!["-666666666"]),
+///     AcsValue::new(String::from("B19013_001EA"), json!["-"]),
+///     AcsValue::new(String::from("B19013_001M"), json!["-222222222"]),
+///     AcsValue::new(String::from("B19013_001MA"), json!["***"]),
+///     AcsValue::new(String::from("B01001_001E"), json!["3889"]),
+///     AcsValue::new(String::from("B01001_001EA"), Value::Null),
+/// ];
+/// let paired = pair_annotations(values);
+/// assert_eq!(paired[0].jam(), Some(AcsJamValue::Suppressed));
+/// assert_eq!(paired[0].annotation.as_deref(), Some("-"));
+/// assert_eq!(paired[2].jam(), Some(AcsJamValue::TopBottomCoded));
+/// assert_eq!(paired[2].annotation.as_deref(), Some("***"));
+/// assert_eq!(paired[4].jam(), None);
+/// assert_eq!(paired[4].annotation, None);
+/// assert_eq!(paired.len(), 6);
+/// ```
+pub fn pair_annotations(values: Vec<AcsValue>) -> Vec<AcsValue> {
+    let annotations = values
+        .iter()
+        .filter_map(|v| {
+            let variable = annotated_variable(&v.name)?;
+            let text = v.value.as_str().map(str::trim).filter(|t| !t.is_empty())?;
+            Some((String::from(variable), String::from(text)))
+        })
+        .collect::<HashMap<_, _>>();
+    if annotations.is_empty() {
+        return values;
+    }
+    values
+        .into_iter()
+        .map(|v| match annotations.get(&v.name) {
+            Some(text) => v.with_annotation(Some(text.clone())),
+            None => v,
+        })
+        .collect()
+}
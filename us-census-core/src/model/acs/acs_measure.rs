@@ -0,0 +1,83 @@
+use super::AcsTable;
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
+use std::fmt::Display;
+
+/// what an ACS table variable measures, from the suffix of its name: `E` estimates,
+/// `PE` percents, and their `M` and `PM` margins of error. subject tables publish
+/// percents as `E` variables of their percent columns, which are only told apart by the
+/// label of the variable, see [`AcsMeasure::from_label`].
+///
+/// # Examples
+///
+/// ```rust
+/// use us_census_core::model::acs::AcsMeasure;
+///
+/// assert_eq!(AcsMeasure::from_variable("DP05_0001E"), Some(AcsMeasure::Estimate));
+/// assert_eq!(AcsMeasure::from_variable("DP05_0002PE"), Some(AcsMeasure::Percent));
+/// assert_eq!(AcsMeasure::from_variable("S0101_C01_001M"), Some(AcsMeasure::MarginOfError));
+/// assert_eq!(AcsMeasure::from_variable("DP05_0002PM"), Some(AcsMeasure::PercentMarginOfError));
+/// assert_eq!(AcsMeasure::from_variable("B01001_001EA"), None);
+/// assert_eq!(AcsMeasure::from_variable("NAME"), None);
+///
+/// let label = "Estimate!!Percent below poverty level!!Population for whom poverty status is determined";
+/// assert_eq!(AcsMeasure::from_label("S1701_C03_001E", label), Some(AcsMeasure::Percent));
+/// let label = "Margin of Error!!Percent below poverty level!!Population for whom poverty status is determined";
+/// assert_eq!(AcsMeasure::from_label("S1701_C03_001M", label), Some(AcsMeasure::PercentMarginOfError));
+/// let label = "Estimate!!Total!!Population for whom poverty status is determined";
+/// assert_eq!(AcsMeasure::from_label("S1701_C01_001E", label), Some(AcsMeasure::Estimate));
+/// ```
+#[derive(Serialize, Deserialize, ValueEnum, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum AcsMeasure {
+    Estimate,
+    Percent,
+    MarginOfError,
+    PercentMarginOfError,
+}
+
+impl Display for AcsMeasure {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AcsMeasure::Estimate => write!(f, "estimate"),
+            AcsMeasure::Percent => write!(f, "percent"),
+            AcsMeasure::MarginOfError => write!(f, "margin_of_error"),
+            AcsMeasure::PercentMarginOfError => write!(f, "percent_margin_of_error"),
+        }
+    }
+}
+
+impl AcsMeasure {
+    /// classifies a table variable by its suffix. None for variables outside of tables
+    /// and for annotation variables.
+    pub fn from_variable(name: &str) -> Option<AcsMeasure> {
+        let (_, suffix) = name.rsplit_once('_')?;
+        let suffix = suffix.trim_start_matches(|c: char| c.is_ascii_digit());
+        match suffix {
+            "E" => Some(AcsMeasure::Estimate),
+            "PE" => Some(AcsMeasure::Percent),
+            "M" => Some(AcsMeasure::MarginOfError),
+            "PM" => Some(AcsMeasure::PercentMarginOfError),
+            _ => None,
+        }
+    }
+
+    /// classifies a table variable by its suffix and its label in the variable catalog.
+    /// subject table variables with a `Percent` part in their label, such as
+    /// `Estimate!!Percent below poverty level!!...` or `Percent!!Estimate!!...`, are
+    /// percents or percent margins of error.
+    pub fn from_label(name: &str, label: &str) -> Option<AcsMeasure> {
+        let measure = AcsMeasure::from_variable(name)?;
+        let subject_percent = AcsTable::from_variable(name) == Some(AcsTable::Subject)
+            && label.split("!!").any(|part| part.starts_with("Percent"));
+        match measure {
+            AcsMeasure::Estimate if subject_percent => Some(AcsMeasure::Percent),
+            AcsMeasure::MarginOfError if subject_percent => Some(AcsMeasure::PercentMarginOfError),
+            other => Some(other),
+        }
+    }
+
+    pub fn is_percent(&self) -> bool {
+        matches!(self, AcsMeasure::Percent | AcsMeasure::PercentMarginOfError)
+    }
+}
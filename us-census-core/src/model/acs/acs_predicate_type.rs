@@ -0,0 +1,51 @@
+use serde::{Deserialize, Serialize};
+use std::fmt::Display;
+use std::str::FromStr;
+
+use crate::error::CensusError;
+
+/// type of the values of an ACS variable, from the `predicateType` of its metadata in the
+/// `variables.json` of a dataset. the API returns values of all types as strings.
+///
+/// # Examples
+///
+/// ```rust
+/// use us_census_core::model::acs::AcsPredicateType;
+///
+/// assert_eq!("int".parse::<AcsPredicateType>().unwrap(), AcsPredicateType::Int);
+/// assert_eq!(AcsPredicateType::Float.to_string(), "float");
+/// assert!("fips-for".parse::<AcsPredicateType>().is_err());
+/// ```
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum AcsPredicateType {
+    Int,
+    Float,
+    String,
+}
+
+impl Display for AcsPredicateType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AcsPredicateType::Int => write!(f, "int"),
+            AcsPredicateType::Float => write!(f, "float"),
+            AcsPredicateType::String => write!(f, "string"),
+        }
+    }
+}
+
+impl FromStr for AcsPredicateType {
+    type Err = CensusError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim() {
+            "int" => Ok(AcsPredicateType::Int),
+            "float" => Ok(AcsPredicateType::Float),
+            "string" => Ok(AcsPredicateType::String),
+            _ => Err(CensusError::ResponseSchema(format!(
+                "unknown ACS predicate type '{}'",
+                s
+            ))),
+        }
+    }
+}
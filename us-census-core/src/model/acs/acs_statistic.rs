@@ -0,0 +1,64 @@
+use itertools::Itertools;
+use serde::{Deserialize, Serialize};
+use std::fmt::Display;
+
+/// the statistic an ACS table variable reports, from its label in the variable catalog.
+/// counts can be summed, while medians and means, such as median household income or
+/// per capita income, cannot.
+///
+/// # Examples
+///
+/// ```rust
+/// use us_census_core::model::acs::AcsStatistic;
+///
+/// let label = "Estimate!!Median family income in the past 12 months (in 2022 inflation-adjusted dollars)";
+/// assert_eq!(AcsStatistic::from_label(label), AcsStatistic::Median);
+/// let label = "Estimate!!INCOME AND BENEFITS!!Total households!!Mean household income (dollars)";
+/// assert_eq!(AcsStatistic::from_label(label), AcsStatistic::Mean);
+/// let label = "Estimate!!Per capita income in the past 12 months";
+/// assert_eq!(AcsStatistic::from_label(label), AcsStatistic::Mean);
+/// let label = "Estimate!!Aggregate household income in the past 12 months";
+/// assert_eq!(AcsStatistic::from_label(label), AcsStatistic::Count);
+/// assert!(!AcsStatistic::Median.is_additive());
+/// ```
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum AcsStatistic {
+    Count,
+    Median,
+    Mean,
+}
+
+impl Display for AcsStatistic {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AcsStatistic::Count => write!(f, "count"),
+            AcsStatistic::Median => write!(f, "median"),
+            AcsStatistic::Mean => write!(f, "mean"),
+        }
+    }
+}
+
+impl AcsStatistic {
+    /// classifies a variable by the words of its label: "median" labels are medians, and
+    /// "mean", "average" and "per capita" labels are means. all others are counts.
+    pub fn from_label(label: &str) -> AcsStatistic {
+        let lower = label.to_lowercase();
+        let words = lower
+            .split(|c: char| !c.is_alphanumeric())
+            .filter(|w| !w.is_empty())
+            .collect_vec();
+        let per_capita = words.windows(2).any(|w| w == ["per", "capita"]);
+        if words.contains(&"median") {
+            AcsStatistic::Median
+        } else if per_capita || words.contains(&"mean") || words.contains(&"average") {
+            AcsStatistic::Mean
+        } else {
+            AcsStatistic::Count
+        }
+    }
+
+    pub fn is_additive(&self) -> bool {
+        matches!(self, AcsStatistic::Count)
+    }
+}
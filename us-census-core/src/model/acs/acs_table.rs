@@ -0,0 +1,92 @@
+use crate::error::CensusError;
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
+use std::fmt::Display;
+
+/// families of ACS tables, each published as its own dataset of the Census Data API
+/// with its own variable naming:
+///
+/// - detailed tables, such as `B01001_001E`, including collapsed `C` tables
+/// - subject tables, such as `S0101_C01_001E`, with a column number in the name
+/// - data profiles, such as `DP05_0001E` and the percent `DP05_0001PE`
+/// - comparison profiles, such as `CP05_2019_001E`, comparing profiles across years
+///
+/// # Examples
+///
+/// ```rust
+/// use us_census_core::model::acs::AcsTable;
+///
+/// assert_eq!(AcsTable::from_variable("S0101_C01_001E"), Some(AcsTable::Subject));
+/// assert_eq!(AcsTable::from_variable("C24010_001E"), Some(AcsTable::Detailed));
+/// assert_eq!(AcsTable::from_variable("CP05_2019_001E"), Some(AcsTable::ComparisonProfile));
+/// assert_eq!(AcsTable::from_variable("NAME"), None);
+///
+/// assert!(AcsTable::Profile.validate_variable("DP05_0001PE").is_ok());
+/// assert!(AcsTable::Profile.validate_variable("NAME").is_ok());
+/// assert!(AcsTable::Detailed.validate_variable("DP05_0001PE").is_err());
+/// ```
+#[derive(Serialize, Deserialize, ValueEnum, Default, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum AcsTable {
+    #[default]
+    Detailed,
+    Subject,
+    Profile,
+    ComparisonProfile,
+}
+
+impl Display for AcsTable {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AcsTable::Detailed => write!(f, "detailed"),
+            AcsTable::Subject => write!(f, "subject"),
+            AcsTable::Profile => write!(f, "profile"),
+            AcsTable::ComparisonProfile => write!(f, "cprofile"),
+        }
+    }
+}
+
+impl AcsTable {
+    /// the path of this table family below the ACS survey directory in API URLs,
+    /// which is empty for the detailed tables.
+    pub fn to_directory_suffix(&self) -> String {
+        match self {
+            AcsTable::Detailed => String::new(),
+            _ => format!("/{}", self),
+        }
+    }
+
+    /// the table family of a table variable, by its table id prefix. None for variables
+    /// outside of tables, such as `NAME` or `GEO_ID`.
+    pub fn from_variable(name: &str) -> Option<AcsTable> {
+        let (table_id, _) = name.split_once('_')?;
+        let starts_with_digit = |prefix: &str| {
+            table_id
+                .strip_prefix(prefix)
+                .is_some_and(|rest| rest.starts_with(|c: char| c.is_ascii_digit()))
+        };
+        if starts_with_digit("DP") {
+            Some(AcsTable::Profile)
+        } else if starts_with_digit("CP") {
+            Some(AcsTable::ComparisonProfile)
+        } else if starts_with_digit("S") {
+            Some(AcsTable::Subject)
+        } else if starts_with_digit("B") || starts_with_digit("C") {
+            Some(AcsTable::Detailed)
+        } else {
+            None
+        }
+    }
+
+    /// checks that a table variable belongs to this table family. variables outside of
+    /// tables are accepted.
+    pub fn validate_variable(&self, name: &str) -> Result<(), CensusError> {
+        match AcsTable::from_variable(name) {
+            Some(table) if table != *self => Err(CensusError::InvalidQuery(format!(
+                "{} is a variable of the {} tables, but the query is for the {} tables",
+                name, table, self
+            ))),
+            _ => Ok(()),
+        }
+    }
+}
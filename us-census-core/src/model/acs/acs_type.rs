@@ -1,9 +1,10 @@
+use crate::error::CensusError;
 use std::fmt::Display;
 
 use clap::ValueEnum;
 use serde::{Deserialize, Serialize};
 
-#[derive(Serialize, Deserialize, ValueEnum, Clone, Copy, Debug)]
+#[derive(Serialize, Deserialize, ValueEnum, Clone, Copy, Debug, PartialEq, Eq, Hash)]
 #[serde(rename_all = "snake_case")]
 pub enum AcsType {
     OneYear,
@@ -36,13 +37,16 @@ impl AcsType {
 }
 
 impl TryFrom<u64> for AcsType {
-    type Error = String;
+    type Error = CensusError;
 
     fn try_from(value: u64) -> Result<Self, Self::Error> {
         match value {
             1 => Ok(AcsType::OneYear),
             5 => Ok(AcsType::FiveYear),
-            _ => Err(format!("unknown acs type {}", value)),
+            _ => Err(CensusError::InvalidQuery(format!(
+                "unknown acs type {}",
+                value
+            ))),
         }
     }
 }
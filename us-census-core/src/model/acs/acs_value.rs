@@ -1,3 +1,8 @@
+use super::{AcsEstimate, AcsJamValue, AcsMeasure, AcsReliability, AcsStatistic, AcsVariableValue};
+use crate::error::CensusError;
+use crate::model::derived::DerivedValue;
+use crate::model::identifier::{GeographyVintage, HasGeographyVintage};
+use crate::ops::agg::CanAggregateAsNumber;
 use std::fmt::Display;
 
 use serde::{Deserialize, Serialize};
@@ -5,39 +10,189 @@ use serde::{Deserialize, Serialize};
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AcsValue {
     pub name: String,
-    pub value: serde_json::Value,
+    pub value: AcsVariableValue,
+    /// geography vintage of the GEOID this value was tabulated for, if known
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub vintage: Option<GeographyVintage>,
+    /// 90% margin of error of this estimate, paired from its MOE variable
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub moe: Option<f64>,
+    /// reliability of this estimate based on its coefficient of variation
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reliability: Option<AcsReliability>,
+    /// text of the annotation variable of this value, such as `B01001_001EA`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub annotation: Option<String>,
+    /// what this value measures, when known from the variable catalog, see [`AcsValue::measure`]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    measure: Option<AcsMeasure>,
+    /// the statistic this value reports, when known from the variable catalog
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    statistic: Option<AcsStatistic>,
 }
 
 impl AcsValue {
-    pub fn new(name: String, value: serde_json::Value) -> AcsValue {
-        AcsValue { name, value }
+    /// creates a value. JSON values are parsed by inferring their type, see
+    /// [`AcsVariableValue::infer`].
+    pub fn new(name: String, value: impl Into<AcsVariableValue>) -> AcsValue {
+        AcsValue {
+            name,
+            value: value.into(),
+            vintage: None,
+            moe: None,
+            reliability: None,
+            annotation: None,
+            measure: None,
+            statistic: None,
+        }
     }
 
-    /// to numeric operation.
+    pub fn with_vintage(self, vintage: Option<GeographyVintage>) -> AcsValue {
+        AcsValue { vintage, ..self }
+    }
+
+    pub fn with_annotation(self, annotation: Option<String>) -> AcsValue {
+        AcsValue { annotation, ..self }
+    }
+
+    /// sets what this value measures, such as from the label of its variable in the
+    /// variable catalog, see [`AcsMeasure::from_label`].
+    pub fn with_measure(self, measure: Option<AcsMeasure>) -> AcsValue {
+        AcsValue { measure, ..self }
+    }
+
+    /// sets the statistic this value reports, such as from the label of its variable in the
+    /// variable catalog, see [`AcsStatistic::from_label`].
+    pub fn with_statistic(self, statistic: Option<AcsStatistic>) -> AcsValue {
+        AcsValue { statistic, ..self }
+    }
+
+    /// the statistic this value reports, if known from the variable catalog.
+    pub fn statistic(&self) -> Option<AcsStatistic> {
+        self.statistic
+    }
+
+    /// the special case encoded by a sentinel value of the ACS API, if any, see
+    /// [`AcsJamValue`].
+    pub fn jam(&self) -> Option<AcsJamValue> {
+        self.value.jam()
+    }
+
+    /// true if this value is a jam value without a numeric value, which cannot be
+    /// aggregated or derived from.
+    pub fn is_jammed(&self) -> bool {
+        self.jam().is_some_and(|jam| jam.as_number().is_none())
+    }
+
+    /// sets the margin of error of this estimate along with its reliability, which is
+    /// only known for numeric estimates.
+    pub fn with_moe(self, moe: Option<f64>) -> AcsValue {
+        let reliability = match (self.as_number(), moe) {
+            (Some(estimate), Some(moe)) => Some(AcsEstimate::new(estimate, moe).reliability()),
+            _ => None,
+        };
+        AcsValue {
+            moe,
+            reliability,
+            ..self
+        }
+    }
+
+    /// what this value measures, as set from the variable catalog, or else from the suffix
+    /// of its variable name.
+    pub fn measure(&self) -> Option<AcsMeasure> {
+        self.measure
+            .or_else(|| AcsMeasure::from_variable(&self.name))
+    }
+
+    /// this value paired with its margin of error, if both are known.
+    pub fn estimate(&self) -> Option<AcsEstimate> {
+        Some(AcsEstimate::new(self.as_number()?, self.moe?))
+    }
+
+    /// the value as a number. labels and jam values are not numbers, except for
+    /// controlled margins of error, which are zero.
+    pub fn as_number(&self) -> Option<f64> {
+        self.value.as_number()
+    }
+
+    /// to numeric operation, as an error for values that are not numbers.
     ///
     /// # Background
     ///
-    /// `serde_json::Value.as_f64()` is for values that are actual JSON number types, but when
-    /// dealing with ACS values, they are always string types, and so the built-in `as_f64()`
-    /// method gives the following error, for example:
-    ///
-    ///   > cannot parse acs value for B01001_001E as number, found \"3889\""
-    ///
-    /// this method also ensures we are dealing with a trimmed string before attempting to parse it as a number.
-    pub fn as_f64_safe(&self) -> Result<f64, String> {
-        let value_str = self
-            .value
-            .as_str()
-            .ok_or_else(|| format!("failed to decode value as string: {}", self.value))?;
-        value_str
-            .trim()
-            .parse::<f64>()
-            .map_err(|e| format!("failed to decode value as f64: {}", e))
+    /// values of the ACS API are parsed into an [`AcsVariableValue`], see
+    /// [`AcsVariableValue::parse`], so only integer and float values are numbers.
+    /// labels, nulls and the sentinel numbers of jam values are rejected, see [`AcsJamValue`].
+    pub fn as_f64_safe(&self) -> Result<f64, CensusError> {
+        match &self.value {
+            AcsVariableValue::Integer(i) => Ok(*i as f64),
+            AcsVariableValue::Float(x) => Ok(*x),
+            AcsVariableValue::Jam(jam) => Err(CensusError::ResponseSchema(format!(
+                "value of {} is the {} jam value {}",
+                self.name,
+                jam,
+                jam.sentinel()
+            ))),
+            other => Err(CensusError::ResponseSchema(format!(
+                "failed to decode value of {} as f64: {}",
+                self.name, other
+            ))),
+        }
+    }
+}
+
+impl HasGeographyVintage for AcsValue {
+    fn geography_vintage(&self) -> Option<GeographyVintage> {
+        self.vintage
     }
 }
 
 impl Display for AcsValue {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}: {}", self.name, self.value)
+        match self.moe {
+            Some(moe) => write!(f, "{}: {} ± {}", self.name, self.value, moe),
+            None => write!(f, "{}: {}", self.name, self.value),
+        }
+    }
+}
+
+impl CanAggregateAsNumber for AcsValue {
+    type Key = String;
+
+    fn aggregation_key(&self) -> String {
+        self.name.clone()
+    }
+
+    /// accepts integer and float values, and controlled margins of error as zero.
+    fn aggregation_value(&self) -> Result<f64, CensusError> {
+        if let Some(jam) = self.jam().filter(|_| self.is_jammed()) {
+            return Err(CensusError::Aggregation(format!(
+                "ACS value for {} is a {} jam value ({}) and cannot be aggregated",
+                self.name,
+                jam,
+                self.annotation.as_deref().unwrap_or(jam.annotation())
+            )));
+        }
+        self.as_number().ok_or_else(|| {
+            CensusError::Aggregation(format!(
+                "ACS value for {} is not numeric (found {}) but user requested aggregation",
+                self.name, self.value
+            ))
+        })
+    }
+
+    fn from_aggregate(key: String, value: f64, vintage: Option<GeographyVintage>) -> AcsValue {
+        AcsValue::new(key, value).with_vintage(vintage)
+    }
+}
+
+/// derived values that could not be evaluated, which are NaN, become null values.
+impl From<DerivedValue> for AcsValue {
+    fn from(derived: DerivedValue) -> Self {
+        let value = match derived.value.is_nan() {
+            true => AcsVariableValue::Null,
+            false => AcsVariableValue::from(derived.value),
+        };
+        AcsValue::new(derived.name, value).with_vintage(derived.vintage)
     }
 }
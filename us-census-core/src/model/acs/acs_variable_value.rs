@@ -0,0 +1,204 @@
+use std::fmt::Display;
+
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+use super::{AcsJamValue, AcsPredicateType};
+use crate::error::CensusError;
+
+/// a value of an ACS variable. the ACS API returns all values as strings, which are parsed
+/// into numbers or labels following the [`AcsPredicateType`] of their variable, or inferred
+/// from their contents when the type is not known. sentinel numbers are decoded into their
+/// [`AcsJamValue`].
+///
+/// values serialize to JSON numbers, strings and null, with jam values written as their
+/// sentinel numbers.
+///
+/// # Examples
+///
+/// ```rust
+/// use us_census_core::model::acs::{AcsJamValue, AcsPredicateType, AcsVariableValue};
+/// use serde_json::json;
+///
+/// let int = Some(AcsPredicateType::Int);
+/// assert_eq!(AcsVariableValue::parse(&json!["3889"], int).unwrap(), AcsVariableValue::Integer(3889));
+/// assert_eq!(
+///     AcsVariableValue::parse(&json!["-666666666"], int).unwrap(),
+///     AcsVariableValue::Jam(AcsJamValue::Suppressed)
+/// );
+/// assert!(AcsVariableValue::parse(&json!["Census Tract 1"], int).is_err());
+///
+/// // FIPS codes and other string variables keep their leading zeros
+/// let string = Some(AcsPredicateType::String);
+/// let code = AcsVariableValue::parse(&json!["08"], string).unwrap();
+/// assert_eq!(code, AcsVariableValue::Label(String::from("08")));
+/// assert_eq!(code.as_number(), None);
+///
+/// // without a predicate type, numeric strings are inferred as numbers
+/// assert_eq!(AcsVariableValue::from(json!["42.5"]), AcsVariableValue::Float(42.5));
+/// assert_eq!(AcsVariableValue::from(json!["08"]), AcsVariableValue::Label(String::from("08")));
+/// assert_eq!(AcsVariableValue::from(json!["42.5"]).as_number(), Some(42.5));
+/// assert_eq!(serde_json::to_value(AcsVariableValue::Integer(3889)).unwrap(), json![3889]);
+/// ```
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(from = "serde_json::Value", into = "serde_json::Value")]
+pub enum AcsVariableValue {
+    Integer(i64),
+    Float(f64),
+    /// a text value, such as the `NAME` of a geography or an annotation
+    Label(String),
+    /// a special case encoded by a sentinel number, such as a suppressed estimate
+    Jam(AcsJamValue),
+    Null,
+}
+
+impl AcsVariableValue {
+    /// parses a value of the ACS API following the predicate type of its variable. values
+    /// that do not match an `int` or `float` predicate type are an error. without a
+    /// predicate type, the type is inferred, see [`AcsVariableValue::infer`].
+    pub fn parse(
+        value: &serde_json::Value,
+        predicate_type: Option<AcsPredicateType>,
+    ) -> Result<AcsVariableValue, CensusError> {
+        let text = match value {
+            serde_json::Value::Null => return Ok(AcsVariableValue::Null),
+            serde_json::Value::String(s) => s.trim().to_string(),
+            other => other.to_string(),
+        };
+        let invalid = |predicate_type: AcsPredicateType| {
+            CensusError::ResponseSchema(format!(
+                "expected a value of predicate type {}, found {}",
+                predicate_type, value
+            ))
+        };
+        match predicate_type {
+            None => Ok(AcsVariableValue::infer(value)),
+            Some(AcsPredicateType::String) => Ok(AcsVariableValue::Label(text)),
+            Some(_) if text.is_empty() => Ok(AcsVariableValue::Null),
+            Some(p @ AcsPredicateType::Int) => {
+                let number = text.parse::<f64>().map_err(|_| invalid(p))?;
+                match AcsJamValue::from_sentinel(number) {
+                    Some(jam) => Ok(AcsVariableValue::Jam(jam)),
+                    None if number.fract() == 0.0 => Ok(AcsVariableValue::Integer(number as i64)),
+                    None => Err(invalid(p)),
+                }
+            }
+            Some(p @ AcsPredicateType::Float) => {
+                let number = text.parse::<f64>().map_err(|_| invalid(p))?;
+                match AcsJamValue::from_sentinel(number) {
+                    Some(jam) => Ok(AcsVariableValue::Jam(jam)),
+                    None => Ok(AcsVariableValue::Float(number)),
+                }
+            }
+        }
+    }
+
+    /// infers the type of a value of unknown predicate type. JSON numbers and strings that
+    /// are written like numbers are numbers, other strings are labels. strings with leading
+    /// zeros, such as FIPS codes, are labels.
+    pub fn infer(value: &serde_json::Value) -> AcsVariableValue {
+        if let Some(jam) = AcsJamValue::from_value(value) {
+            return AcsVariableValue::Jam(jam);
+        }
+        match value {
+            serde_json::Value::Null => AcsVariableValue::Null,
+            serde_json::Value::Number(n) => match n.as_i64() {
+                Some(i) => AcsVariableValue::Integer(i),
+                None => AcsVariableValue::Float(n.as_f64().unwrap_or(f64::NAN)),
+            },
+            serde_json::Value::String(s) => {
+                let text = s.trim();
+                let digits = text.trim_start_matches('-');
+                let leading_zero =
+                    digits.len() > 1 && digits.starts_with('0') && !digits.starts_with("0.");
+                if leading_zero {
+                    return AcsVariableValue::Label(String::from(s));
+                }
+                if let Ok(i) = text.parse::<i64>() {
+                    return AcsVariableValue::Integer(i);
+                }
+                match text.parse::<f64>() {
+                    Ok(x) if x.is_finite() => AcsVariableValue::Float(x),
+                    _ => AcsVariableValue::Label(String::from(s)),
+                }
+            }
+            other => AcsVariableValue::Label(other.to_string()),
+        }
+    }
+
+    /// the value as a number. labels, nulls and jam values are not numbers, except for
+    /// controlled margins of error, which are zero.
+    pub fn as_number(&self) -> Option<f64> {
+        match self {
+            AcsVariableValue::Integer(i) => Some(*i as f64),
+            AcsVariableValue::Float(x) => Some(*x),
+            AcsVariableValue::Jam(jam) => jam.as_number(),
+            AcsVariableValue::Label(_) | AcsVariableValue::Null => None,
+        }
+    }
+
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            AcsVariableValue::Label(s) => Some(s),
+            _ => None,
+        }
+    }
+
+    pub fn jam(&self) -> Option<AcsJamValue> {
+        match self {
+            AcsVariableValue::Jam(jam) => Some(*jam),
+            _ => None,
+        }
+    }
+
+    /// this value with jam values replaced by null, for outputs that report jam
+    /// values separately from their sentinel numbers.
+    pub fn without_jam(self) -> AcsVariableValue {
+        match self {
+            AcsVariableValue::Jam(_) => AcsVariableValue::Null,
+            other => other,
+        }
+    }
+}
+
+impl Display for AcsVariableValue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AcsVariableValue::Integer(i) => write!(f, "{}", i),
+            AcsVariableValue::Float(x) => write!(f, "{}", x),
+            AcsVariableValue::Label(s) => write!(f, "{}", s),
+            AcsVariableValue::Jam(jam) => write!(f, "{} ({})", jam, jam.annotation()),
+            AcsVariableValue::Null => write!(f, "null"),
+        }
+    }
+}
+
+impl From<serde_json::Value> for AcsVariableValue {
+    fn from(value: serde_json::Value) -> Self {
+        AcsVariableValue::infer(&value)
+    }
+}
+
+impl From<AcsVariableValue> for serde_json::Value {
+    fn from(value: AcsVariableValue) -> Self {
+        match value {
+            AcsVariableValue::Integer(i) => json![i],
+            AcsVariableValue::Float(x) => json![x],
+            AcsVariableValue::Label(s) => json![s],
+            AcsVariableValue::Jam(jam) => json![jam.sentinel() as i64],
+            AcsVariableValue::Null => serde_json::Value::Null,
+        }
+    }
+}
+
+impl From<f64> for AcsVariableValue {
+    fn from(value: f64) -> Self {
+        AcsVariableValue::Float(value)
+    }
+}
+
+impl From<i64> for AcsVariableValue {
+    fn from(value: i64) -> Self {
+        AcsVariableValue::Integer(value)
+    }
+}
@@ -1,4 +1,18 @@
+pub use acs_estimate::{AcsEstimate, AcsReliability};
+pub use acs_jam_value::AcsJamValue;
+pub use acs_measure::AcsMeasure;
+pub use acs_predicate_type::AcsPredicateType;
+pub use acs_statistic::AcsStatistic;
+pub use acs_table::AcsTable;
 pub use acs_type::AcsType;
 pub use acs_value::AcsValue;
+pub use acs_variable_value::AcsVariableValue;
+pub mod acs_estimate;
+pub mod acs_jam_value;
+pub mod acs_measure;
+pub mod acs_predicate_type;
+pub mod acs_statistic;
+pub mod acs_table;
 pub mod acs_type;
 pub mod acs_value;
+pub mod acs_variable_value;
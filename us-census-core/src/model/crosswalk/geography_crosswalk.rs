@@ -0,0 +1,335 @@
+use std::collections::HashMap;
+
+use indexmap::IndexMap;
+use itertools::Itertools;
+
+use super::RelationshipRecord;
+use crate::error::CensusError;
+use crate::model::identifier::{
+    geography_vintage::dataset_vintage, has_geoid_string::HasGeoidString, GeographyVintage, Geoid,
+    GeoidType,
+};
+use crate::ops::agg::CanAggregateAsNumber;
+
+/// the parts of a source GEOID that intersect each target GEOID, with their land and water areas.
+type Intersections = HashMap<Geoid, Vec<(Geoid, f64, f64)>>;
+
+/// weights for apportioning data from the GEOIDs of one decennial geography vintage
+/// to another, built from [`RelationshipRecord`]s. each source GEOID maps to the target
+/// GEOIDs it intersects, with weights that sum to 1.
+///
+/// area weights apportion values by the share of the source area's land that falls in
+/// each target area. population weights assume each target area's population is spread
+/// evenly over its land, and apportion values by the share of the source area's
+/// population that falls in each target area.
+///
+/// # Examples
+///
+/// ```rust
+/// use std::collections::HashMap;
+/// use us_census_core::model::crosswalk::{GeographyCrosswalk, RelationshipFile};
+/// use us_census_core::model::identifier::{GeographyVintage, Geoid, GeoidType};
+/// use us_census_core::model::lodes::{WacSegment, WacValue};
+///
+/// // 2010 tract 011900 was split into 2020 tracts 012000 and 012100
+/// let contents = "\
+/// GEOID_TRACT_20|GEOID_TRACT_10|AREALAND_PART|AREAWATER_PART
+/// 08059012000|08059011900|300|0
+/// 08059012100|08059011900|100|0
+/// ";
+/// let records = RelationshipFile::CensusTract.read(contents.as_bytes()).unwrap();
+/// let tract_2010: Geoid = "08059011900".parse().unwrap();
+/// let (tract_a, tract_b): (Geoid, Geoid) =
+///     ("08059012000".parse().unwrap(), "08059012100".parse().unwrap());
+///
+/// let by_area = GeographyCrosswalk::area_weighted(
+///     &records, GeographyVintage::Census2010, GeoidType::CensusTract
+/// ).unwrap();
+/// assert_eq!(by_area.weights(&tract_2010).unwrap(), &[(tract_a.clone(), 0.75), (tract_b.clone(), 0.25)]);
+///
+/// let rows = vec![(
+///     tract_2010.clone(),
+///     vec![WacValue::new(WacSegment::C000, 100.0).with_vintage(Some(GeographyVintage::Census2010))],
+/// )];
+/// let result = by_area.reallocate(&rows).unwrap();
+/// assert_eq!(result[0].0, tract_a);
+/// assert_eq!(result[0].1[0].value, 75.0);
+/// assert_eq!(result[1].1[0].vintage, Some(GeographyVintage::Census2020));
+///
+/// // the smaller 2020 tract is far more populous
+/// let population = HashMap::from([(tract_a.clone(), 300.0), (tract_b.clone(), 900.0)]);
+/// let by_population = GeographyCrosswalk::population_weighted(
+///     &records, GeographyVintage::Census2010, GeoidType::CensusTract, &population
+/// ).unwrap();
+/// let result = by_population.reallocate(&rows).unwrap();
+/// assert_eq!(result[0].1[0].value, 25.0);
+/// assert_eq!(result[1].1[0].value, 75.0);
+///
+/// // 2020 data cannot be reallocated with a 2010 to 2020 crosswalk
+/// let rows_2020 = vec![(
+///     tract_a,
+///     vec![WacValue::new(WacSegment::C000, 1.0).with_vintage(Some(GeographyVintage::Census2020))],
+/// )];
+/// assert!(by_area.reallocate(&rows_2020).is_err());
+/// ```
+#[derive(Clone, Debug)]
+pub struct GeographyCrosswalk {
+    source: GeographyVintage,
+    target: GeographyVintage,
+    geoid_type: GeoidType,
+    weights: HashMap<Geoid, Vec<(Geoid, f64)>>,
+}
+
+impl GeographyCrosswalk {
+    /// builds a crosswalk from the source vintage to the other vintage of the records,
+    /// weighted by land area. records at a finer GEOID type than the crosswalk GEOID
+    /// type, such as blocks for a tract crosswalk, are truncated and combined.
+    pub fn area_weighted(
+        records: &[RelationshipRecord],
+        source: GeographyVintage,
+        geoid_type: GeoidType,
+    ) -> Result<GeographyCrosswalk, CensusError> {
+        let target = target_vintage(source)?;
+        let intersections = intersections(records, source, geoid_type)?;
+        let weights = intersections
+            .into_iter()
+            .map(|(geoid, parts)| {
+                let weights = area_weights(&parts);
+                (geoid, weights)
+            })
+            .collect();
+        Ok(GeographyCrosswalk {
+            source,
+            target,
+            geoid_type,
+            weights,
+        })
+    }
+
+    /// builds a crosswalk from the source vintage to the other vintage of the records,
+    /// weighted by the population of the target GEOIDs. target GEOIDs missing from the
+    /// population table are treated as unpopulated. source GEOIDs that only intersect
+    /// unpopulated target GEOIDs fall back to land area weights.
+    pub fn population_weighted(
+        records: &[RelationshipRecord],
+        source: GeographyVintage,
+        geoid_type: GeoidType,
+        target_population: &HashMap<Geoid, f64>,
+    ) -> Result<GeographyCrosswalk, CensusError> {
+        let target = target_vintage(source)?;
+        let intersections = intersections(records, source, geoid_type)?;
+
+        // total area of each target GEOID, used to estimate the population of its parts
+        let mut target_areas: HashMap<&Geoid, (f64, f64)> = HashMap::new();
+        for (target_geoid, land, water) in intersections.values().flatten() {
+            let (total_land, total_water) = target_areas.entry(target_geoid).or_default();
+            *total_land += land;
+            *total_water += water;
+        }
+
+        let weights = intersections
+            .iter()
+            .map(|(geoid, parts)| {
+                let estimates = parts
+                    .iter()
+                    .map(|(target_geoid, land, water)| {
+                        let population = target_population.get(target_geoid).unwrap_or(&0.0);
+                        let (total_land, total_water) = target_areas[target_geoid];
+                        let share = if total_land > 0.0 {
+                            land / total_land
+                        } else if total_land + total_water > 0.0 {
+                            (land + water) / (total_land + total_water)
+                        } else {
+                            0.0
+                        };
+                        population * share
+                    })
+                    .collect_vec();
+                let total: f64 = estimates.iter().sum();
+                let weights = if total > 0.0 {
+                    parts
+                        .iter()
+                        .zip(estimates)
+                        .map(|((target_geoid, _, _), estimate)| {
+                            (target_geoid.clone(), estimate / total)
+                        })
+                        .collect_vec()
+                } else {
+                    area_weights(parts)
+                };
+                (geoid.clone(), weights)
+            })
+            .collect();
+        Ok(GeographyCrosswalk {
+            source,
+            target,
+            geoid_type,
+            weights,
+        })
+    }
+
+    /// the geography vintage of the GEOIDs this crosswalk reallocates from.
+    pub fn source(&self) -> GeographyVintage {
+        self.source
+    }
+
+    /// the geography vintage of the GEOIDs this crosswalk reallocates to.
+    pub fn target(&self) -> GeographyVintage {
+        self.target
+    }
+
+    pub fn geoid_type(&self) -> GeoidType {
+        self.geoid_type
+    }
+
+    /// number of source GEOIDs in this crosswalk.
+    pub fn len(&self) -> usize {
+        self.weights.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.weights.is_empty()
+    }
+
+    /// the target GEOIDs and weights of a source GEOID, in target GEOID order.
+    pub fn weights(&self, geoid: &Geoid) -> Option<&[(Geoid, f64)]> {
+        self.weights.get(geoid).map(|w| w.as_slice())
+    }
+
+    /// apportions each value of a dataset of source GEOIDs to the target GEOIDs by
+    /// crosswalk weight, summing values with the same key that arrive at the same target
+    /// GEOID. the dataset must be tagged with the source vintage of this crosswalk (or not
+    /// tagged at all) and its GEOIDs must be of the crosswalk GEOID type. the reallocated
+    /// values are tagged with the target vintage and returned in GEOID order.
+    ///
+    /// weights only apportion counts, such as WAC jobs or ACS population estimates. medians,
+    /// averages and percentages cannot be reallocated this way, see `reallocate_acs` in the
+    /// `us-census-acs` crate, which rejects ACS variables that are not additive.
+    pub fn reallocate<T: CanAggregateAsNumber>(
+        &self,
+        rows: &[(Geoid, Vec<T>)],
+    ) -> Result<Vec<(Geoid, Vec<T>)>, CensusError> {
+        if let Some(vintage) = dataset_vintage(rows)? {
+            if vintage != self.source {
+                return Err(CensusError::VintageMismatch(format!(
+                    "cannot reallocate data from {} geographies with a {} to {} crosswalk",
+                    vintage, self.source, self.target
+                )));
+            }
+        }
+
+        let mut errors = vec![];
+        let mut reallocated: HashMap<&Geoid, IndexMap<T::Key, f64>> = HashMap::new();
+        for (geoid, values) in rows.iter() {
+            if geoid.geoid_type() != self.geoid_type {
+                errors.push(format!(
+                    "geoid {} is not a {} geoid",
+                    geoid, self.geoid_type
+                ));
+                continue;
+            }
+            let Some(weights) = self.weights.get(geoid) else {
+                errors.push(format!(
+                    "geoid {} is not found in the {} to {} crosswalk",
+                    geoid, self.source, self.target
+                ));
+                continue;
+            };
+            for value in values.iter() {
+                let key = value.aggregation_key();
+                let x = value.aggregation_value()?;
+                for (target_geoid, weight) in weights.iter() {
+                    let totals = reallocated.entry(target_geoid).or_default();
+                    *totals.entry(key.clone()).or_default() += x * weight;
+                }
+            }
+        }
+
+        if !errors.is_empty() {
+            let msg = errors.into_iter().unique().take(5).join("\n");
+            return Err(CensusError::Aggregation(format!(
+                "errors during reallocation. first 5 unique errors:\n{}",
+                msg
+            )));
+        }
+
+        let result = reallocated
+            .into_iter()
+            .sorted_by_cached_key(|(geoid, _)| geoid.geoid_string())
+            .map(|(geoid, totals)| {
+                let values = totals
+                    .into_iter()
+                    .map(|(key, total)| T::from_aggregate(key, total, Some(self.target)))
+                    .collect_vec();
+                (geoid.clone(), values)
+            })
+            .collect_vec();
+        Ok(result)
+    }
+}
+
+fn target_vintage(source: GeographyVintage) -> Result<GeographyVintage, CensusError> {
+    match source {
+        GeographyVintage::Census2010 => Ok(GeographyVintage::Census2020),
+        GeographyVintage::Census2020 => Ok(GeographyVintage::Census2010),
+        other => Err(CensusError::InvalidQuery(format!(
+            "relationship files only relate 2010 and 2020 census geographies, found {}",
+            other
+        ))),
+    }
+}
+
+/// groups records by source GEOID, combining the areas of records that share a source
+/// and target GEOID after truncating to the crosswalk GEOID type.
+fn intersections(
+    records: &[RelationshipRecord],
+    source: GeographyVintage,
+    geoid_type: GeoidType,
+) -> Result<Intersections, CensusError> {
+    let target = target_vintage(source)?;
+    let mut areas: HashMap<Geoid, HashMap<Geoid, (f64, f64)>> = HashMap::new();
+    for record in records.iter() {
+        let source_geoid = record.geoid(source)?.truncate_geoid_to_type(&geoid_type)?;
+        let target_geoid = record.geoid(target)?.truncate_geoid_to_type(&geoid_type)?;
+        let (land, water) = areas
+            .entry(source_geoid)
+            .or_default()
+            .entry(target_geoid)
+            .or_default();
+        *land += record.area_land_part;
+        *water += record.area_water_part;
+    }
+    let result = areas
+        .into_iter()
+        .map(|(source_geoid, parts)| {
+            let parts = parts
+                .into_iter()
+                .map(|(g, (land, water))| (g, land, water))
+                .sorted_by_cached_key(|(g, _, _)| g.geoid_string())
+                .collect_vec();
+            (source_geoid, parts)
+        })
+        .collect();
+    Ok(result)
+}
+
+/// weights by share of land area, falling back to total area for water-only areas and
+/// to an even split for areas without any recorded area.
+fn area_weights(parts: &[(Geoid, f64, f64)]) -> Vec<(Geoid, f64)> {
+    let total_land: f64 = parts.iter().map(|(_, land, _)| land).sum();
+    let total_area: f64 = parts.iter().map(|(_, land, water)| land + water).sum();
+    let n = parts.len() as f64;
+    parts
+        .iter()
+        .map(|(geoid, land, water)| {
+            let weight = if total_land > 0.0 {
+                land / total_land
+            } else if total_area > 0.0 {
+                (land + water) / total_area
+            } else {
+                1.0 / n
+            };
+            (geoid.clone(), weight)
+        })
+        .collect_vec()
+}
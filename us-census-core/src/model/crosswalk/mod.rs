@@ -0,0 +1,7 @@
+pub mod geography_crosswalk;
+pub mod relationship_file;
+pub mod relationship_record;
+
+pub use geography_crosswalk::GeographyCrosswalk;
+pub use relationship_file::RelationshipFile;
+pub use relationship_record::RelationshipRecord;
@@ -0,0 +1,276 @@
+use std::fmt::Display;
+use std::fs::File;
+use std::io::{BufRead, BufReader};
+use std::path::Path;
+
+use itertools::Itertools;
+use serde::{Deserialize, Serialize};
+
+use super::RelationshipRecord;
+use crate::error::CensusError;
+use crate::model::fips::state_code::StateCode;
+use crate::model::identifier::{Geoid, GeoidType};
+
+pub const RELATIONSHIP_BASE_URL: &str = "https://www2.census.gov/geo/docs/maps-data/data/rel2020";
+
+/// the 2010 to 2020 geography relationship files published by the Census Bureau. tract
+/// and block group files are national, block files are published by state.
+/// see https://www.census.gov/geographies/reference-files/time-series/geo/relationship-files.html
+///
+/// # Examples
+///
+/// ```rust
+/// use us_census_core::model::crosswalk::RelationshipFile;
+/// use us_census_core::model::fips::state_code::StateCode;
+///
+/// let file = RelationshipFile::Block { state: StateCode::Colorado };
+/// assert_eq!(
+///     file.uri(),
+///     "https://www2.census.gov/geo/docs/maps-data/data/rel2020/t10t20/TAB2010_TAB2020_ST08.zip"
+/// );
+/// assert_eq!(file.filename(), "tab2010_tab2020_st08.txt");
+/// assert!(file.is_archive());
+/// ```
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case", tag = "type")]
+pub enum RelationshipFile {
+    CensusTract,
+    BlockGroup,
+    Block { state: StateCode },
+}
+
+impl RelationshipFile {
+    /// the GEOID type of the 2010 and 2020 areas related by this file.
+    pub fn geoid_type(&self) -> GeoidType {
+        match self {
+            RelationshipFile::CensusTract => GeoidType::CensusTract,
+            RelationshipFile::BlockGroup => GeoidType::BlockGroup,
+            RelationshipFile::Block { .. } => GeoidType::Block,
+        }
+    }
+
+    pub fn uri(&self) -> String {
+        match self {
+            RelationshipFile::CensusTract => {
+                format!("{}/tract/{}", RELATIONSHIP_BASE_URL, self.filename())
+            }
+            RelationshipFile::BlockGroup => {
+                format!("{}/blkgrp/{}", RELATIONSHIP_BASE_URL, self.filename())
+            }
+            RelationshipFile::Block { state } => format!(
+                "{}/t10t20/TAB2010_TAB2020_ST{}.zip",
+                RELATIONSHIP_BASE_URL,
+                state.to_fips_string()
+            ),
+        }
+    }
+
+    /// name of the pipe-delimited text file, which for block files is stored
+    /// in a .zip archive at [`RelationshipFile::uri`].
+    pub fn filename(&self) -> String {
+        match self {
+            RelationshipFile::CensusTract => String::from("tab20_tract20_tract10_natl.txt"),
+            RelationshipFile::BlockGroup => String::from("tab20_blkgrp20_blkgrp10_natl.txt"),
+            RelationshipFile::Block { state } => {
+                format!("tab2010_tab2020_st{}.txt", state.to_fips_string())
+            }
+        }
+    }
+
+    /// true if the file is downloaded as a .zip archive.
+    pub fn is_archive(&self) -> bool {
+        matches!(self, RelationshipFile::Block { .. })
+    }
+
+    /// reads the records of a relationship file from a local path.
+    pub fn read_path(&self, path: &Path) -> Result<Vec<RelationshipRecord>, CensusError> {
+        let file = File::open(path).map_err(|e| CensusError::Archive {
+            message: format!("failure opening relationship file {}", path.display()),
+            source: Box::new(e),
+        })?;
+        self.read(BufReader::new(file))
+    }
+
+    /// reads the records of a pipe-delimited relationship file. columns are found by
+    /// name in the header row.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use us_census_core::model::crosswalk::RelationshipFile;
+    /// use us_census_core::model::identifier::Geoid;
+    ///
+    /// let contents = "\
+    /// OID_TRACT_20|GEOID_TRACT_20|AREALAND_TRACT_20|OID_TRACT_10|GEOID_TRACT_10|AREALAND_PART|AREAWATER_PART
+    /// 1|08059012000|300|2|08059011901|100|0
+    /// 1|08059012000|300|3|08059011902|200|5
+    /// ";
+    /// let records = RelationshipFile::CensusTract.read(contents.as_bytes()).unwrap();
+    /// assert_eq!(records.len(), 2);
+    /// assert_eq!(records[0].geoid_2010, "08059011901".parse::<Geoid>().unwrap());
+    /// assert_eq!(records[1].area_land_part, 200.0);
+    /// ```
+    pub fn read<R: BufRead>(&self, reader: R) -> Result<Vec<RelationshipRecord>, CensusError> {
+        let mut lines = reader.lines();
+        let header_line = lines
+            .next()
+            .ok_or_else(|| {
+                CensusError::ResponseSchema(format!("relationship file {} is empty", self))
+            })?
+            .map_err(|e| self.read_error(e))?;
+        let header = header_line
+            .trim_start_matches('\u{feff}')
+            .split('|')
+            .map(|h| h.trim().to_uppercase())
+            .collect_vec();
+        let columns = RelationshipColumns::new(self, &header)?;
+        let geoid_type = self.geoid_type();
+
+        let mut records = vec![];
+        for line in lines {
+            let line = line.map_err(|e| self.read_error(e))?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            let row = line.split('|').map(str::trim).collect_vec();
+            let record = columns.record(&row, &geoid_type).map_err(|e| {
+                CensusError::ResponseSchema(format!(
+                    "invalid row in relationship file {}: '{}': {}",
+                    self, line, e
+                ))
+            })?;
+            records.push(record);
+        }
+        Ok(records)
+    }
+
+    fn read_error(&self, e: std::io::Error) -> CensusError {
+        CensusError::Archive {
+            message: format!("failure reading relationship file {}", self),
+            source: Box::new(e),
+        }
+    }
+}
+
+impl Display for RelationshipFile {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.filename())
+    }
+}
+
+/// column positions of a relationship file. tract and block group files carry full
+/// GEOIDs, block files split GEOIDs into their state, county, tract and block parts.
+enum RelationshipColumns {
+    Geoid {
+        geoid_2010: usize,
+        geoid_2020: usize,
+        area_land: usize,
+        area_water: usize,
+    },
+    Components {
+        geoid_2010: [usize; 4],
+        geoid_2020: [usize; 4],
+        area_land: usize,
+        area_water: usize,
+    },
+}
+
+impl RelationshipColumns {
+    fn new(file: &RelationshipFile, header: &[String]) -> Result<RelationshipColumns, CensusError> {
+        let find = |predicate: &dyn Fn(&str) -> bool, description: &str| {
+            header.iter().position(|h| predicate(h)).ok_or_else(|| {
+                CensusError::ResponseSchema(format!(
+                    "relationship file {} is missing a {} column, found [{}]",
+                    file,
+                    description,
+                    header.join(", ")
+                ))
+            })
+        };
+        let column = |name: &str| find(&|h: &str| h == name, name);
+        match file {
+            RelationshipFile::CensusTract | RelationshipFile::BlockGroup => {
+                Ok(RelationshipColumns::Geoid {
+                    geoid_2010: find(
+                        &|h: &str| h.starts_with("GEOID_") && h.ends_with("_10"),
+                        "2010 GEOID",
+                    )?,
+                    geoid_2020: find(
+                        &|h: &str| h.starts_with("GEOID_") && h.ends_with("_20"),
+                        "2020 GEOID",
+                    )?,
+                    area_land: column("AREALAND_PART")?,
+                    area_water: column("AREAWATER_PART")?,
+                })
+            }
+            RelationshipFile::Block { .. } => {
+                let components = |year: &str| -> Result<[usize; 4], CensusError> {
+                    Ok([
+                        column(&format!("STATE_{}", year))?,
+                        column(&format!("COUNTY_{}", year))?,
+                        column(&format!("TRACT_{}", year))?,
+                        column(&format!("BLK_{}", year))?,
+                    ])
+                };
+                Ok(RelationshipColumns::Components {
+                    geoid_2010: components("2010")?,
+                    geoid_2020: components("2020")?,
+                    area_land: column("AREALAND_INT")?,
+                    area_water: column("AREAWATER_INT")?,
+                })
+            }
+        }
+    }
+
+    fn record(
+        &self,
+        row: &[&str],
+        geoid_type: &GeoidType,
+    ) -> Result<RelationshipRecord, CensusError> {
+        let get = |idx: usize| {
+            row.get(idx).copied().ok_or_else(|| {
+                CensusError::ResponseSchema(format!("missing value for column {}", idx))
+            })
+        };
+        let area = |idx: usize| -> Result<f64, CensusError> {
+            let value = get(idx)?;
+            if value.is_empty() {
+                return Ok(0.0);
+            }
+            value.parse::<f64>().map_err(|e| {
+                CensusError::ResponseSchema(format!("invalid area '{}': {}", value, e))
+            })
+        };
+        let geoid = |idxs: &[usize]| -> Result<Geoid, CensusError> {
+            let digits = idxs
+                .iter()
+                .map(|idx| get(*idx))
+                .collect::<Result<String, _>>()?;
+            Geoid::parse_with_type(&digits, geoid_type)
+        };
+        match self {
+            RelationshipColumns::Geoid {
+                geoid_2010,
+                geoid_2020,
+                area_land,
+                area_water,
+            } => Ok(RelationshipRecord {
+                geoid_2010: geoid(&[*geoid_2010])?,
+                geoid_2020: geoid(&[*geoid_2020])?,
+                area_land_part: area(*area_land)?,
+                area_water_part: area(*area_water)?,
+            }),
+            RelationshipColumns::Components {
+                geoid_2010,
+                geoid_2020,
+                area_land,
+                area_water,
+            } => Ok(RelationshipRecord {
+                geoid_2010: geoid(geoid_2010)?,
+                geoid_2020: geoid(geoid_2020)?,
+                area_land_part: area(*area_land)?,
+                area_water_part: area(*area_water)?,
+            }),
+        }
+    }
+}
@@ -0,0 +1,30 @@
+use serde::{Deserialize, Serialize};
+
+use crate::error::CensusError;
+use crate::model::identifier::{GeographyVintage, Geoid};
+
+/// a row of a 2010 to 2020 relationship file, describing the intersection of
+/// a 2010 area with a 2020 area. areas are in square meters.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct RelationshipRecord {
+    pub geoid_2010: Geoid,
+    pub geoid_2020: Geoid,
+    /// land area of the intersection
+    pub area_land_part: f64,
+    /// water area of the intersection
+    pub area_water_part: f64,
+}
+
+impl RelationshipRecord {
+    /// the GEOID of this intersection in the given geography vintage.
+    pub fn geoid(&self, vintage: GeographyVintage) -> Result<&Geoid, CensusError> {
+        match vintage {
+            GeographyVintage::Census2010 => Ok(&self.geoid_2010),
+            GeographyVintage::Census2020 => Ok(&self.geoid_2020),
+            other => Err(CensusError::InvalidQuery(format!(
+                "relationship files only relate 2010 and 2020 census geographies, found {}",
+                other
+            ))),
+        }
+    }
+}
@@ -0,0 +1,135 @@
+use crate::error::CensusError;
+use crate::model::acs::{AcsTable, AcsType};
+use crate::model::decennial::DecennialType;
+use crate::model::identifier::GeoidType;
+use serde::{Deserialize, Serialize};
+use std::fmt::Display;
+
+/// a dataset of the Census Data API that is queried by variable and geography, such as
+/// the five-year ACS detailed tables, the one-year ACS subject tables or the 2020 P.L.
+/// 94-171 redistricting data.
+///
+/// # Examples
+///
+/// ```rust
+/// use us_census_core::model::acs::{AcsTable, AcsType};
+/// use us_census_core::model::dataset::CensusDataset;
+/// use us_census_core::model::decennial::DecennialType;
+/// use us_census_core::model::identifier::GeoidType;
+///
+/// let acs = CensusDataset::from(AcsType::FiveYear);
+/// assert_eq!(acs.to_directory_name(), "acs/acs5");
+/// assert!(!acs.supports_geoid_type(&GeoidType::Block));
+///
+/// let subject = CensusDataset::Acs(AcsType::FiveYear, AcsTable::Subject);
+/// assert_eq!(subject.to_directory_name(), "acs/acs5/subject");
+/// assert!(subject.validate_variable("S0101_C01_001E").is_ok());
+/// assert!(subject.validate_variable("B01001_001E").is_err());
+///
+/// let pl = CensusDataset::from(DecennialType::Pl);
+/// assert_eq!(pl.to_directory_name(), "dec/pl");
+/// assert!(pl.supports_geoid_type(&GeoidType::Block));
+/// assert!(pl.validate_year(2020).is_ok());
+/// assert!(pl.validate_year(2022).is_err());
+/// ```
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum CensusDataset {
+    Acs(AcsType, AcsTable),
+    Decennial(DecennialType),
+}
+
+impl Display for CensusDataset {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CensusDataset::Acs(acs_type, AcsTable::Detailed) => write!(f, "{}", acs_type),
+            CensusDataset::Acs(acs_type, table) => write!(f, "{}-{}", acs_type, table),
+            CensusDataset::Decennial(dec_type) => write!(f, "dec-{}", dec_type),
+        }
+    }
+}
+
+impl From<AcsType> for CensusDataset {
+    fn from(value: AcsType) -> Self {
+        CensusDataset::Acs(value, AcsTable::Detailed)
+    }
+}
+
+impl From<DecennialType> for CensusDataset {
+    fn from(value: DecennialType) -> Self {
+        CensusDataset::Decennial(value)
+    }
+}
+
+impl CensusDataset {
+    /// the path of this dataset below the year in Census Data API URLs.
+    pub fn to_directory_name(&self) -> String {
+        match self {
+            CensusDataset::Acs(acs_type, table) => format!(
+                "acs/{}{}",
+                acs_type.to_directory_name(),
+                table.to_directory_suffix()
+            ),
+            CensusDataset::Decennial(dec_type) => {
+                format!("dec/{}", dec_type.to_directory_name())
+            }
+        }
+    }
+
+    /// the ACS survey of this dataset, if it is an ACS dataset.
+    pub fn acs_type(&self) -> Option<AcsType> {
+        match self {
+            CensusDataset::Acs(acs_type, _) => Some(*acs_type),
+            CensusDataset::Decennial(_) => None,
+        }
+    }
+
+    /// true if this dataset publishes data at a GeoidType. blocks are only published
+    /// by the Decennial Census.
+    pub fn supports_geoid_type(&self, geoid_type: &GeoidType) -> bool {
+        !matches!(
+            (self, geoid_type),
+            (CensusDataset::Acs(_, _), GeoidType::Block)
+        )
+    }
+
+    /// checks that this dataset was published for a year.
+    pub fn validate_year(&self, year: u64) -> Result<(), CensusError> {
+        match self {
+            CensusDataset::Acs(_, _) => Ok(()),
+            CensusDataset::Decennial(dec_type) => dec_type.validate_year(year),
+        }
+    }
+
+    /// checks that a variable follows the naming of this dataset's ACS table family.
+    pub fn validate_variable(&self, name: &str) -> Result<(), CensusError> {
+        match self {
+            CensusDataset::Acs(_, table) => table.validate_variable(name),
+            CensusDataset::Decennial(_) => Ok(()),
+        }
+    }
+}
+
+/// true for the variables of the tables of any dataset, such as `B01001_001E`,
+/// `S0101_C01_001E` or the decennial `P1_001N`, including their annotation variables.
+/// false for the geography and label variables returned alongside them, such as `NAME`,
+/// `GEO_ID` or `SUMLEVEL`, whose table id has no number.
+///
+/// # Examples
+///
+/// ```rust
+/// use us_census_core::model::dataset::census_dataset::is_table_variable;
+///
+/// assert!(is_table_variable("B01001_001E"));
+/// assert!(is_table_variable("S1701_C03_001E"));
+/// assert!(is_table_variable("PCT12_001N"));
+/// assert!(!is_table_variable("NAME"));
+/// assert!(!is_table_variable("GEO_ID"));
+/// ```
+pub fn is_table_variable(name: &str) -> bool {
+    let Some((table_id, _)) = name.split_once('_') else {
+        return false;
+    };
+    let number = table_id.trim_start_matches(|c: char| c.is_ascii_uppercase());
+    number.len() < table_id.len() && number.starts_with(|c: char| c.is_ascii_digit())
+}
@@ -0,0 +1,3 @@
+pub mod census_dataset;
+
+pub use census_dataset::CensusDataset;
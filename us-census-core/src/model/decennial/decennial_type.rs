@@ -0,0 +1,47 @@
+use crate::error::CensusError;
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
+use std::fmt::Display;
+
+/// summary files of the Decennial Census available from the Census Data API. unlike
+/// the ACS, decennial summary files are published down to the block level.
+#[derive(Serialize, Deserialize, ValueEnum, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum DecennialType {
+    /// P.L. 94-171 redistricting data: population by race and ethnicity, voting age
+    /// population, group quarters and housing occupancy
+    Pl,
+    /// Demographic and Housing Characteristics file, published from 2020
+    Dhc,
+}
+
+impl Display for DecennialType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DecennialType::Pl => write!(f, "pl"),
+            DecennialType::Dhc => write!(f, "dhc"),
+        }
+    }
+}
+
+impl DecennialType {
+    pub fn to_directory_name(&self) -> String {
+        self.to_string()
+    }
+
+    /// checks that this summary file was published for a Census year.
+    pub fn validate_year(&self, year: u64) -> Result<(), CensusError> {
+        let valid = match self {
+            DecennialType::Pl => year == 2010 || year == 2020,
+            DecennialType::Dhc => year == 2020,
+        };
+        if valid {
+            Ok(())
+        } else {
+            Err(CensusError::InvalidQuery(format!(
+                "decennial summary file {} is not available for year {}",
+                self, year
+            )))
+        }
+    }
+}
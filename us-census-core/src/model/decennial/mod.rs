@@ -0,0 +1,3 @@
+pub mod decennial_type;
+
+pub use decennial_type::DecennialType;
@@ -0,0 +1,59 @@
+use std::fmt::Display;
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::CensusError;
+use crate::model::identifier::{GeographyVintage, HasGeographyVintage};
+use crate::ops::agg::CanAggregateAsNumber;
+
+/// the value of a [`super::DerivedVariable`] for a GEOID.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct DerivedValue {
+    pub name: String,
+    pub value: f64,
+    /// geography vintage of the values this value was derived from, if known
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub vintage: Option<GeographyVintage>,
+}
+
+impl DerivedValue {
+    pub fn new(name: String, value: f64) -> DerivedValue {
+        DerivedValue {
+            name,
+            value,
+            vintage: None,
+        }
+    }
+
+    pub fn with_vintage(self, vintage: Option<GeographyVintage>) -> DerivedValue {
+        DerivedValue { vintage, ..self }
+    }
+}
+
+impl HasGeographyVintage for DerivedValue {
+    fn geography_vintage(&self) -> Option<GeographyVintage> {
+        self.vintage
+    }
+}
+
+impl Display for DerivedValue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {}", self.name, self.value)
+    }
+}
+
+impl CanAggregateAsNumber for DerivedValue {
+    type Key = String;
+
+    fn aggregation_key(&self) -> String {
+        self.name.clone()
+    }
+
+    fn aggregation_value(&self) -> Result<f64, CensusError> {
+        Ok(self.value)
+    }
+
+    fn from_aggregate(key: String, value: f64, vintage: Option<GeographyVintage>) -> DerivedValue {
+        DerivedValue::new(key, value).with_vintage(vintage)
+    }
+}
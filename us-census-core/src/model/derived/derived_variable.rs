@@ -0,0 +1,78 @@
+use std::fmt::Display;
+use std::str::FromStr;
+
+use itertools::Itertools;
+
+use super::Expression;
+use crate::error::CensusError;
+
+/// a named expression computed from the values of each GEOID, written `name = expression`,
+/// such as `male_share = B01001_002E / B01001_001E`.
+///
+/// # Examples
+///
+/// ```rust
+/// use us_census_core::model::derived::DerivedVariable;
+///
+/// let derived = DerivedVariable::parse_list(
+///     "male_share = B01001_002E / B01001_001E; retail_share = CNS07 / C000"
+/// ).unwrap();
+/// assert_eq!(derived.len(), 2);
+/// assert_eq!(derived[0].name, "male_share");
+/// assert_eq!(derived[1].expression.variables(), vec!["CNS07", "C000"]);
+///
+/// assert!("B01001_002E / B01001_001E".parse::<DerivedVariable>().is_err());
+/// assert!("male share = B01001_002E".parse::<DerivedVariable>().is_err());
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct DerivedVariable {
+    pub name: String,
+    pub expression: Expression,
+}
+
+impl DerivedVariable {
+    pub fn new(name: &str, expression: Expression) -> DerivedVariable {
+        DerivedVariable {
+            name: String::from(name),
+            expression,
+        }
+    }
+
+    /// parses a semicolon-delimited list of derived variables.
+    pub fn parse_list(s: &str) -> Result<Vec<DerivedVariable>, CensusError> {
+        s.split(';')
+            .map(str::trim)
+            .filter(|d| !d.is_empty())
+            .map(DerivedVariable::from_str)
+            .try_collect()
+    }
+}
+
+impl FromStr for DerivedVariable {
+    type Err = CensusError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (name, expression) = s.split_once('=').ok_or_else(|| {
+            CensusError::InvalidQuery(format!(
+                "derived variable '{}' must be written as 'name = expression'",
+                s
+            ))
+        })?;
+        let name = name.trim();
+        let valid_name = name.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_')
+            && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+        if !valid_name {
+            return Err(CensusError::InvalidQuery(format!(
+                "invalid derived variable name '{}', expected letters, digits and underscores",
+                name
+            )));
+        }
+        Ok(DerivedVariable::new(name, expression.parse()?))
+    }
+}
+
+impl Display for DerivedVariable {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} = {}", self.name, self.expression)
+    }
+}
@@ -0,0 +1,276 @@
+use std::fmt::Display;
+use std::str::FromStr;
+
+use crate::error::CensusError;
+
+/// arithmetic operators of an [`Expression`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Operator {
+    Add,
+    Subtract,
+    Multiply,
+    Divide,
+}
+
+impl Operator {
+    pub fn apply(&self, lhs: f64, rhs: f64) -> f64 {
+        match self {
+            Operator::Add => lhs + rhs,
+            Operator::Subtract => lhs - rhs,
+            Operator::Multiply => lhs * rhs,
+            Operator::Divide => lhs / rhs,
+        }
+    }
+}
+
+impl Display for Operator {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Operator::Add => write!(f, "+"),
+            Operator::Subtract => write!(f, "-"),
+            Operator::Multiply => write!(f, "*"),
+            Operator::Divide => write!(f, "/"),
+        }
+    }
+}
+
+/// an arithmetic expression over named variables, such as ACS variables or WAC segments.
+/// expressions support numbers, variables, parentheses, unary negation and the `+`, `-`,
+/// `*` and `/` operators with the usual precedence. division follows floating point
+/// semantics, so dividing by zero yields an infinite or NaN value.
+///
+/// # Examples
+///
+/// ```rust
+/// use us_census_core::model::derived::Expression;
+///
+/// let expression: Expression = "(CNS07 + CNS18) / C000 * 100".parse().unwrap();
+/// assert_eq!(expression.variables(), vec!["CNS07", "CNS18", "C000"]);
+///
+/// let result = expression.evaluate(&mut |name| match name {
+///     "C000" => Ok(200.0),
+///     _ => Ok(25.0),
+/// });
+/// assert_eq!(result.unwrap(), 25.0);
+/// assert_eq!(expression.to_string(), "(((CNS07 + CNS18) / C000) * 100)");
+///
+/// assert!("B01001_002E / ".parse::<Expression>().is_err());
+/// assert!("B01001_002E $ 2".parse::<Expression>().is_err());
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub enum Expression {
+    Number(f64),
+    Variable(String),
+    Negate(Box<Expression>),
+    Binary(Box<Expression>, Operator, Box<Expression>),
+}
+
+impl Expression {
+    /// the variables referenced by this expression, in order of first appearance.
+    pub fn variables(&self) -> Vec<&str> {
+        let mut result = vec![];
+        self.collect_variables(&mut result);
+        result
+    }
+
+    fn collect_variables<'a>(&'a self, acc: &mut Vec<&'a str>) {
+        match self {
+            Expression::Number(_) => {}
+            Expression::Variable(name) => {
+                if !acc.contains(&name.as_str()) {
+                    acc.push(name);
+                }
+            }
+            Expression::Negate(inner) => inner.collect_variables(acc),
+            Expression::Binary(lhs, _, rhs) => {
+                lhs.collect_variables(acc);
+                rhs.collect_variables(acc);
+            }
+        }
+    }
+
+    /// evaluates this expression, looking up the value of each variable.
+    pub fn evaluate(
+        &self,
+        lookup: &mut dyn FnMut(&str) -> Result<f64, CensusError>,
+    ) -> Result<f64, CensusError> {
+        match self {
+            Expression::Number(x) => Ok(*x),
+            Expression::Variable(name) => lookup(name),
+            Expression::Negate(inner) => Ok(-inner.evaluate(lookup)?),
+            Expression::Binary(lhs, op, rhs) => {
+                let lhs = lhs.evaluate(lookup)?;
+                let rhs = rhs.evaluate(lookup)?;
+                Ok(op.apply(lhs, rhs))
+            }
+        }
+    }
+}
+
+impl Display for Expression {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Expression::Number(x) => write!(f, "{}", x),
+            Expression::Variable(name) => write!(f, "{}", name),
+            Expression::Negate(inner) => write!(f, "-{}", inner),
+            Expression::Binary(lhs, op, rhs) => write!(f, "({} {} {})", lhs, op, rhs),
+        }
+    }
+}
+
+impl FromStr for Expression {
+    type Err = CensusError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid =
+            |msg: String| CensusError::InvalidQuery(format!("invalid expression '{}': {}", s, msg));
+        let tokens = tokenize(s).map_err(invalid)?;
+        let mut parser = Parser { tokens, pos: 0 };
+        let expression = parser.expression().map_err(invalid)?;
+        match parser.peek() {
+            None => Ok(expression),
+            Some(token) => Err(invalid(format!("unexpected '{}'", token))),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Token {
+    Number(f64),
+    Identifier(String),
+    Operator(Operator),
+    Open,
+    Close,
+}
+
+impl Display for Token {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Token::Number(x) => write!(f, "{}", x),
+            Token::Identifier(name) => write!(f, "{}", name),
+            Token::Operator(op) => write!(f, "{}", op),
+            Token::Open => write!(f, "("),
+            Token::Close => write!(f, ")"),
+        }
+    }
+}
+
+fn tokenize(s: &str) -> Result<Vec<Token>, String> {
+    let chars = s.chars().collect::<Vec<_>>();
+    let mut tokens = vec![];
+    let mut idx = 0;
+    while idx < chars.len() {
+        let c = chars[idx];
+        let token = match c {
+            _ if c.is_whitespace() => {
+                idx += 1;
+                continue;
+            }
+            '+' => Token::Operator(Operator::Add),
+            '-' => Token::Operator(Operator::Subtract),
+            '*' => Token::Operator(Operator::Multiply),
+            '/' => Token::Operator(Operator::Divide),
+            '(' => Token::Open,
+            ')' => Token::Close,
+            _ if c.is_ascii_digit() || c == '.' => {
+                let start = idx;
+                while idx < chars.len() && (chars[idx].is_ascii_digit() || chars[idx] == '.') {
+                    idx += 1;
+                }
+                let literal = chars[start..idx].iter().collect::<String>();
+                let number = literal
+                    .parse::<f64>()
+                    .map_err(|e| format!("invalid number '{}': {}", literal, e))?;
+                tokens.push(Token::Number(number));
+                continue;
+            }
+            _ if c.is_ascii_alphabetic() || c == '_' => {
+                let start = idx;
+                while idx < chars.len() && (chars[idx].is_ascii_alphanumeric() || chars[idx] == '_')
+                {
+                    idx += 1;
+                }
+                tokens.push(Token::Identifier(chars[start..idx].iter().collect()));
+                continue;
+            }
+            _ => return Err(format!("unexpected character '{}'", c)),
+        };
+        tokens.push(token);
+        idx += 1;
+    }
+    Ok(tokens)
+}
+
+/// recursive descent parser over the grammar
+///
+/// ```text
+/// expression := term (('+' | '-') term)*
+/// term       := unary (('*' | '/') unary)*
+/// unary      := '-' unary | primary
+/// primary    := number | variable | '(' expression ')'
+/// ```
+struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.pos).cloned();
+        self.pos += 1;
+        token
+    }
+
+    fn expression(&mut self) -> Result<Expression, String> {
+        let mut lhs = self.term()?;
+        while let Some(Token::Operator(op @ (Operator::Add | Operator::Subtract))) = self.peek() {
+            let op = *op;
+            self.pos += 1;
+            let rhs = self.term()?;
+            lhs = Expression::Binary(Box::new(lhs), op, Box::new(rhs));
+        }
+        Ok(lhs)
+    }
+
+    fn term(&mut self) -> Result<Expression, String> {
+        let mut lhs = self.unary()?;
+        while let Some(Token::Operator(op @ (Operator::Multiply | Operator::Divide))) = self.peek()
+        {
+            let op = *op;
+            self.pos += 1;
+            let rhs = self.unary()?;
+            lhs = Expression::Binary(Box::new(lhs), op, Box::new(rhs));
+        }
+        Ok(lhs)
+    }
+
+    fn unary(&mut self) -> Result<Expression, String> {
+        if let Some(Token::Operator(Operator::Subtract)) = self.peek() {
+            self.pos += 1;
+            let inner = self.unary()?;
+            return Ok(Expression::Negate(Box::new(inner)));
+        }
+        self.primary()
+    }
+
+    fn primary(&mut self) -> Result<Expression, String> {
+        match self.next() {
+            Some(Token::Number(x)) => Ok(Expression::Number(x)),
+            Some(Token::Identifier(name)) => Ok(Expression::Variable(name)),
+            Some(Token::Open) => {
+                let inner = self.expression()?;
+                match self.next() {
+                    Some(Token::Close) => Ok(inner),
+                    Some(token) => Err(format!("expected ')', found '{}'", token)),
+                    None => Err(String::from("expected ')', found end of expression")),
+                }
+            }
+            Some(token) => Err(format!("unexpected '{}'", token)),
+            None => Err(String::from("unexpected end of expression")),
+        }
+    }
+}
@@ -0,0 +1,7 @@
+pub mod derived_value;
+pub mod derived_variable;
+pub mod expression;
+
+pub use derived_value::DerivedValue;
+pub use derived_variable::DerivedVariable;
+pub use expression::{Expression, Operator};
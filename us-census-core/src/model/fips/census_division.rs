@@ -0,0 +1,102 @@
+use super::{census_region::CensusRegion, state_code::StateCode};
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
+use std::fmt::Display;
+
+/// the nine Census divisions, which group the 50 states and the District of Columbia
+/// into the four Census regions. Puerto Rico and the island areas are not part of any
+/// division.
+#[derive(Serialize, Deserialize, ValueEnum, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum CensusDivision {
+    NewEngland,
+    MiddleAtlantic,
+    EastNorthCentral,
+    WestNorthCentral,
+    SouthAtlantic,
+    EastSouthCentral,
+    WestSouthCentral,
+    Mountain,
+    Pacific,
+}
+
+impl CensusDivision {
+    pub const ALL: [CensusDivision; 9] = [
+        CensusDivision::NewEngland,
+        CensusDivision::MiddleAtlantic,
+        CensusDivision::EastNorthCentral,
+        CensusDivision::WestNorthCentral,
+        CensusDivision::SouthAtlantic,
+        CensusDivision::EastSouthCentral,
+        CensusDivision::WestSouthCentral,
+        CensusDivision::Mountain,
+        CensusDivision::Pacific,
+    ];
+
+    /// the Census division code, 1 through 9.
+    pub fn code(&self) -> u64 {
+        match self {
+            CensusDivision::NewEngland => 1,
+            CensusDivision::MiddleAtlantic => 2,
+            CensusDivision::EastNorthCentral => 3,
+            CensusDivision::WestNorthCentral => 4,
+            CensusDivision::SouthAtlantic => 5,
+            CensusDivision::EastSouthCentral => 6,
+            CensusDivision::WestSouthCentral => 7,
+            CensusDivision::Mountain => 8,
+            CensusDivision::Pacific => 9,
+        }
+    }
+
+    pub fn region(&self) -> CensusRegion {
+        match self {
+            CensusDivision::NewEngland => CensusRegion::Northeast,
+            CensusDivision::MiddleAtlantic => CensusRegion::Northeast,
+            CensusDivision::EastNorthCentral => CensusRegion::Midwest,
+            CensusDivision::WestNorthCentral => CensusRegion::Midwest,
+            CensusDivision::SouthAtlantic => CensusRegion::South,
+            CensusDivision::EastSouthCentral => CensusRegion::South,
+            CensusDivision::WestSouthCentral => CensusRegion::South,
+            CensusDivision::Mountain => CensusRegion::West,
+            CensusDivision::Pacific => CensusRegion::West,
+        }
+    }
+
+    /// the states (and District of Columbia) in this division.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use us_census_core::model::fips::{CensusDivision, state_code::StateCode};
+    ///
+    /// let mountain = CensusDivision::Mountain.states();
+    /// assert_eq!(mountain.len(), 8);
+    /// assert!(mountain.contains(&StateCode::Colorado));
+    /// ```
+    pub fn states(&self) -> Vec<StateCode> {
+        StateCode::ALL
+            .into_iter()
+            .filter(|sc| sc.division() == Some(*self))
+            .collect()
+    }
+
+    pub fn to_full_name(&self) -> String {
+        match self {
+            CensusDivision::NewEngland => String::from("New England"),
+            CensusDivision::MiddleAtlantic => String::from("Middle Atlantic"),
+            CensusDivision::EastNorthCentral => String::from("East North Central"),
+            CensusDivision::WestNorthCentral => String::from("West North Central"),
+            CensusDivision::SouthAtlantic => String::from("South Atlantic"),
+            CensusDivision::EastSouthCentral => String::from("East South Central"),
+            CensusDivision::WestSouthCentral => String::from("West South Central"),
+            CensusDivision::Mountain => String::from("Mountain"),
+            CensusDivision::Pacific => String::from("Pacific"),
+        }
+    }
+}
+
+impl Display for CensusDivision {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.to_full_name())
+    }
+}
@@ -0,0 +1,26 @@
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
+use std::fmt::Display;
+
+/// the US Census data products supported by this library. used to describe
+/// which states and territories are published by each product.
+#[derive(Serialize, Deserialize, ValueEnum, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum CensusProduct {
+    /// American Community Survey
+    Acs,
+    /// LEHD Origin-Destination Employment Statistics
+    Lodes,
+    /// TIGER/Lines geometries
+    Tiger,
+}
+
+impl Display for CensusProduct {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CensusProduct::Acs => write!(f, "ACS"),
+            CensusProduct::Lodes => write!(f, "LODES"),
+            CensusProduct::Tiger => write!(f, "TIGER/Lines"),
+        }
+    }
+}
@@ -0,0 +1,74 @@
+use super::{census_division::CensusDivision, state_code::StateCode};
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
+use std::fmt::Display;
+
+/// the four Census regions. Puerto Rico and the island areas are not part of any region.
+#[derive(Serialize, Deserialize, ValueEnum, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum CensusRegion {
+    Northeast,
+    Midwest,
+    South,
+    West,
+}
+
+impl CensusRegion {
+    pub const ALL: [CensusRegion; 4] = [
+        CensusRegion::Northeast,
+        CensusRegion::Midwest,
+        CensusRegion::South,
+        CensusRegion::West,
+    ];
+
+    /// the Census region code, 1 through 4.
+    pub fn code(&self) -> u64 {
+        match self {
+            CensusRegion::Northeast => 1,
+            CensusRegion::Midwest => 2,
+            CensusRegion::South => 3,
+            CensusRegion::West => 4,
+        }
+    }
+
+    pub fn divisions(&self) -> Vec<CensusDivision> {
+        CensusDivision::ALL
+            .into_iter()
+            .filter(|d| d.region() == *self)
+            .collect()
+    }
+
+    /// the states (and District of Columbia) in this region.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use us_census_core::model::fips::{CensusRegion, state_code::StateCode};
+    ///
+    /// let west = CensusRegion::West.states();
+    /// assert_eq!(west.len(), 13);
+    /// assert!(west.contains(&StateCode::Hawaii));
+    /// assert_eq!(CensusRegion::South.states().len(), 17);
+    /// ```
+    pub fn states(&self) -> Vec<StateCode> {
+        StateCode::ALL
+            .into_iter()
+            .filter(|sc| sc.region() == Some(*self))
+            .collect()
+    }
+
+    pub fn to_full_name(&self) -> String {
+        match self {
+            CensusRegion::Northeast => String::from("Northeast"),
+            CensusRegion::Midwest => String::from("Midwest"),
+            CensusRegion::South => String::from("South"),
+            CensusRegion::West => String::from("West"),
+        }
+    }
+}
+
+impl Display for CensusRegion {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.to_full_name())
+    }
+}
@@ -0,0 +1,306 @@
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::sync::OnceLock;
+
+use itertools::Itertools;
+use serde::{Deserialize, Serialize};
+
+use super::state_code::{normalize_name, StateCode};
+use crate::error::CensusError;
+use crate::model::identifier::{fips, Geoid};
+
+/// county and county-equivalent records for the states, District of Columbia,
+/// Puerto Rico and the island areas, with columns `geoid,name,first_vintage,last_vintage`.
+/// an empty vintage bound means the record has no known start or end.
+const COUNTY_REGISTRY_CSV: &str = include_str!("county_registry.csv");
+
+/// legal/statistical area descriptions that may be omitted when looking up a county by name.
+const LEGAL_SUFFIXES: [&str; 11] = [
+    "county",
+    "parish",
+    "borough",
+    "cityandborough",
+    "censusarea",
+    "municipality",
+    "municipio",
+    "city",
+    "planningregion",
+    "district",
+    "island",
+];
+
+/// a county or county-equivalent area, such as a Louisiana parish, an Alaska borough,
+/// an independent city or a Connecticut planning region.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct CountyRecord {
+    pub state: StateCode,
+    pub county: fips::County,
+    /// the legal/statistical area name, such as "Jefferson County" or "Baltimore city"
+    pub name: String,
+    /// first geography vintage (year) this county is published in, if bounded
+    pub first_vintage: Option<u64>,
+    /// last geography vintage (year) this county is published in, if bounded
+    pub last_vintage: Option<u64>,
+}
+
+impl CountyRecord {
+    pub fn geoid(&self) -> Geoid {
+        Geoid::County(self.state.into(), self.county)
+    }
+
+    /// true if this county exists in the geography of the given vintage (year).
+    pub fn is_valid_for(&self, vintage: u64) -> bool {
+        self.first_vintage.is_none_or(|first| first <= vintage)
+            && self.last_vintage.is_none_or(|last| vintage <= last)
+    }
+
+    /// the county name qualified by state abbreviation, such as "Jefferson County, CO".
+    pub fn qualified_name(&self) -> String {
+        format!("{}, {}", self.name, self.state.to_state_abbreviation())
+    }
+
+    fn vintage_range(&self) -> String {
+        let first = self
+            .first_vintage
+            .map(|v| v.to_string())
+            .unwrap_or_default();
+        let last = self.last_vintage.map(|v| v.to_string()).unwrap_or_default();
+        format!("[{}, {}]", first, last)
+    }
+
+    /// true if the (normalized) name matches this county's name, with or without
+    /// its legal/statistical area description.
+    fn matches_name(&self, normalized: &str) -> bool {
+        let own = normalize_name(&self.name);
+        own == normalized
+            || LEGAL_SUFFIXES
+                .iter()
+                .any(|suffix| own.strip_suffix(suffix) == Some(normalized))
+    }
+}
+
+impl Display for CountyRecord {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} ({})", self.qualified_name(), self.geoid())
+    }
+}
+
+/// national registry of counties and county equivalents, used to validate county
+/// GEOIDs and to look up counties by name before running any downloads.
+///
+/// counties that were created, renamed or dissolved carry the range of geography
+/// vintages they are valid for. for example, Connecticut's 8 counties were replaced
+/// by 9 planning regions as county equivalents beginning with 2022 geographies.
+///
+/// # Examples
+///
+/// ```rust
+/// use us_census_core::model::fips::{CountyRegistry, state_code::StateCode};
+/// use us_census_core::model::identifier::{fips, Geoid};
+///
+/// let registry = CountyRegistry::embedded();
+/// let jefferson = registry.find_by_name("Jefferson County, CO", None).unwrap();
+/// assert_eq!(jefferson.geoid(), Geoid::County(fips::State(8), fips::County(59)));
+/// assert_eq!(registry.counties_in_state(StateCode::Colorado, None).len(), 64);
+/// assert!(registry.validate(&Geoid::County(fips::State(8), fips::County(999)), None).is_err());
+///
+/// // Fairfield County, CT was replaced by planning regions in 2022
+/// let fairfield = Geoid::County(fips::State(9), fips::County(1));
+/// assert!(registry.validate(&fairfield, Some(2020)).is_ok());
+/// assert!(registry.validate(&fairfield, Some(2022)).is_err());
+/// assert_eq!(registry.counties_in_state(StateCode::Connecticut, Some(2022)).len(), 9);
+/// ```
+#[derive(Clone, Debug)]
+pub struct CountyRegistry {
+    records: Vec<CountyRecord>,
+    index: HashMap<(u64, u64), usize>,
+}
+
+impl CountyRegistry {
+    /// the registry compiled into this library.
+    pub fn embedded() -> &'static CountyRegistry {
+        static REGISTRY: OnceLock<CountyRegistry> = OnceLock::new();
+        REGISTRY.get_or_init(|| {
+            CountyRegistry::from_csv(COUNTY_REGISTRY_CSV)
+                .expect("embedded county registry should be valid")
+        })
+    }
+
+    /// builds a registry from CSV rows of `geoid,name,first_vintage,last_vintage`
+    /// following a header row.
+    pub fn from_csv(data: &str) -> Result<CountyRegistry, CensusError> {
+        let records = data
+            .lines()
+            .skip(1)
+            .filter(|line| !line.trim().is_empty())
+            .map(parse_record)
+            .collect::<Result<Vec<_>, _>>()?;
+        let mut index = HashMap::new();
+        for (idx, record) in records.iter().enumerate() {
+            let key = (fips::State::from(record.state).0, record.county.0);
+            if index.insert(key, idx).is_some() {
+                return Err(CensusError::Identifier(format!(
+                    "county registry has duplicate entries for {}",
+                    record.geoid()
+                )));
+            }
+        }
+        Ok(CountyRegistry { records, index })
+    }
+
+    pub fn len(&self) -> usize {
+        self.records.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.records.is_empty()
+    }
+
+    /// iterates over all counties in this registry, of any vintage, in GEOID order.
+    pub fn iter(&self) -> impl Iterator<Item = &CountyRecord> {
+        self.records.iter()
+    }
+
+    /// finds the county of a GEOID containing a county component. when a vintage is
+    /// provided, only counties valid for that vintage are returned.
+    pub fn get(&self, geoid: &Geoid, vintage: Option<u64>) -> Option<&CountyRecord> {
+        let record = self.get_any_vintage(geoid)?;
+        match vintage {
+            Some(v) if !record.is_valid_for(v) => None,
+            _ => Some(record),
+        }
+    }
+
+    /// confirms that a GEOID refers to a real state and, if it has a county component,
+    /// a county in this registry that is valid for the vintage, if provided. GEOIDs without
+    /// a county component, such as places and ZCTAs, are only checked for their state.
+    pub fn validate(&self, geoid: &Geoid, vintage: Option<u64>) -> Result<(), CensusError> {
+        if let Ok(Geoid::State(state)) = geoid.to_state() {
+            StateCode::try_from(state)?;
+        }
+        if geoid.to_county().is_err() {
+            return Ok(());
+        }
+        let record = self.get_any_vintage(geoid).ok_or_else(|| {
+            CensusError::Identifier(format!(
+                "geoid {} does not refer to a known county or county equivalent",
+                geoid
+            ))
+        })?;
+        match vintage {
+            Some(v) if !record.is_valid_for(v) => Err(CensusError::Identifier(format!(
+                "county {} is not part of {} geographies, it is only valid for vintages {}",
+                record,
+                v,
+                record.vintage_range()
+            ))),
+            _ => Ok(()),
+        }
+    }
+
+    /// looks up a county by a name qualified with a state FIPS code, abbreviation or name,
+    /// such as "Jefferson County, CO" or "orleans, louisiana". the legal/statistical area
+    /// description ("County", "Parish", "city", ...) may be omitted when it is unambiguous.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use us_census_core::model::fips::CountyRegistry;
+    ///
+    /// let registry = CountyRegistry::embedded();
+    /// let orleans = registry.find_by_name("orleans, louisiana", None).unwrap();
+    /// assert_eq!(orleans.qualified_name(), "Orleans Parish, LA");
+    /// let dona_ana = registry.find_by_name("Dona Ana, NM", None).unwrap();
+    /// assert_eq!(dona_ana.name, "Doña Ana County");
+    /// // both Baltimore County and Baltimore city exist
+    /// assert!(registry.find_by_name("Baltimore, MD", None).is_err());
+    /// assert!(registry.find_by_name("Baltimore city, MD", None).is_ok());
+    /// ```
+    pub fn find_by_name(
+        &self,
+        name: &str,
+        vintage: Option<u64>,
+    ) -> Result<&CountyRecord, CensusError> {
+        let (county_name, state_name) = name.rsplit_once(',').ok_or_else(|| {
+            CensusError::Identifier(format!(
+                "county name '{}' should include a state, such as 'Jefferson County, CO'",
+                name
+            ))
+        })?;
+        let state = state_name.parse::<StateCode>()?;
+        let normalized = normalize_name(county_name);
+        let candidates = self
+            .counties_in_state(state, vintage)
+            .into_iter()
+            .filter(|r| r.matches_name(&normalized))
+            .collect_vec();
+        let exact = candidates
+            .iter()
+            .filter(|r| normalize_name(&r.name) == normalized)
+            .collect_vec();
+        match (&candidates[..], &exact[..]) {
+            (_, [record]) => Ok(record),
+            ([record], _) => Ok(record),
+            ([], _) => Err(CensusError::Identifier(format!(
+                "no county named '{}' found in {}",
+                county_name.trim(),
+                state.to_state_abbreviation()
+            ))),
+            (many, _) => Err(CensusError::Identifier(format!(
+                "county name '{}' is ambiguous, matches [{}]",
+                name.trim(),
+                many.iter().map(|r| r.qualified_name()).join(", ")
+            ))),
+        }
+    }
+
+    /// all counties of a state in GEOID order. when a vintage is provided, only
+    /// counties valid for that vintage are returned.
+    pub fn counties_in_state(&self, state: StateCode, vintage: Option<u64>) -> Vec<&CountyRecord> {
+        self.records
+            .iter()
+            .filter(|r| r.state == state)
+            .filter(|r| vintage.is_none_or(|v| r.is_valid_for(v)))
+            .collect_vec()
+    }
+
+    fn get_any_vintage(&self, geoid: &Geoid) -> Option<&CountyRecord> {
+        match geoid.to_county() {
+            Ok(Geoid::County(state, county)) => self
+                .index
+                .get(&(state.0, county.0))
+                .map(|idx| &self.records[*idx]),
+            _ => None,
+        }
+    }
+}
+
+fn parse_record(line: &str) -> Result<CountyRecord, CensusError> {
+    let (geoid_str, name, first, last) = line.split(',').collect_tuple().ok_or_else(|| {
+        CensusError::Identifier(format!("invalid county registry row '{}'", line))
+    })?;
+    let (state, county) = match geoid_str.parse::<Geoid>()? {
+        Geoid::County(state, county) => Ok((state, county)),
+        other => Err(CensusError::Identifier(format!(
+            "county registry row has non-county geoid {}",
+            other
+        ))),
+    }?;
+    Ok(CountyRecord {
+        state: StateCode::try_from(state)?,
+        county,
+        name: String::from(name),
+        first_vintage: parse_vintage(first)?,
+        last_vintage: parse_vintage(last)?,
+    })
+}
+
+fn parse_vintage(value: &str) -> Result<Option<u64>, CensusError> {
+    if value.is_empty() {
+        return Ok(None);
+    }
+    value
+        .parse::<u64>()
+        .map(Some)
+        .map_err(|e| CensusError::Identifier(format!("invalid vintage '{}': {}", value, e)))
+}
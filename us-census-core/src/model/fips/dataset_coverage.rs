@@ -0,0 +1,19 @@
+use serde::{Deserialize, Serialize};
+
+/// the extent to which a Census product publishes data for a state or territory.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum DatasetCoverage {
+    /// all years and tables are published
+    Full,
+    /// only some years, tables or job types are published
+    Partial,
+    /// no data is published
+    NotCovered,
+}
+
+impl DatasetCoverage {
+    pub fn is_covered(&self) -> bool {
+        !matches!(self, DatasetCoverage::NotCovered)
+    }
+}
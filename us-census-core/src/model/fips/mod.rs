@@ -1 +1,14 @@
+pub mod census_division;
+pub mod census_product;
+pub mod census_region;
+pub mod county_registry;
+pub mod dataset_coverage;
 pub mod state_code;
+pub mod state_scope;
+
+pub use census_division::CensusDivision;
+pub use census_product::CensusProduct;
+pub use census_region::CensusRegion;
+pub use county_registry::{CountyRecord, CountyRegistry};
+pub use dataset_coverage::DatasetCoverage;
+pub use state_scope::StateScope;
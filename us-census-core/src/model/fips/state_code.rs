@@ -1,7 +1,11 @@
+use super::{CensusDivision, CensusProduct, CensusRegion, DatasetCoverage};
+use crate::error::CensusError;
 use crate::model::identifier::{fips::State, has_geoid_string::HasGeoidString, Geoid};
+use clap::{builder::PossibleValue, ValueEnum};
 use serde::{Deserialize, Serialize};
+use std::{fmt::Display, str::FromStr};
 
-#[derive(Serialize, Deserialize, Clone, Copy, Debug)]
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, Hash)]
 #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
 pub enum StateCode {
     Alabama,
@@ -55,10 +59,15 @@ pub enum StateCode {
     WestVirginia,
     Wisconsin,
     Wyoming,
+    AmericanSamoa,
+    Guam,
+    NorthernMarianaIslands,
+    PuertoRico,
+    VirginIslands,
 }
 
 impl TryFrom<State> for StateCode {
-    type Error = String;
+    type Error = CensusError;
 
     fn try_from(value: State) -> Result<Self, Self::Error> {
         match value {
@@ -113,18 +122,26 @@ impl TryFrom<State> for StateCode {
             State(54) => Ok(StateCode::WestVirginia),
             State(55) => Ok(StateCode::Wisconsin),
             State(56) => Ok(StateCode::Wyoming),
-            _ => Err(format!("unknown FIPS state code {}", value.geoid_string())),
+            State(60) => Ok(StateCode::AmericanSamoa),
+            State(66) => Ok(StateCode::Guam),
+            State(69) => Ok(StateCode::NorthernMarianaIslands),
+            State(72) => Ok(StateCode::PuertoRico),
+            State(78) => Ok(StateCode::VirginIslands),
+            _ => Err(CensusError::Identifier(format!(
+                "unknown FIPS state code {}",
+                value.geoid_string()
+            ))),
         }
     }
 }
 
 impl TryFrom<Geoid> for StateCode {
-    type Error = String;
+    type Error = CensusError;
 
     fn try_from(value: Geoid) -> Result<Self, Self::Error> {
-        match value.to_state() {
+        match value.to_state()? {
             Geoid::State(s) => s.try_into(),
-            _ => Err(String::from("internal error")),
+            _ => Err(CensusError::Identifier(String::from("internal error"))),
         }
     }
 }
@@ -183,12 +200,18 @@ impl From<StateCode> for State {
             StateCode::WestVirginia => State(54),
             StateCode::Wisconsin => State(55),
             StateCode::Wyoming => State(56),
+            StateCode::AmericanSamoa => State(60),
+            StateCode::Guam => State(66),
+            StateCode::NorthernMarianaIslands => State(69),
+            StateCode::PuertoRico => State(72),
+            StateCode::VirginIslands => State(78),
         }
     }
 }
 
 impl StateCode {
-    pub const ALL: [StateCode; 51] = [
+    /// all states, the District of Columbia, Puerto Rico and the island areas.
+    pub const ALL: [StateCode; 56] = [
         StateCode::Alabama,
         StateCode::Alaska,
         StateCode::Arizona,
@@ -240,174 +263,463 @@ impl StateCode {
         StateCode::WestVirginia,
         StateCode::Wisconsin,
         StateCode::Wyoming,
+        StateCode::AmericanSamoa,
+        StateCode::Guam,
+        StateCode::NorthernMarianaIslands,
+        StateCode::PuertoRico,
+        StateCode::VirginIslands,
     ];
 
+    /// the 50 states.
+    pub const STATES: [StateCode; 50] = [
+        StateCode::Alabama,
+        StateCode::Alaska,
+        StateCode::Arizona,
+        StateCode::Arkansas,
+        StateCode::California,
+        StateCode::Colorado,
+        StateCode::Connecticut,
+        StateCode::Delaware,
+        StateCode::Florida,
+        StateCode::Georgia,
+        StateCode::Hawaii,
+        StateCode::Idaho,
+        StateCode::Illinois,
+        StateCode::Indiana,
+        StateCode::Iowa,
+        StateCode::Kansas,
+        StateCode::Kentucky,
+        StateCode::Louisiana,
+        StateCode::Maine,
+        StateCode::Maryland,
+        StateCode::Massachusetts,
+        StateCode::Michigan,
+        StateCode::Minnesota,
+        StateCode::Mississippi,
+        StateCode::Missouri,
+        StateCode::Montana,
+        StateCode::Nebraska,
+        StateCode::Nevada,
+        StateCode::NewHampshire,
+        StateCode::NewJersey,
+        StateCode::NewMexico,
+        StateCode::NewYork,
+        StateCode::NorthCarolina,
+        StateCode::NorthDakota,
+        StateCode::Ohio,
+        StateCode::Oklahoma,
+        StateCode::Oregon,
+        StateCode::Pennsylvania,
+        StateCode::RhodeIsland,
+        StateCode::SouthCarolina,
+        StateCode::SouthDakota,
+        StateCode::Tennessee,
+        StateCode::Texas,
+        StateCode::Utah,
+        StateCode::Vermont,
+        StateCode::Virginia,
+        StateCode::Washington,
+        StateCode::WestVirginia,
+        StateCode::Wisconsin,
+        StateCode::Wyoming,
+    ];
+
+    /// Puerto Rico and the island areas.
+    pub const TERRITORIES: [StateCode; 5] = [
+        StateCode::AmericanSamoa,
+        StateCode::Guam,
+        StateCode::NorthernMarianaIslands,
+        StateCode::PuertoRico,
+        StateCode::VirginIslands,
+    ];
+
+    /// true for the 50 states, false for the District of Columbia and territories.
+    pub fn is_state(&self) -> bool {
+        !matches!(self, StateCode::DistrictOfColumbia) && !self.is_territory()
+    }
+
+    /// true for Puerto Rico and the island areas.
+    pub fn is_territory(&self) -> bool {
+        matches!(
+            self,
+            StateCode::AmericanSamoa
+                | StateCode::Guam
+                | StateCode::NorthernMarianaIslands
+                | StateCode::PuertoRico
+                | StateCode::VirginIslands
+        )
+    }
+
+    /// describes whether the given Census product publishes data for this state.
+    ///
+    /// - ACS covers the states, DC and Puerto Rico, but not the island areas
+    /// - LODES covers the states and DC, with limited coverage of Puerto Rico
+    /// - TIGER/Lines covers all states and territories
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use us_census_core::model::fips::{CensusProduct, DatasetCoverage, state_code::StateCode};
+    ///
+    /// assert_eq!(StateCode::PuertoRico.coverage(&CensusProduct::Acs), DatasetCoverage::Full);
+    /// assert_eq!(StateCode::PuertoRico.coverage(&CensusProduct::Lodes), DatasetCoverage::Partial);
+    /// assert_eq!(StateCode::Guam.coverage(&CensusProduct::Acs), DatasetCoverage::NotCovered);
+    /// ```
+    pub fn coverage(&self, product: &CensusProduct) -> DatasetCoverage {
+        match (product, self) {
+            (CensusProduct::Tiger, _) => DatasetCoverage::Full,
+            (CensusProduct::Acs, StateCode::PuertoRico) => DatasetCoverage::Full,
+            (CensusProduct::Lodes, StateCode::PuertoRico) => DatasetCoverage::Partial,
+            (_, sc) if sc.is_territory() => DatasetCoverage::NotCovered,
+            _ => DatasetCoverage::Full,
+        }
+    }
+
+    /// the Census division containing this state, or None for Puerto Rico and
+    /// the island areas.
+    pub fn division(&self) -> Option<CensusDivision> {
+        use CensusDivision as D;
+        match self {
+            StateCode::Connecticut
+            | StateCode::Maine
+            | StateCode::Massachusetts
+            | StateCode::NewHampshire
+            | StateCode::RhodeIsland
+            | StateCode::Vermont => Some(D::NewEngland),
+            StateCode::NewJersey | StateCode::NewYork | StateCode::Pennsylvania => {
+                Some(D::MiddleAtlantic)
+            }
+            StateCode::Illinois
+            | StateCode::Indiana
+            | StateCode::Michigan
+            | StateCode::Ohio
+            | StateCode::Wisconsin => Some(D::EastNorthCentral),
+            StateCode::Iowa
+            | StateCode::Kansas
+            | StateCode::Minnesota
+            | StateCode::Missouri
+            | StateCode::Nebraska
+            | StateCode::NorthDakota
+            | StateCode::SouthDakota => Some(D::WestNorthCentral),
+            StateCode::Delaware
+            | StateCode::DistrictOfColumbia
+            | StateCode::Florida
+            | StateCode::Georgia
+            | StateCode::Maryland
+            | StateCode::NorthCarolina
+            | StateCode::SouthCarolina
+            | StateCode::Virginia
+            | StateCode::WestVirginia => Some(D::SouthAtlantic),
+            StateCode::Alabama
+            | StateCode::Kentucky
+            | StateCode::Mississippi
+            | StateCode::Tennessee => Some(D::EastSouthCentral),
+            StateCode::Arkansas | StateCode::Louisiana | StateCode::Oklahoma | StateCode::Texas => {
+                Some(D::WestSouthCentral)
+            }
+            StateCode::Arizona
+            | StateCode::Colorado
+            | StateCode::Idaho
+            | StateCode::Montana
+            | StateCode::Nevada
+            | StateCode::NewMexico
+            | StateCode::Utah
+            | StateCode::Wyoming => Some(D::Mountain),
+            StateCode::Alaska
+            | StateCode::California
+            | StateCode::Hawaii
+            | StateCode::Oregon
+            | StateCode::Washington => Some(D::Pacific),
+            _ => None,
+        }
+    }
+
+    /// the Census region containing this state, or None for Puerto Rico and
+    /// the island areas.
+    pub fn region(&self) -> Option<CensusRegion> {
+        self.division().map(|d| d.region())
+    }
+
     /// a two-letter state abbreviation
     pub fn to_state_abbreviation(&self) -> String {
+        String::from(self.abbreviation_str())
+    }
+
+    pub fn to_fips_string(&self) -> String {
+        String::from(self.fips_str())
+    }
+
+    pub fn to_full_name(&self) -> String {
+        String::from(self.full_name_str())
+    }
+
+    fn abbreviation_str(&self) -> &'static str {
         match self {
-            StateCode::Alabama => String::from("AL"),
-            StateCode::Alaska => String::from("AK"),
-            StateCode::Arizona => String::from("AZ"),
-            StateCode::Arkansas => String::from("AR"),
-            StateCode::California => String::from("CA"),
-            StateCode::Colorado => String::from("CO"),
-            StateCode::Connecticut => String::from("CT"),
-            StateCode::Delaware => String::from("DE"),
-            StateCode::DistrictOfColumbia => String::from("DC"),
-            StateCode::Florida => String::from("FL"),
-            StateCode::Georgia => String::from("GA"),
-            StateCode::Hawaii => String::from("HI"),
-            StateCode::Idaho => String::from("ID"),
-            StateCode::Illinois => String::from("IL"),
-            StateCode::Indiana => String::from("IN"),
-            StateCode::Iowa => String::from("IA"),
-            StateCode::Kansas => String::from("KS"),
-            StateCode::Kentucky => String::from("Ky"),
-            StateCode::Louisiana => String::from("LA"),
-            StateCode::Maine => String::from("ME"),
-            StateCode::Maryland => String::from("MD"),
-            StateCode::Massachusetts => String::from("MA"),
-            StateCode::Michigan => String::from("MI"),
-            StateCode::Minnesota => String::from("MN"),
-            StateCode::Mississippi => String::from("MS"),
-            StateCode::Missouri => String::from("MO"),
-            StateCode::Montana => String::from("MT"),
-            StateCode::Nebraska => String::from("NE"),
-            StateCode::Nevada => String::from("NV"),
-            StateCode::NewHampshire => String::from("NH"),
-            StateCode::NewJersey => String::from("NJ"),
-            StateCode::NewMexico => String::from("NM"),
-            StateCode::NewYork => String::from("NY"),
-            StateCode::NorthCarolina => String::from("NC"),
-            StateCode::NorthDakota => String::from("ND"),
-            StateCode::Ohio => String::from("OH"),
-            StateCode::Oklahoma => String::from("OK"),
-            StateCode::Oregon => String::from("OR"),
-            StateCode::Pennsylvania => String::from("PA"),
-            StateCode::RhodeIsland => String::from("RI"),
-            StateCode::SouthCarolina => String::from("SC"),
-            StateCode::SouthDakota => String::from("SD"),
-            StateCode::Tennessee => String::from("TN"),
-            StateCode::Texas => String::from("TX"),
-            StateCode::Utah => String::from("UT"),
-            StateCode::Vermont => String::from("VT"),
-            StateCode::Virginia => String::from("VA"),
-            StateCode::Washington => String::from("WA"),
-            StateCode::WestVirginia => String::from("WV"),
-            StateCode::Wisconsin => String::from("WI"),
-            StateCode::Wyoming => String::from("WY"),
+            StateCode::Alabama => "AL",
+            StateCode::Alaska => "AK",
+            StateCode::Arizona => "AZ",
+            StateCode::Arkansas => "AR",
+            StateCode::California => "CA",
+            StateCode::Colorado => "CO",
+            StateCode::Connecticut => "CT",
+            StateCode::Delaware => "DE",
+            StateCode::DistrictOfColumbia => "DC",
+            StateCode::Florida => "FL",
+            StateCode::Georgia => "GA",
+            StateCode::Hawaii => "HI",
+            StateCode::Idaho => "ID",
+            StateCode::Illinois => "IL",
+            StateCode::Indiana => "IN",
+            StateCode::Iowa => "IA",
+            StateCode::Kansas => "KS",
+            StateCode::Kentucky => "KY",
+            StateCode::Louisiana => "LA",
+            StateCode::Maine => "ME",
+            StateCode::Maryland => "MD",
+            StateCode::Massachusetts => "MA",
+            StateCode::Michigan => "MI",
+            StateCode::Minnesota => "MN",
+            StateCode::Mississippi => "MS",
+            StateCode::Missouri => "MO",
+            StateCode::Montana => "MT",
+            StateCode::Nebraska => "NE",
+            StateCode::Nevada => "NV",
+            StateCode::NewHampshire => "NH",
+            StateCode::NewJersey => "NJ",
+            StateCode::NewMexico => "NM",
+            StateCode::NewYork => "NY",
+            StateCode::NorthCarolina => "NC",
+            StateCode::NorthDakota => "ND",
+            StateCode::Ohio => "OH",
+            StateCode::Oklahoma => "OK",
+            StateCode::Oregon => "OR",
+            StateCode::Pennsylvania => "PA",
+            StateCode::RhodeIsland => "RI",
+            StateCode::SouthCarolina => "SC",
+            StateCode::SouthDakota => "SD",
+            StateCode::Tennessee => "TN",
+            StateCode::Texas => "TX",
+            StateCode::Utah => "UT",
+            StateCode::Vermont => "VT",
+            StateCode::Virginia => "VA",
+            StateCode::Washington => "WA",
+            StateCode::WestVirginia => "WV",
+            StateCode::Wisconsin => "WI",
+            StateCode::Wyoming => "WY",
+            StateCode::AmericanSamoa => "AS",
+            StateCode::Guam => "GU",
+            StateCode::NorthernMarianaIslands => "MP",
+            StateCode::PuertoRico => "PR",
+            StateCode::VirginIslands => "VI",
         }
     }
 
-    pub fn to_fips_string(&self) -> String {
+    fn fips_str(&self) -> &'static str {
         match self {
-            StateCode::Alabama => String::from("01"),
-            StateCode::Alaska => String::from("02"),
-            StateCode::Arizona => String::from("04"),
-            StateCode::Arkansas => String::from("05"),
-            StateCode::California => String::from("06"),
-            StateCode::Colorado => String::from("08"),
-            StateCode::Connecticut => String::from("09"),
-            StateCode::Delaware => String::from("10"),
-            StateCode::DistrictOfColumbia => String::from("11"),
-            StateCode::Florida => String::from("12"),
-            StateCode::Georgia => String::from("13"),
-            StateCode::Hawaii => String::from("15"),
-            StateCode::Idaho => String::from("16"),
-            StateCode::Illinois => String::from("17"),
-            StateCode::Indiana => String::from("18"),
-            StateCode::Iowa => String::from("19"),
-            StateCode::Kansas => String::from("20"),
-            StateCode::Kentucky => String::from("21"),
-            StateCode::Louisiana => String::from("22"),
-            StateCode::Maine => String::from("23"),
-            StateCode::Maryland => String::from("24"),
-            StateCode::Massachusetts => String::from("25"),
-            StateCode::Michigan => String::from("26"),
-            StateCode::Minnesota => String::from("27"),
-            StateCode::Mississippi => String::from("28"),
-            StateCode::Missouri => String::from("29"),
-            StateCode::Montana => String::from("30"),
-            StateCode::Nebraska => String::from("31"),
-            StateCode::Nevada => String::from("32"),
-            StateCode::NewHampshire => String::from("33"),
-            StateCode::NewJersey => String::from("34"),
-            StateCode::NewMexico => String::from("35"),
-            StateCode::NewYork => String::from("36"),
-            StateCode::NorthCarolina => String::from("37"),
-            StateCode::NorthDakota => String::from("38"),
-            StateCode::Ohio => String::from("39"),
-            StateCode::Oklahoma => String::from("40"),
-            StateCode::Oregon => String::from("41"),
-            StateCode::Pennsylvania => String::from("42"),
-            StateCode::RhodeIsland => String::from("44"),
-            StateCode::SouthCarolina => String::from("45"),
-            StateCode::SouthDakota => String::from("46"),
-            StateCode::Tennessee => String::from("47"),
-            StateCode::Texas => String::from("48"),
-            StateCode::Utah => String::from("49"),
-            StateCode::Vermont => String::from("50"),
-            StateCode::Virginia => String::from("51"),
-            StateCode::Washington => String::from("53"),
-            StateCode::WestVirginia => String::from("54"),
-            StateCode::Wisconsin => String::from("55"),
-            StateCode::Wyoming => String::from("56"),
+            StateCode::Alabama => "01",
+            StateCode::Alaska => "02",
+            StateCode::Arizona => "04",
+            StateCode::Arkansas => "05",
+            StateCode::California => "06",
+            StateCode::Colorado => "08",
+            StateCode::Connecticut => "09",
+            StateCode::Delaware => "10",
+            StateCode::DistrictOfColumbia => "11",
+            StateCode::Florida => "12",
+            StateCode::Georgia => "13",
+            StateCode::Hawaii => "15",
+            StateCode::Idaho => "16",
+            StateCode::Illinois => "17",
+            StateCode::Indiana => "18",
+            StateCode::Iowa => "19",
+            StateCode::Kansas => "20",
+            StateCode::Kentucky => "21",
+            StateCode::Louisiana => "22",
+            StateCode::Maine => "23",
+            StateCode::Maryland => "24",
+            StateCode::Massachusetts => "25",
+            StateCode::Michigan => "26",
+            StateCode::Minnesota => "27",
+            StateCode::Mississippi => "28",
+            StateCode::Missouri => "29",
+            StateCode::Montana => "30",
+            StateCode::Nebraska => "31",
+            StateCode::Nevada => "32",
+            StateCode::NewHampshire => "33",
+            StateCode::NewJersey => "34",
+            StateCode::NewMexico => "35",
+            StateCode::NewYork => "36",
+            StateCode::NorthCarolina => "37",
+            StateCode::NorthDakota => "38",
+            StateCode::Ohio => "39",
+            StateCode::Oklahoma => "40",
+            StateCode::Oregon => "41",
+            StateCode::Pennsylvania => "42",
+            StateCode::RhodeIsland => "44",
+            StateCode::SouthCarolina => "45",
+            StateCode::SouthDakota => "46",
+            StateCode::Tennessee => "47",
+            StateCode::Texas => "48",
+            StateCode::Utah => "49",
+            StateCode::Vermont => "50",
+            StateCode::Virginia => "51",
+            StateCode::Washington => "53",
+            StateCode::WestVirginia => "54",
+            StateCode::Wisconsin => "55",
+            StateCode::Wyoming => "56",
+            StateCode::AmericanSamoa => "60",
+            StateCode::Guam => "66",
+            StateCode::NorthernMarianaIslands => "69",
+            StateCode::PuertoRico => "72",
+            StateCode::VirginIslands => "78",
         }
     }
 
-    pub fn to_full_name(&self) -> String {
+    fn full_name_str(&self) -> &'static str {
         match self {
-            StateCode::Alabama => String::from("Alabama"),
-            StateCode::Alaska => String::from("Alaska"),
-            StateCode::Arizona => String::from("Arizona"),
-            StateCode::Arkansas => String::from("Arkansas"),
-            StateCode::California => String::from("California"),
-            StateCode::Colorado => String::from("Colorado"),
-            StateCode::Connecticut => String::from("Connecticut"),
-            StateCode::Delaware => String::from("Delaware"),
-            StateCode::DistrictOfColumbia => String::from("DistrictOfColumbia"),
-            StateCode::Florida => String::from("Florida"),
-            StateCode::Georgia => String::from("Georgia"),
-            StateCode::Hawaii => String::from("Hawaii"),
-            StateCode::Idaho => String::from("Idaho"),
-            StateCode::Illinois => String::from("Illinois"),
-            StateCode::Indiana => String::from("Indiana"),
-            StateCode::Iowa => String::from("Iowa"),
-            StateCode::Kansas => String::from("Kansas"),
-            StateCode::Kentucky => String::from("Kentucky"),
-            StateCode::Louisiana => String::from("Louisiana"),
-            StateCode::Maine => String::from("Maine"),
-            StateCode::Maryland => String::from("Maryland"),
-            StateCode::Massachusetts => String::from("Massachusetts"),
-            StateCode::Michigan => String::from("Michigan"),
-            StateCode::Minnesota => String::from("Minnesota"),
-            StateCode::Mississippi => String::from("Mississippi"),
-            StateCode::Missouri => String::from("Missouri"),
-            StateCode::Montana => String::from("Montana"),
-            StateCode::Nebraska => String::from("Nebraska"),
-            StateCode::Nevada => String::from("Nevada"),
-            StateCode::NewHampshire => String::from("NewHampshire"),
-            StateCode::NewJersey => String::from("NewJersey"),
-            StateCode::NewMexico => String::from("NewMexico"),
-            StateCode::NewYork => String::from("NewYork"),
-            StateCode::NorthCarolina => String::from("NorthCarolina"),
-            StateCode::NorthDakota => String::from("NorthDakota"),
-            StateCode::Ohio => String::from("Ohio"),
-            StateCode::Oklahoma => String::from("Oklahoma"),
-            StateCode::Oregon => String::from("Oregon"),
-            StateCode::Pennsylvania => String::from("Pennsylvania"),
-            StateCode::RhodeIsland => String::from("RhodeIsland"),
-            StateCode::SouthCarolina => String::from("SouthCarolina"),
-            StateCode::SouthDakota => String::from("SouthDakota"),
-            StateCode::Tennessee => String::from("Tennessee"),
-            StateCode::Texas => String::from("Texas"),
-            StateCode::Utah => String::from("Utah"),
-            StateCode::Vermont => String::from("Vermont"),
-            StateCode::Virginia => String::from("Virginia"),
-            StateCode::Washington => String::from("Washington"),
-            StateCode::WestVirginia => String::from("WestVirginia"),
-            StateCode::Wisconsin => String::from("Wisconsin"),
-            StateCode::Wyoming => String::from("Wyoming"),
+            StateCode::Alabama => "Alabama",
+            StateCode::Alaska => "Alaska",
+            StateCode::Arizona => "Arizona",
+            StateCode::Arkansas => "Arkansas",
+            StateCode::California => "California",
+            StateCode::Colorado => "Colorado",
+            StateCode::Connecticut => "Connecticut",
+            StateCode::Delaware => "Delaware",
+            StateCode::DistrictOfColumbia => "DistrictOfColumbia",
+            StateCode::Florida => "Florida",
+            StateCode::Georgia => "Georgia",
+            StateCode::Hawaii => "Hawaii",
+            StateCode::Idaho => "Idaho",
+            StateCode::Illinois => "Illinois",
+            StateCode::Indiana => "Indiana",
+            StateCode::Iowa => "Iowa",
+            StateCode::Kansas => "Kansas",
+            StateCode::Kentucky => "Kentucky",
+            StateCode::Louisiana => "Louisiana",
+            StateCode::Maine => "Maine",
+            StateCode::Maryland => "Maryland",
+            StateCode::Massachusetts => "Massachusetts",
+            StateCode::Michigan => "Michigan",
+            StateCode::Minnesota => "Minnesota",
+            StateCode::Mississippi => "Mississippi",
+            StateCode::Missouri => "Missouri",
+            StateCode::Montana => "Montana",
+            StateCode::Nebraska => "Nebraska",
+            StateCode::Nevada => "Nevada",
+            StateCode::NewHampshire => "NewHampshire",
+            StateCode::NewJersey => "NewJersey",
+            StateCode::NewMexico => "NewMexico",
+            StateCode::NewYork => "NewYork",
+            StateCode::NorthCarolina => "NorthCarolina",
+            StateCode::NorthDakota => "NorthDakota",
+            StateCode::Ohio => "Ohio",
+            StateCode::Oklahoma => "Oklahoma",
+            StateCode::Oregon => "Oregon",
+            StateCode::Pennsylvania => "Pennsylvania",
+            StateCode::RhodeIsland => "RhodeIsland",
+            StateCode::SouthCarolina => "SouthCarolina",
+            StateCode::SouthDakota => "SouthDakota",
+            StateCode::Tennessee => "Tennessee",
+            StateCode::Texas => "Texas",
+            StateCode::Utah => "Utah",
+            StateCode::Vermont => "Vermont",
+            StateCode::Virginia => "Virginia",
+            StateCode::Washington => "Washington",
+            StateCode::WestVirginia => "WestVirginia",
+            StateCode::Wisconsin => "Wisconsin",
+            StateCode::Wyoming => "Wyoming",
+            StateCode::AmericanSamoa => "AmericanSamoa",
+            StateCode::Guam => "Guam",
+            StateCode::NorthernMarianaIslands => "NorthernMarianaIslands",
+            StateCode::PuertoRico => "PuertoRico",
+            StateCode::VirginIslands => "VirginIslands",
         }
     }
 }
+
+impl Display for StateCode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.abbreviation_str())
+    }
+}
+
+/// parses a state from its FIPS code ("08" or "8"), its USPS abbreviation ("CO")
+/// or its name ("Colorado", "New York", "district of columbia"), ignoring case,
+/// whitespace and punctuation.
+///
+/// # Examples
+///
+/// ```rust
+/// use us_census_core::model::fips::state_code::StateCode;
+///
+/// assert_eq!("08".parse::<StateCode>().unwrap(), StateCode::Colorado);
+/// assert_eq!("8".parse::<StateCode>().unwrap(), StateCode::Colorado);
+/// assert_eq!("co".parse::<StateCode>().unwrap(), StateCode::Colorado);
+/// assert_eq!("New York".parse::<StateCode>().unwrap(), StateCode::NewYork);
+/// assert_eq!("DISTRICT_OF_COLUMBIA".parse::<StateCode>().unwrap(), StateCode::DistrictOfColumbia);
+/// assert!("Atlantis".parse::<StateCode>().is_err());
+/// ```
+impl FromStr for StateCode {
+    type Err = CensusError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let value = s.trim();
+        if !value.is_empty() && value.chars().all(|c| c.is_ascii_digit()) {
+            let fips = value.parse::<u64>().map_err(|e| {
+                CensusError::Identifier(format!("invalid state FIPS code '{}': {}", value, e))
+            })?;
+            return StateCode::try_from(State(fips));
+        }
+        let normalized = normalize_name(value);
+        StateCode::ALL
+            .into_iter()
+            .find(|sc| {
+                sc.abbreviation_str().eq_ignore_ascii_case(&normalized)
+                    || normalize_name(sc.full_name_str()) == normalized
+            })
+            .ok_or_else(|| {
+                CensusError::Identifier(format!(
+                    "'{}' is not a state FIPS code, abbreviation or name",
+                    value
+                ))
+            })
+    }
+}
+
+/// state arguments on the command line are listed by abbreviation and also accept
+/// FIPS codes and names (without spaces) as aliases.
+impl ValueEnum for StateCode {
+    fn value_variants<'a>() -> &'a [Self] {
+        &StateCode::ALL
+    }
+
+    fn to_possible_value(&self) -> Option<PossibleValue> {
+        Some(
+            PossibleValue::new(self.abbreviation_str())
+                .aliases([self.fips_str(), self.full_name_str()])
+                .help(self.full_name_str()),
+        )
+    }
+
+    fn from_str(input: &str, _ignore_case: bool) -> Result<Self, String> {
+        input.parse::<StateCode>().map_err(|e| e.to_string())
+    }
+}
+
+/// lowercases a place name and drops everything but letters and digits, folding the
+/// accented characters found in Census place names to ASCII.
+pub(crate) fn normalize_name(name: &str) -> String {
+    name.chars()
+        .map(|c| match c {
+            'á' | 'Á' => 'a',
+            'é' | 'É' => 'e',
+            'í' | 'Í' => 'i',
+            'ó' | 'Ó' => 'o',
+            'ú' | 'Ú' | 'ü' | 'Ü' => 'u',
+            'ñ' | 'Ñ' => 'n',
+            _ => c,
+        })
+        .filter(|c| c.is_ascii_alphanumeric())
+        .map(|c| c.to_ascii_lowercase())
+        .collect()
+}
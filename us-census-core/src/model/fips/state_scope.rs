@@ -0,0 +1,57 @@
+use super::state_code::StateCode;
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
+use std::fmt::Display;
+
+/// selects a set of states and territories, such as when a query has no
+/// geographic restriction and should run over "all" states.
+#[derive(Serialize, Deserialize, ValueEnum, Default, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum StateScope {
+    /// the 50 states
+    States,
+    /// the 50 states and the District of Columbia
+    #[default]
+    StatesAndDc,
+    /// the 50 states, the District of Columbia and Puerto Rico
+    StatesDcAndPuertoRico,
+    /// all states, the District of Columbia, Puerto Rico and the island areas
+    All,
+}
+
+impl StateScope {
+    /// # Examples
+    ///
+    /// ```rust
+    /// use us_census_core::model::fips::StateScope;
+    ///
+    /// assert_eq!(StateScope::States.state_codes().len(), 50);
+    /// assert_eq!(StateScope::StatesAndDc.state_codes().len(), 51);
+    /// assert_eq!(StateScope::StatesDcAndPuertoRico.state_codes().len(), 52);
+    /// assert_eq!(StateScope::All.state_codes().len(), 56);
+    /// ```
+    pub fn state_codes(&self) -> Vec<StateCode> {
+        StateCode::ALL
+            .into_iter()
+            .filter(|sc| match self {
+                StateScope::States => sc.is_state(),
+                StateScope::StatesAndDc => !sc.is_territory(),
+                StateScope::StatesDcAndPuertoRico => {
+                    !sc.is_territory() || *sc == StateCode::PuertoRico
+                }
+                StateScope::All => true,
+            })
+            .collect()
+    }
+}
+
+impl Display for StateScope {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            StateScope::States => write!(f, "states"),
+            StateScope::StatesAndDc => write!(f, "states_and_dc"),
+            StateScope::StatesDcAndPuertoRico => write!(f, "states_dc_and_puerto_rico"),
+            StateScope::All => write!(f, "all"),
+        }
+    }
+}
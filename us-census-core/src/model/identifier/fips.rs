@@ -30,11 +30,13 @@ pub struct Block(pub String);
 #[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct CongressionalDistrict(pub u64);
 
-#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
-pub struct StateLegislativeDistrictUpperChamber(pub u64);
+/// 3-character district code, which is alphanumeric in some states.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct StateLegislativeDistrictUpperChamber(pub String);
 
-#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
-pub struct StateLegislativeDistrictLowerChamber(pub u64);
+/// 3-character district code, which is alphanumeric in some states.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct StateLegislativeDistrictLowerChamber(pub String);
 
 #[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct ZipCodeTabulationArea(pub u64);
@@ -75,6 +77,26 @@ impl HasGeoidType for Block {
         GeoidType::Block
     }
 }
+impl HasGeoidType for CongressionalDistrict {
+    fn geoid_type(&self) -> GeoidType {
+        GeoidType::CongressionalDistrict
+    }
+}
+impl HasGeoidType for StateLegislativeDistrictUpperChamber {
+    fn geoid_type(&self) -> GeoidType {
+        GeoidType::StateLegislativeDistrictUpperChamber
+    }
+}
+impl HasGeoidType for StateLegislativeDistrictLowerChamber {
+    fn geoid_type(&self) -> GeoidType {
+        GeoidType::StateLegislativeDistrictLowerChamber
+    }
+}
+impl HasGeoidType for ZipCodeTabulationArea {
+    fn geoid_type(&self) -> GeoidType {
+        GeoidType::ZipCodeTabulationArea
+    }
+}
 
 impl HasGeoidString for State {
     fn geoid_string(&self) -> String {
@@ -119,12 +141,12 @@ impl HasGeoidString for CongressionalDistrict {
 }
 impl HasGeoidString for StateLegislativeDistrictUpperChamber {
     fn geoid_string(&self) -> String {
-        format!("{:03}", self.0)
+        self.0.clone()
     }
 }
 impl HasGeoidString for StateLegislativeDistrictLowerChamber {
     fn geoid_string(&self) -> String {
-        format!("{:03}", self.0)
+        self.0.clone()
     }
 }
 impl HasGeoidString for ZipCodeTabulationArea {
@@ -0,0 +1,107 @@
+use std::fmt::Display;
+
+use clap::ValueEnum;
+use itertools::Itertools;
+use serde::{Deserialize, Serialize};
+
+use crate::error::CensusError;
+
+/// the decennial census geography that GEOIDs are defined against. census blocks,
+/// block groups and tracts are redrawn every ten years, so the same GEOID may refer
+/// to different areas (or not exist) in 2010 and 2020 geographies.
+///
+/// # Examples
+///
+/// ```rust
+/// use us_census_core::model::identifier::GeographyVintage;
+///
+/// assert_eq!(GeographyVintage::from_year(2015), GeographyVintage::Census2010);
+/// assert_eq!(GeographyVintage::from_year(2021), GeographyVintage::Census2020);
+/// assert!(GeographyVintage::combine(
+///     Some(GeographyVintage::Census2010),
+///     Some(GeographyVintage::Census2020)
+/// ).is_err());
+/// assert_eq!(
+///     GeographyVintage::combine(None, Some(GeographyVintage::Census2020)).unwrap(),
+///     Some(GeographyVintage::Census2020)
+/// );
+/// ```
+#[derive(
+    Serialize, Deserialize, ValueEnum, Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum GeographyVintage {
+    Census2000,
+    Census2010,
+    Census2020,
+}
+
+impl GeographyVintage {
+    /// the decennial geography used by data products published for the given year,
+    /// such as a TIGER/Lines year or an ACS year.
+    pub fn from_year(year: u64) -> GeographyVintage {
+        match year {
+            y if y < 2010 => GeographyVintage::Census2000,
+            y if y < 2020 => GeographyVintage::Census2010,
+            _ => GeographyVintage::Census2020,
+        }
+    }
+
+    /// the decennial census year of this geography.
+    pub fn year(&self) -> u64 {
+        match self {
+            GeographyVintage::Census2000 => 2000,
+            GeographyVintage::Census2010 => 2010,
+            GeographyVintage::Census2020 => 2020,
+        }
+    }
+
+    /// combines two optional vintages, where an unknown (None) vintage is compatible with
+    /// any other. fails if both vintages are known and differ.
+    pub fn combine(
+        a: Option<GeographyVintage>,
+        b: Option<GeographyVintage>,
+    ) -> Result<Option<GeographyVintage>, CensusError> {
+        match (a, b) {
+            (Some(x), Some(y)) if x != y => Err(CensusError::VintageMismatch(format!(
+                "cannot combine GEOIDs from {} and {} geographies",
+                x, y
+            ))),
+            (Some(x), _) => Ok(Some(x)),
+            (None, y) => Ok(y),
+        }
+    }
+}
+
+impl Display for GeographyVintage {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} census", self.year())
+    }
+}
+
+/// implemented by values that record the geography vintage of the GEOID they belong to.
+pub trait HasGeographyVintage {
+    fn geography_vintage(&self) -> Option<GeographyVintage>;
+}
+
+/// finds the single geography vintage of a dataset, or None if no values are tagged.
+/// fails if the dataset mixes values from different geography vintages. rows are keyed
+/// by GEOID or by any other id, such as a zone id.
+pub fn dataset_vintage<K, T: HasGeographyVintage>(
+    rows: &[(K, Vec<T>)],
+) -> Result<Option<GeographyVintage>, CensusError> {
+    let vintages = rows
+        .iter()
+        .flat_map(|(_, values)| values.iter().filter_map(|v| v.geography_vintage()))
+        .unique()
+        .sorted()
+        .collect_vec();
+    match &vintages[..] {
+        [] => Ok(None),
+        [one] => Ok(Some(*one)),
+        many => Err(CensusError::VintageMismatch(format!(
+            "dataset mixes values from multiple geography vintages: [{}]",
+            many.iter().join(", ")
+        ))),
+    }
+}
@@ -1,10 +1,15 @@
+use clap::ValueEnum;
 use itertools::Itertools;
 use serde::{Deserialize, Serialize};
 
-use crate::model::fips::state_code::StateCode;
+use crate::error::CensusError;
+use crate::model::fips::{state_code::StateCode, CensusDivision, CensusRegion, StateScope};
 
-use super::{fips, geoid_type::GeoidType, has_geoid_string::HasGeoidString};
+use super::{
+    fips, geoid_type::GeoidType, has_geoid_string::HasGeoidString, summary_level::SummaryLevel,
+};
 use std::fmt::Display;
+use std::str::FromStr;
 
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq, Hash)]
 pub enum Geoid {
@@ -20,35 +25,219 @@ pub enum Geoid {
         fips::BlockGroup,
     ),
     Block(fips::State, fips::County, fips::CensusTract, fips::Block),
+    CongressionalDistrict(fips::State, fips::CongressionalDistrict),
+    StateLegislativeDistrictUpperChamber(fips::State, fips::StateLegislativeDistrictUpperChamber),
+    StateLegislativeDistrictLowerChamber(fips::State, fips::StateLegislativeDistrictLowerChamber),
+    ZipCodeTabulationArea(fips::ZipCodeTabulationArea),
 }
 
+/// infers the GeoidType of a GEOID string from its length.
+///
+/// GEOID lengths are not unique across types: 5-digit values are always read
+/// as counties, even though state legislative district and ZCTA GEOIDs are also
+/// 5 digits long. use [`GeoidType::geoid_from_str`] to parse those types.
+///
+/// # Examples
+///
+/// ```rust
+/// use us_census_core::model::identifier::{fips, Geoid};
+///
+/// let cd = Geoid::try_from("0807").unwrap();
+/// assert_eq!(cd, Geoid::CongressionalDistrict(fips::State(8), fips::CongressionalDistrict(7)));
+/// let county = Geoid::try_from("08059").unwrap();
+/// assert_eq!(county, Geoid::County(fips::State(8), fips::County(59)));
+/// ```
 impl TryFrom<&str> for Geoid {
-    type Error = String;
+    type Error = CensusError;
 
     fn try_from(value: &str) -> Result<Self, Self::Error> {
         match value.len() {
             2 => GeoidType::State.geoid_from_str(value),
+            4 => GeoidType::CongressionalDistrict.geoid_from_str(value),
             5 => GeoidType::County.geoid_from_str(value),
             7 => GeoidType::Place.geoid_from_str(value),
             10 => GeoidType::CountySubdivision.geoid_from_str(value),
             11 => GeoidType::CensusTract.geoid_from_str(value),
             12 => GeoidType::BlockGroup.geoid_from_str(value),
             x if x == 15 || x == 16 => GeoidType::Block.geoid_from_str(value),
-            x => Err(format!(
+            x => Err(CensusError::Identifier(format!(
                 "unsupported GEOID type with length {}: {}",
                 x, value
-            )),
+            ))),
         }
     }
 }
 
-// todo:
-// - Geoid methods to unpack/pack between types (Geoid::County.to_state())
+/// parses a Geoid from any of its text formats:
+///   - the `type=digits` form written by `Display`, e.g. `county=08059`
+///   - the Census `GEO_ID` / `ucgid` form, a summary level prefix followed by the
+///     GEOID digits, e.g. `1400000US08059009838`
+///   - bare GEOID digits, where the type is inferred from the length as in
+///     `Geoid::try_from`. use [`Geoid::parse_with_type`] when the type is known.
+///
+/// # Examples
+///
+/// ```rust
+/// use us_census_core::model::identifier::{fips, Geoid};
+///
+/// let tract = Geoid::CensusTract(fips::State(8), fips::County(59), fips::CensusTract(9838));
+/// assert_eq!("census_tract=08059009838".parse::<Geoid>().unwrap(), tract);
+/// assert_eq!("1400000US08059009838".parse::<Geoid>().unwrap(), tract);
+/// assert_eq!("08059009838".parse::<Geoid>().unwrap(), tract);
+/// assert_eq!(tract.to_string().parse::<Geoid>().unwrap(), tract);
+/// assert_eq!(tract.to_ucgid().parse::<Geoid>().unwrap(), tract);
+/// assert!("00é000US123".parse::<Geoid>().is_err());
+/// assert!("1400000US0805900983é".parse::<Geoid>().is_err());
+/// ```
+impl FromStr for Geoid {
+    type Err = CensusError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let value = s.trim();
+        if let Some((type_name, digits)) = value.split_once('=') {
+            let geoid_type = GeoidType::value_variants()
+                .iter()
+                .find(|t| t.to_string() == type_name.trim())
+                .ok_or_else(|| {
+                    CensusError::Identifier(format!(
+                        "unknown geoid type '{}' in geoid '{}'",
+                        type_name, value
+                    ))
+                })?;
+            geoid_type.geoid_from_str(digits.trim())
+        } else if let Some((prefix, digits)) = value.split_once("US") {
+            let geoid_type = ucgid_geoid_type(prefix).ok_or_else(|| {
+                CensusError::Identifier(format!(
+                    "unsupported summary level prefix '{}' in GEO_ID '{}'",
+                    prefix, value
+                ))
+            })?;
+            geoid_type.geoid_from_str(digits)
+        } else {
+            Geoid::try_from(value)
+        }
+    }
+}
 
 impl Geoid {
-    /// generates all state level Geoids for the U.S.
-    pub fn all_states() -> Vec<Geoid> {
-        StateCode::ALL
+    /// parses a Geoid from any of the formats supported by `FromStr`, where bare GEOID
+    /// digits are read as the given GeoidType instead of inferring the type from length.
+    /// typed inputs must match the given GeoidType.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use us_census_core::model::identifier::{fips, Geoid, GeoidType};
+    ///
+    /// let zcta = Geoid::parse_with_type("80401", &GeoidType::ZipCodeTabulationArea).unwrap();
+    /// assert_eq!(zcta, Geoid::ZipCodeTabulationArea(fips::ZipCodeTabulationArea(80401)));
+    /// assert!(Geoid::parse_with_type("county=08059", &GeoidType::ZipCodeTabulationArea).is_err());
+    /// ```
+    pub fn parse_with_type(value: &str, geoid_type: &GeoidType) -> Result<Geoid, CensusError> {
+        let value = value.trim();
+        if !value.contains('=') && !value.contains("US") {
+            return geoid_type.geoid_from_str(value);
+        }
+        let geoid = Geoid::from_str(value)?;
+        if geoid.geoid_type() != *geoid_type {
+            return Err(CensusError::Identifier(format!(
+                "expected {} geoid, found {}",
+                geoid_type, geoid
+            )));
+        }
+        Ok(geoid)
+    }
+
+    /// parses a comma-delimited list of geographies into GEOIDs. each entry may be any
+    /// GEOID format supported by `FromStr`, a state FIPS code, abbreviation or name, or
+    /// the name of a Census division or region (such as `mountain` or `northeast`),
+    /// which expands to the GEOIDs of its states. empty entries are skipped and
+    /// duplicates are removed, keeping the first occurrence.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use us_census_core::model::identifier::{fips, Geoid};
+    ///
+    /// let geoids = Geoid::parse_list("CO, new york,08059").unwrap();
+    /// assert_eq!(geoids, vec![
+    ///     Geoid::State(fips::State(8)),
+    ///     Geoid::State(fips::State(36)),
+    ///     Geoid::County(fips::State(8), fips::County(59)),
+    /// ]);
+    /// assert_eq!(Geoid::parse_list("8").unwrap(), vec![Geoid::State(fips::State(8))]);
+    /// assert_eq!(Geoid::parse_list("New England").unwrap().len(), 6);
+    /// assert_eq!(Geoid::parse_list("pacific").unwrap().len(), 5);
+    /// assert_eq!(Geoid::parse_list("west,pacific").unwrap().len(), 13);
+    /// assert!(Geoid::parse_list("").unwrap().is_empty());
+    /// ```
+    pub fn parse_list(value: &str) -> Result<Vec<Geoid>, CensusError> {
+        let mut result: Vec<Geoid> = vec![];
+        for token in value.split(',').map(str::trim).filter(|t| !t.is_empty()) {
+            let geoids = Geoid::parse_geography(token)?;
+            for geoid in geoids.into_iter() {
+                if !result.contains(&geoid) {
+                    result.push(geoid);
+                }
+            }
+        }
+        Ok(result)
+    }
+
+    /// parses a single geography entry, see [`Geoid::parse_list`].
+    fn parse_geography(token: &str) -> Result<Vec<Geoid>, CensusError> {
+        if let Ok(geoid) = Geoid::from_str(token) {
+            return Ok(vec![geoid]);
+        }
+        if let Ok(state) = token.parse::<StateCode>() {
+            return Ok(vec![Geoid::State(state.into())]);
+        }
+        // division and region names are matched in kebab case, e.g. "new-england"
+        let name = token.replace(['_', ' '], "-");
+        let states = if let Ok(division) = CensusDivision::from_str(&name, true) {
+            division.states()
+        } else if let Ok(region) = CensusRegion::from_str(&name, true) {
+            region.states()
+        } else {
+            return Err(CensusError::Identifier(format!(
+                "'{}' is not a GEOID, state, Census division or Census region",
+                token
+            )));
+        };
+        Ok(states
+            .into_iter()
+            .map(|sc| Geoid::State(sc.into()))
+            .collect_vec())
+    }
+
+    /// formats this GEOID in the Census `GEO_ID` / `ucgid` form, which prefixes the
+    /// GEOID digits with the summary level of the GEOID type.
+    ///
+    /// geographic variant and component codes are not modeled by `Geoid`, so they are
+    /// written as zeroes. for example, congressional districts are written with prefix
+    /// `5000000US` regardless of the Congress they were drawn for.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use us_census_core::model::identifier::{fips, Geoid};
+    ///
+    /// let county = Geoid::County(fips::State(8), fips::County(59));
+    /// assert_eq!(county.to_ucgid(), "0500000US08059");
+    /// ```
+    pub fn to_ucgid(&self) -> String {
+        format!(
+            "{}0000US{}",
+            self.geoid_type().summary_level(),
+            self.geoid_string()
+        )
+    }
+
+    /// generates all state level Geoids for the U.S. within the given scope,
+    /// for example, with or without Puerto Rico and the island areas.
+    pub fn all_states(scope: &StateScope) -> Vec<Geoid> {
+        scope
+            .state_codes()
             .iter()
             .map(|sc| {
                 let s: fips::State = (*sc).into();
@@ -66,6 +255,14 @@ impl Geoid {
             Geoid::CensusTract(_, _, _) => GeoidType::CensusTract,
             Geoid::BlockGroup(_, _, _, _) => GeoidType::BlockGroup,
             Geoid::Block(_, _, _, _) => GeoidType::Block,
+            Geoid::CongressionalDistrict(_, _) => GeoidType::CongressionalDistrict,
+            Geoid::StateLegislativeDistrictUpperChamber(_, _) => {
+                GeoidType::StateLegislativeDistrictUpperChamber
+            }
+            Geoid::StateLegislativeDistrictLowerChamber(_, _) => {
+                GeoidType::StateLegislativeDistrictLowerChamber
+            }
+            Geoid::ZipCodeTabulationArea(_) => GeoidType::ZipCodeTabulationArea,
         }
     }
 
@@ -94,14 +291,45 @@ impl Geoid {
     /// let result = geoid.truncate_geoid_to_type(&GeoidType::County).unwrap();
     /// assert_eq!(result, Geoid::County(fips::State(8), fips::County(59)))
     /// ```
-    pub fn truncate_geoid_to_type(&self, target: &GeoidType) -> Result<Geoid, String> {
-        fn _err(src: &GeoidType, dst: &GeoidType) -> String {
-            format!(
+    ///
+    /// congressional districts, state legislative districts and ZCTAs are not
+    /// part of the county/tract/block hierarchy, so truncation is only defined from
+    /// a district to its state. ZCTAs do not nest within states at all.
+    ///
+    /// ```rust
+    /// use us_census_core::model::identifier::{Geoid, GeoidType, fips};
+    /// let block = Geoid::Block(
+    ///     fips::State(8),
+    ///     fips::County(59),
+    ///     fips::CensusTract(9838),
+    ///     fips::Block(String::from("1001"))
+    /// );
+    /// assert!(block.truncate_geoid_to_type(&GeoidType::CongressionalDistrict).is_err());
+    /// let zcta = Geoid::ZipCodeTabulationArea(fips::ZipCodeTabulationArea(80401));
+    /// assert!(zcta.truncate_geoid_to_type(&GeoidType::State).is_err());
+    /// ```
+    pub fn truncate_geoid_to_type(&self, target: &GeoidType) -> Result<Geoid, CensusError> {
+        fn _err(src: &GeoidType, dst: &GeoidType) -> CensusError {
+            CensusError::Identifier(format!(
                 "{} not a parent type of {}, cannot truncate geoid.",
                 dst, src
-            )
+            ))
         }
         match (self, target) {
+            (_, GeoidType::CongressionalDistrict)
+            | (_, GeoidType::StateLegislativeDistrictUpperChamber)
+            | (_, GeoidType::StateLegislativeDistrictLowerChamber)
+            | (_, GeoidType::ZipCodeTabulationArea)
+                if self.geoid_type() != *target =>
+            {
+                Err(CensusError::Identifier(format!(
+                    "{} geoids do not nest by GEOID truncation, cannot truncate {} geoid {} to {}",
+                    target,
+                    self.geoid_type(),
+                    self.geoid_string(),
+                    target
+                )))
+            }
             (Geoid::State(_), GeoidType::State) => Ok(self.clone()),
             (Geoid::State(_), _) => Err(_err(&self.geoid_type(), target)),
             (Geoid::County(s, _), GeoidType::State) => Ok(Geoid::State(*s)),
@@ -133,14 +361,48 @@ impl Geoid {
                 // special edge case of truncation, since we have no other operations for
                 // converting between Block and Block Group.
                 let block_str = &b.0[0..1];
-                let bg = block_str
-                    .parse::<u64>()
-                    .map_err(|e| format!("cannot read first digit of block as integer: {}", e))?;
+                let bg = block_str.parse::<u64>().map_err(|e| {
+                    CensusError::Identifier(format!(
+                        "cannot read first digit of block as integer: {}",
+                        e
+                    ))
+                })?;
                 let geoid = Geoid::BlockGroup(*s, *c, *t, fips::BlockGroup(bg));
                 Ok(geoid)
             }
             (Geoid::Block(_, _, _, _), GeoidType::Block) => Ok(self.clone()),
             (Geoid::Block(_, _, _, _), _) => Err(_err(&self.geoid_type(), target)),
+            (Geoid::CongressionalDistrict(s, _), GeoidType::State) => Ok(Geoid::State(*s)),
+            (Geoid::CongressionalDistrict(_, _), GeoidType::CongressionalDistrict) => {
+                Ok(self.clone())
+            }
+            (Geoid::CongressionalDistrict(_, _), _) => Err(_err(&self.geoid_type(), target)),
+            (Geoid::StateLegislativeDistrictUpperChamber(s, _), GeoidType::State) => {
+                Ok(Geoid::State(*s))
+            }
+            (
+                Geoid::StateLegislativeDistrictUpperChamber(_, _),
+                GeoidType::StateLegislativeDistrictUpperChamber,
+            ) => Ok(self.clone()),
+            (Geoid::StateLegislativeDistrictUpperChamber(_, _), _) => {
+                Err(_err(&self.geoid_type(), target))
+            }
+            (Geoid::StateLegislativeDistrictLowerChamber(s, _), GeoidType::State) => {
+                Ok(Geoid::State(*s))
+            }
+            (
+                Geoid::StateLegislativeDistrictLowerChamber(_, _),
+                GeoidType::StateLegislativeDistrictLowerChamber,
+            ) => Ok(self.clone()),
+            (Geoid::StateLegislativeDistrictLowerChamber(_, _), _) => {
+                Err(_err(&self.geoid_type(), target))
+            }
+            (Geoid::ZipCodeTabulationArea(_), GeoidType::ZipCodeTabulationArea) => Ok(self.clone()),
+            (Geoid::ZipCodeTabulationArea(_), _) => Err(CensusError::Identifier(format!(
+                "zip code tabulation areas do not nest within {} geoids, cannot truncate geoid {}",
+                target,
+                self.geoid_string()
+            ))),
         }
     }
 
@@ -153,6 +415,9 @@ impl Geoid {
             (Geoid::State(s1), Geoid::CensusTract(s2, _, _)) => s1 == s2,
             (Geoid::State(s1), Geoid::BlockGroup(s2, _, _, _)) => s1 == s2,
             (Geoid::State(s1), Geoid::Block(s2, _, _, _)) => s1 == s2,
+            (Geoid::State(s1), Geoid::CongressionalDistrict(s2, _)) => s1 == s2,
+            (Geoid::State(s1), Geoid::StateLegislativeDistrictUpperChamber(s2, _)) => s1 == s2,
+            (Geoid::State(s1), Geoid::StateLegislativeDistrictLowerChamber(s2, _)) => s1 == s2,
             (Geoid::County(s1, c1), Geoid::CountySubdivision(s2, c2, _)) => s1 == s2 && c1 == c2,
             (Geoid::County(s1, c1), Geoid::CensusTract(s2, c2, _)) => s1 == s2 && c1 == c2,
             (Geoid::County(s1, c1), Geoid::BlockGroup(s2, c2, _, _)) => s1 == s2 && c1 == c2,
@@ -178,6 +443,8 @@ impl Geoid {
     /// Geoid::Block.to_parent() produces a CensusTract, not a BlockGroup, based on
     /// https://www.census.gov/programs-surveys/geography/guidance/geo-identifiers.html,
     /// which does not imply that all block groups are the first digit of all blocks.
+    ///
+    /// ZCTAs are national and so, like `State`, have no parent.
     pub fn to_parent(&self) -> Option<Geoid> {
         match self {
             Geoid::State(_) => None,
@@ -187,60 +454,96 @@ impl Geoid {
             Geoid::CensusTract(s, c, _) => Some(Geoid::County(*s, *c)),
             Geoid::BlockGroup(s, c, t, _) => Some(Geoid::CensusTract(*s, *c, *t)),
             Geoid::Block(s, c, t, _) => Some(Geoid::CensusTract(*s, *c, *t)),
+            Geoid::CongressionalDistrict(s, _) => Some(Geoid::State(*s)),
+            Geoid::StateLegislativeDistrictUpperChamber(s, _) => Some(Geoid::State(*s)),
+            Geoid::StateLegislativeDistrictLowerChamber(s, _) => Some(Geoid::State(*s)),
+            Geoid::ZipCodeTabulationArea(_) => None,
         }
     }
 
-    pub fn to_state(&self) -> Geoid {
+    /// gets the state that contains this GEOID. fails for ZCTAs, which
+    /// are not identified by (and may cross) state boundaries.
+    pub fn to_state(&self) -> Result<Geoid, CensusError> {
         match self {
-            Geoid::State(_) => self.clone(),
-            Geoid::County(st, _) => Geoid::State(*st),
-            Geoid::CountySubdivision(st, _, _) => Geoid::State(*st),
-            Geoid::Place(st, _) => Geoid::State(*st),
-            Geoid::CensusTract(st, _, _) => Geoid::State(*st),
-            Geoid::BlockGroup(st, _, _, _) => Geoid::State(*st),
-            Geoid::Block(st, _, _, _) => Geoid::State(*st),
+            Geoid::State(_) => Ok(self.clone()),
+            Geoid::County(st, _) => Ok(Geoid::State(*st)),
+            Geoid::CountySubdivision(st, _, _) => Ok(Geoid::State(*st)),
+            Geoid::Place(st, _) => Ok(Geoid::State(*st)),
+            Geoid::CensusTract(st, _, _) => Ok(Geoid::State(*st)),
+            Geoid::BlockGroup(st, _, _, _) => Ok(Geoid::State(*st)),
+            Geoid::Block(st, _, _, _) => Ok(Geoid::State(*st)),
+            Geoid::CongressionalDistrict(st, _) => Ok(Geoid::State(*st)),
+            Geoid::StateLegislativeDistrictUpperChamber(st, _) => Ok(Geoid::State(*st)),
+            Geoid::StateLegislativeDistrictLowerChamber(st, _) => Ok(Geoid::State(*st)),
+            Geoid::ZipCodeTabulationArea(_) => Err(CensusError::Identifier(String::from(
+                "zip code tabulation area geoid does not contain a state geoid",
+            ))),
         }
     }
 
-    pub fn to_state_abbreviation(&self) -> Result<String, String> {
-        let state_fips = match self.to_state() {
+    pub fn to_state_abbreviation(&self) -> Result<String, CensusError> {
+        let state_fips = match self.to_state()? {
             Geoid::State(s) => Ok(s),
-            _ => Err(String::from("internal error")),
+            _ => Err(CensusError::Internal(String::from("internal error"))),
         }?;
         let state_code = StateCode::try_from(state_fips)?;
         let state_str = state_code.to_state_abbreviation();
         Ok(state_str)
     }
 
-    pub fn to_county(&self) -> Result<Geoid, String> {
+    pub fn to_county(&self) -> Result<Geoid, CensusError> {
         match self {
-            Geoid::State(_) => Err(String::from("state geoid does not contain a county geoid")),
+            Geoid::State(_) => Err(CensusError::Identifier(String::from("state geoid does not contain a county geoid"))),
             Geoid::County(st, ct) => Ok(Geoid::County(*st, *ct)),
             Geoid::CountySubdivision(st, ct, _) => Ok(Geoid::County(*st, *ct)),
-            Geoid::Place(_, _) => Err(String::from("place geoid does not contain a county geoid")),
+            Geoid::Place(_, _) => Err(CensusError::Identifier(String::from("place geoid does not contain a county geoid"))),
             Geoid::CensusTract(st, ct, _) => Ok(Geoid::County(*st, *ct)),
             Geoid::BlockGroup(st, ct, _, _) => Ok(Geoid::County(*st, *ct)),
             Geoid::Block(st, ct, _, _) => Ok(Geoid::County(*st, *ct)),
+            Geoid::CongressionalDistrict(_, _) => Err(CensusError::Identifier(String::from(
+                "congressional district geoid does not contain a county geoid",
+            ))),
+            Geoid::StateLegislativeDistrictUpperChamber(_, _) => Err(CensusError::Identifier(String::from(
+                "state legislative district (upper chamber) geoid does not contain a county geoid",
+            ))),
+            Geoid::StateLegislativeDistrictLowerChamber(_, _) => Err(CensusError::Identifier(String::from(
+                "state legislative district (lower chamber) geoid does not contain a county geoid",
+            ))),
+            Geoid::ZipCodeTabulationArea(_) => Err(CensusError::Identifier(String::from(
+                "zip code tabulation area geoid does not contain a county geoid",
+            ))),
         }
     }
 
-    pub fn to_census_tract(&self) -> Result<Geoid, String> {
+    pub fn to_census_tract(&self) -> Result<Geoid, CensusError> {
         match self {
-            Geoid::State(_) => Err(String::from(
+            Geoid::State(_) => Err(CensusError::Identifier(String::from(
                 "state geoid does not contain a census tract geoid",
-            )),
-            Geoid::County(_, _) => Err(String::from(
+            ))),
+            Geoid::County(_, _) => Err(CensusError::Identifier(String::from(
                 "county geoid does not contain a census tract geoid",
-            )),
-            Geoid::CountySubdivision(_, _, _) => Err(String::from(
+            ))),
+            Geoid::CountySubdivision(_, _, _) => Err(CensusError::Identifier(String::from(
                 "county subdivision geoid does not contain a census tract geoid",
-            )),
-            Geoid::Place(_, _) => Err(String::from(
+            ))),
+            Geoid::Place(_, _) => Err(CensusError::Identifier(String::from(
                 "place geoid does not contain a census tract geoid",
-            )),
+            ))),
             Geoid::CensusTract(st, ct, tr) => Ok(Geoid::CensusTract(*st, *ct, *tr)),
             Geoid::BlockGroup(st, ct, tr, _) => Ok(Geoid::CensusTract(*st, *ct, *tr)),
             Geoid::Block(st, ct, tr, _) => Ok(Geoid::CensusTract(*st, *ct, *tr)),
+            Geoid::CongressionalDistrict(_, _) => Err(CensusError::Identifier(String::from(
+                "congressional district geoid does not contain a census tract geoid",
+            ))),
+            Geoid::StateLegislativeDistrictUpperChamber(_, _) => Err(CensusError::Identifier(String::from(
+                "state legislative district (upper chamber) geoid does not contain a census tract geoid",
+            ))),
+            Geoid::StateLegislativeDistrictLowerChamber(_, _) => Err(CensusError::Identifier(String::from(
+                "state legislative district (lower chamber) geoid does not contain a census tract geoid",
+            ))),
+            Geoid::ZipCodeTabulationArea(_) => Err(CensusError::Identifier(String::from(
+                "zip code tabulation area geoid does not contain a census tract geoid",
+            ))),
         }
     }
 }
@@ -277,12 +580,33 @@ impl HasGeoidString for Geoid {
                 tr.geoid_string(),
                 bl.geoid_string()
             ),
+            Geoid::CongressionalDistrict(st, cd) => {
+                format!("{}{}", st.geoid_string(), cd.geoid_string())
+            }
+            Geoid::StateLegislativeDistrictUpperChamber(st, sldu) => {
+                format!("{}{}", st.geoid_string(), sldu.geoid_string())
+            }
+            Geoid::StateLegislativeDistrictLowerChamber(st, sldl) => {
+                format!("{}{}", st.geoid_string(), sldl.geoid_string())
+            }
+            Geoid::ZipCodeTabulationArea(zcta) => zcta.geoid_string(),
         }
     }
 }
 
+/// writes the `type=digits` form, which can be parsed back via `FromStr`.
 impl Display for Geoid {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "{}={}", self.variant_name(), self.geoid_string())
     }
 }
+
+/// reads the GeoidType from the 7-character GEO_ID prefix, which is the summary level
+/// followed by the geographic variant and component codes.
+fn ucgid_geoid_type(prefix: &str) -> Option<GeoidType> {
+    if prefix.len() != 7 {
+        return None;
+    }
+    let sumlev = prefix.get(0..3)?.parse::<SummaryLevel>().ok()?;
+    GeoidType::try_from(sumlev).ok()
+}
@@ -0,0 +1,174 @@
+use std::collections::BTreeSet;
+
+use itertools::Itertools;
+
+use super::{geoid::Geoid, geoid_type::GeoidType, packed_geoid::PackedGeoid};
+use crate::error::CensusError;
+
+/// GeoidTypes that may contain other GEOIDs by truncation, see
+/// [`Geoid::truncate_geoid_to_type`].
+const ANCESTOR_TYPES: [GeoidType; 4] = [
+    GeoidType::State,
+    GeoidType::County,
+    GeoidType::CensusTract,
+    GeoidType::BlockGroup,
+];
+
+/// a set of GEOIDs of mixed types supporting hierarchical containment queries.
+///
+/// members are stored as [`PackedGeoid`]s in GEOID string order, so all members
+/// that share a GEOID prefix are adjacent. ancestry follows the truncation rules of
+/// [`Geoid::truncate_geoid_to_type`]: a GEOID is an ancestor of another if the other
+/// truncates to it, for example, a county is an ancestor of its tracts and blocks,
+/// but not of the places that share its state.
+///
+/// # Examples
+///
+/// ```rust
+/// use us_census_core::model::identifier::{fips, Geoid, GeoidSet};
+///
+/// let filter = GeoidSet::try_from(vec![
+///     Geoid::State(fips::State(9)),
+///     Geoid::County(fips::State(8), fips::County(59)),
+/// ]).unwrap();
+/// let block = Geoid::Block(
+///     fips::State(8),
+///     fips::County(59),
+///     fips::CensusTract(9838),
+///     fips::Block(String::from("1001")),
+/// );
+/// let other_county = Geoid::County(fips::State(8), fips::County(31));
+/// assert!(filter.has_ancestor_of(&block));
+/// assert!(!filter.has_ancestor_of(&other_county));
+/// assert!(filter.covers(&Geoid::County(fips::State(8), fips::County(59))));
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct GeoidSet {
+    members: BTreeSet<PackedGeoid>,
+}
+
+impl GeoidSet {
+    pub fn new() -> GeoidSet {
+        GeoidSet::default()
+    }
+
+    /// adds a GEOID to this set. returns true if it was not already present.
+    pub fn insert(&mut self, geoid: &Geoid) -> Result<bool, CensusError> {
+        let packed = PackedGeoid::try_from(geoid)?;
+        Ok(self.members.insert(packed))
+    }
+
+    pub fn len(&self) -> usize {
+        self.members.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.members.is_empty()
+    }
+
+    pub fn contains(&self, geoid: &Geoid) -> bool {
+        match PackedGeoid::try_from(geoid) {
+            Ok(packed) => self.members.contains(&packed),
+            Err(_) => false,
+        }
+    }
+
+    /// true if any member of this set is a strict ancestor of the GEOID.
+    pub fn has_ancestor_of(&self, geoid: &Geoid) -> bool {
+        match PackedGeoid::try_from(geoid) {
+            Ok(packed) => self.has_ancestor_of_packed(&packed),
+            Err(_) => false,
+        }
+    }
+
+    /// true if the GEOID is a member of this set or has an ancestor in this set.
+    /// used to filter data rows by a list of GEOIDs describing a region.
+    pub fn covers(&self, geoid: &Geoid) -> bool {
+        match PackedGeoid::try_from(geoid) {
+            Ok(packed) => self.members.contains(&packed) || self.has_ancestor_of_packed(&packed),
+            Err(_) => false,
+        }
+    }
+
+    /// all members of this set that are strict descendants of the GEOID, in GEOID order.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use us_census_core::model::identifier::{fips, Geoid, GeoidSet};
+    ///
+    /// let tract = Geoid::CensusTract(fips::State(8), fips::County(59), fips::CensusTract(9838));
+    /// let place = Geoid::Place(fips::State(8), fips::Place(5900));
+    /// let set = GeoidSet::try_from(vec![place, tract.clone()]).unwrap();
+    /// let county = Geoid::County(fips::State(8), fips::County(59));
+    /// // place 0805900 shares the county's GEOID prefix but is not contained by it
+    /// assert_eq!(set.descendants_of(&county), vec![tract]);
+    /// ```
+    pub fn descendants_of(&self, geoid: &Geoid) -> Vec<Geoid> {
+        let packed = match PackedGeoid::try_from(geoid) {
+            Ok(p) => p,
+            Err(_) => return vec![],
+        };
+        let target = packed.geoid_type();
+        let (lower, upper) = packed.prefix_range();
+        self.members
+            .range(lower..upper)
+            .filter(|m| **m != packed && m.truncate_to_type(&target).ok() == Some(packed))
+            .map(|m| Geoid::from(*m))
+            .collect_vec()
+    }
+
+    pub fn union(&self, other: &GeoidSet) -> GeoidSet {
+        GeoidSet {
+            members: self.members.union(&other.members).copied().collect(),
+        }
+    }
+
+    pub fn intersection(&self, other: &GeoidSet) -> GeoidSet {
+        GeoidSet {
+            members: self.members.intersection(&other.members).copied().collect(),
+        }
+    }
+
+    /// iterates over the members of this set in GEOID order.
+    pub fn iter(&self) -> impl Iterator<Item = Geoid> + '_ {
+        self.members.iter().map(|m| Geoid::from(*m))
+    }
+
+    fn has_ancestor_of_packed(&self, packed: &PackedGeoid) -> bool {
+        let geoid_type = packed.geoid_type();
+        ANCESTOR_TYPES
+            .iter()
+            .filter(|t| **t != geoid_type)
+            .filter_map(|t| packed.truncate_to_type(t).ok())
+            .any(|ancestor| self.members.contains(&ancestor))
+    }
+}
+
+impl TryFrom<&[Geoid]> for GeoidSet {
+    type Error = CensusError;
+
+    fn try_from(geoids: &[Geoid]) -> Result<Self, Self::Error> {
+        let members = geoids
+            .iter()
+            .map(PackedGeoid::try_from)
+            .collect::<Result<BTreeSet<_>, _>>()?;
+        Ok(GeoidSet { members })
+    }
+}
+
+impl TryFrom<Vec<Geoid>> for GeoidSet {
+    type Error = CensusError;
+
+    fn try_from(geoids: Vec<Geoid>) -> Result<Self, Self::Error> {
+        GeoidSet::try_from(&geoids[..])
+    }
+}
+
+impl FromIterator<PackedGeoid> for GeoidSet {
+    fn from_iter<T: IntoIterator<Item = PackedGeoid>>(iter: T) -> Self {
+        GeoidSet {
+            members: iter.into_iter().collect(),
+        }
+    }
+}
@@ -1,6 +1,7 @@
 use std::fmt::Display;
 
-use super::{fips, geoid::Geoid};
+use super::{fips, geoid::Geoid, summary_level::SummaryLevel};
+use crate::error::CensusError;
 use clap::ValueEnum;
 use itertools::Itertools;
 use serde::{Deserialize, Serialize};
@@ -15,6 +16,10 @@ pub enum GeoidType {
     CensusTract,
     BlockGroup,
     Block,
+    CongressionalDistrict,
+    StateLegislativeDistrictUpperChamber,
+    StateLegislativeDistrictLowerChamber,
+    ZipCodeTabulationArea,
 }
 
 impl Display for GeoidType {
@@ -27,31 +32,127 @@ impl Display for GeoidType {
             GeoidType::CensusTract => String::from("census_tract"),
             GeoidType::BlockGroup => String::from("block_group"),
             GeoidType::Block => String::from("block"),
+            GeoidType::CongressionalDistrict => String::from("congressional_district"),
+            GeoidType::StateLegislativeDistrictUpperChamber => {
+                String::from("state_legislative_district_upper_chamber")
+            }
+            GeoidType::StateLegislativeDistrictLowerChamber => {
+                String::from("state_legislative_district_lower_chamber")
+            }
+            GeoidType::ZipCodeTabulationArea => String::from("zip_code_tabulation_area"),
         };
         write!(f, "{}", s)
     }
 }
 
 impl GeoidType {
-    pub fn geoid_from_str(&self, value: &str) -> Result<Geoid, String> {
+    /// the summary level (SUMLEV) code for this GeoidType. blocks use the 2020
+    /// summary level, see [`SummaryLevel::BLOCK_2010`] for 2010-vintage blocks.
+    pub fn summary_level(&self) -> SummaryLevel {
+        match self {
+            GeoidType::State => SummaryLevel::STATE,
+            GeoidType::County => SummaryLevel::COUNTY,
+            GeoidType::CountySubdivision => SummaryLevel::COUNTY_SUBDIVISION,
+            GeoidType::Place => SummaryLevel::PLACE,
+            GeoidType::CensusTract => SummaryLevel::CENSUS_TRACT,
+            GeoidType::BlockGroup => SummaryLevel::BLOCK_GROUP,
+            GeoidType::Block => SummaryLevel::BLOCK,
+            GeoidType::CongressionalDistrict => SummaryLevel::CONGRESSIONAL_DISTRICT,
+            GeoidType::StateLegislativeDistrictUpperChamber => {
+                SummaryLevel::STATE_LEGISLATIVE_DISTRICT_UPPER_CHAMBER
+            }
+            GeoidType::StateLegislativeDistrictLowerChamber => {
+                SummaryLevel::STATE_LEGISLATIVE_DISTRICT_LOWER_CHAMBER
+            }
+            GeoidType::ZipCodeTabulationArea => SummaryLevel::ZIP_CODE_TABULATION_AREA,
+        }
+    }
+
+    /// the component types that make up a GEOID of this type, from the
+    /// outermost to this type.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use us_census_core::model::identifier::GeoidType;
+    ///
+    /// assert_eq!(
+    ///     GeoidType::BlockGroup.components(),
+    ///     vec![GeoidType::State, GeoidType::County, GeoidType::CensusTract, GeoidType::BlockGroup]
+    /// );
+    /// assert_eq!(GeoidType::Place.components(), vec![GeoidType::State, GeoidType::Place]);
+    /// ```
+    pub fn components(&self) -> Vec<GeoidType> {
+        use GeoidType as GT;
+        match self {
+            GT::State => vec![GT::State],
+            GT::County => vec![GT::State, GT::County],
+            GT::CountySubdivision => vec![GT::State, GT::County, GT::CountySubdivision],
+            GT::Place => vec![GT::State, GT::Place],
+            GT::CensusTract => vec![GT::State, GT::County, GT::CensusTract],
+            GT::BlockGroup => vec![GT::State, GT::County, GT::CensusTract, GT::BlockGroup],
+            GT::Block => vec![GT::State, GT::County, GT::CensusTract, GT::Block],
+            GT::CongressionalDistrict => vec![GT::State, GT::CongressionalDistrict],
+            GT::StateLegislativeDistrictUpperChamber => {
+                vec![GT::State, GT::StateLegislativeDistrictUpperChamber]
+            }
+            GT::StateLegislativeDistrictLowerChamber => {
+                vec![GT::State, GT::StateLegislativeDistrictLowerChamber]
+            }
+            GT::ZipCodeTabulationArea => vec![GT::ZipCodeTabulationArea],
+        }
+    }
+
+    /// parses a GEOID string of this GeoidType.
+    ///
+    /// several GEOID types share the same string length (for example, county,
+    /// state legislative district and ZCTA GEOIDs are all 5 digits), so this method
+    /// should be preferred over `Geoid::try_from` whenever the type is known.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use us_census_core::model::identifier::{fips, Geoid, GeoidType};
+    ///
+    /// let sldu = GeoidType::StateLegislativeDistrictUpperChamber.geoid_from_str("08019").unwrap();
+    /// assert_eq!(
+    ///     sldu,
+    ///     Geoid::StateLegislativeDistrictUpperChamber(
+    ///         fips::State(8),
+    ///         fips::StateLegislativeDistrictUpperChamber(String::from("019"))
+    ///     )
+    /// );
+    /// // some states use letters in their district codes
+    /// let sldl = GeoidType::StateLegislativeDistrictLowerChamber.geoid_from_str("50A01").unwrap();
+    /// assert_eq!(sldl.to_string(), "state_legislative_district_lower_chamber=50A01");
+    /// let zcta = GeoidType::ZipCodeTabulationArea.geoid_from_str("80401").unwrap();
+    /// assert_eq!(zcta, Geoid::ZipCodeTabulationArea(fips::ZipCodeTabulationArea(80401)));
+    /// ```
+    pub fn geoid_from_str(&self, value: &str) -> Result<Geoid, CensusError> {
+        if !value.is_ascii() {
+            return Err(CensusError::Identifier(format!(
+                "geoid should only contain ASCII digits, found: {}",
+                value
+            )));
+        }
         let value_len = value.len();
         match self {
             GeoidType::State => {
                 if value_len != 2 {
-                    Err(format!(
+                    Err(CensusError::Identifier(format!(
                         "for state geoid, expected 2-digit value, found: {}",
                         value
-                    ))
+                    )))
                 } else {
                     self.geoid_from_slice_of_strings(&[value.to_string()])
                 }
             }
             GeoidType::County => {
                 if value_len != 5 {
-                    Err(format!(
+                    Err(CensusError::Identifier(format!(
                         "for county geoid, expected 5-digit value, found: {}",
                         value
-                    ))
+                    )))
                 } else {
                     self.geoid_from_slice_of_strings(&[
                         value[0..2].to_string(),
@@ -61,10 +162,10 @@ impl GeoidType {
             }
             GeoidType::CountySubdivision => {
                 if value_len != 10 {
-                    Err(format!(
+                    Err(CensusError::Identifier(format!(
                         "for county subdivision geoid, expected 10-digit value, found: {}",
                         value
-                    ))
+                    )))
                 } else {
                     self.geoid_from_slice_of_strings(&[
                         value[0..2].to_string(),
@@ -75,10 +176,10 @@ impl GeoidType {
             }
             GeoidType::Place => {
                 if value_len != 7 {
-                    Err(format!(
+                    Err(CensusError::Identifier(format!(
                         "for place geoid, expected 7-digit value, found: {}",
                         value
-                    ))
+                    )))
                 } else {
                     self.geoid_from_slice_of_strings(&[
                         value[0..2].to_string(),
@@ -88,10 +189,10 @@ impl GeoidType {
             }
             GeoidType::CensusTract => {
                 if value_len != 11 {
-                    Err(format!(
+                    Err(CensusError::Identifier(format!(
                         "for census tract geoid, expected 11-digit value, found: {}",
                         value
-                    ))
+                    )))
                 } else {
                     self.geoid_from_slice_of_strings(&[
                         value[0..2].to_string(),
@@ -102,10 +203,10 @@ impl GeoidType {
             }
             GeoidType::BlockGroup => {
                 if value_len != 12 {
-                    Err(format!(
+                    Err(CensusError::Identifier(format!(
                         "for block group geoid, expected 12-digit value, found: {}",
                         value
-                    ))
+                    )))
                 } else {
                     self.geoid_from_slice_of_strings(&[
                         value[0..2].to_string(),
@@ -117,10 +218,10 @@ impl GeoidType {
             }
             GeoidType::Block => {
                 if value_len != 15 && value_len != 16 {
-                    Err(format!(
+                    Err(CensusError::Identifier(format!(
                         "for block geoid, expected 15 or 16-digit value, found: {} with length {}",
                         value, value_len
-                    ))
+                    )))
                 } else {
                     self.geoid_from_slice_of_strings(&[
                         value[0..2].to_string(),
@@ -130,17 +231,66 @@ impl GeoidType {
                     ])
                 }
             }
+            GeoidType::CongressionalDistrict => {
+                if value_len != 4 {
+                    Err(CensusError::Identifier(format!(
+                        "for congressional district geoid, expected 4-digit value, found: {}",
+                        value
+                    )))
+                } else {
+                    self.geoid_from_slice_of_strings(&[
+                        value[0..2].to_string(),
+                        value[2..4].to_string(),
+                    ])
+                }
+            }
+            GeoidType::StateLegislativeDistrictUpperChamber => {
+                if value_len != 5 {
+                    Err(CensusError::Identifier(format!(
+                        "for state legislative district (upper chamber) geoid, expected 5-character value, found: {}",
+                        value
+                    )))
+                } else {
+                    self.geoid_from_slice_of_strings(&[
+                        value[0..2].to_string(),
+                        value[2..5].to_string(),
+                    ])
+                }
+            }
+            GeoidType::StateLegislativeDistrictLowerChamber => {
+                if value_len != 5 {
+                    Err(CensusError::Identifier(format!(
+                        "for state legislative district (lower chamber) geoid, expected 5-character value, found: {}",
+                        value
+                    )))
+                } else {
+                    self.geoid_from_slice_of_strings(&[
+                        value[0..2].to_string(),
+                        value[2..5].to_string(),
+                    ])
+                }
+            }
+            GeoidType::ZipCodeTabulationArea => {
+                if value_len != 5 {
+                    Err(CensusError::Identifier(format!(
+                        "for zip code tabulation area geoid, expected 5-digit value, found: {}",
+                        value
+                    )))
+                } else {
+                    self.geoid_from_slice_of_strings(&[value.to_string()])
+                }
+            }
         }
     }
-    pub fn geoid_from_slice_of_strings(&self, vals: &[String]) -> Result<Geoid, String> {
+    pub fn geoid_from_slice_of_strings(&self, vals: &[String]) -> Result<Geoid, CensusError> {
         match self {
             GeoidType::State => {
                 let arr = as_usizes(vals)?;
                 if arr.len() != 1 {
-                    Err(format!(
+                    Err(CensusError::Identifier(format!(
                         "for state-level query, expected 1 geoid column, found: {}",
                         arr.into_iter().join(",")
-                    ))
+                    )))
                 } else {
                     Ok(Geoid::State(fips::State(arr[0])))
                 }
@@ -148,10 +298,10 @@ impl GeoidType {
             GeoidType::County => {
                 let arr = as_usizes(vals)?;
                 if arr.len() != 2 {
-                    Err(format!(
+                    Err(CensusError::Identifier(format!(
                         "for county-level query, expected 2 geoid columns, found: {}",
                         arr.into_iter().join(",")
-                    ))
+                    )))
                 } else {
                     Ok(Geoid::County(fips::State(arr[0]), fips::County(arr[1])))
                 }
@@ -159,10 +309,10 @@ impl GeoidType {
             GeoidType::CountySubdivision => {
                 let arr = as_usizes(vals)?;
                 if arr.len() != 3 {
-                    Err(format!(
+                    Err(CensusError::Identifier(format!(
                         "for county subdivision-level query, expected 3 geoid columns, found: {}",
                         arr.into_iter().join(",")
-                    ))
+                    )))
                 } else {
                     Ok(Geoid::CountySubdivision(
                         fips::State(arr[0]),
@@ -174,10 +324,10 @@ impl GeoidType {
             GeoidType::Place => {
                 let arr = as_usizes(vals)?;
                 if arr.len() != 2 {
-                    Err(format!(
+                    Err(CensusError::Identifier(format!(
                         "for place-level query, expected 2 geoid columns, found: {}",
                         arr.into_iter().join(",")
-                    ))
+                    )))
                 } else {
                     Ok(Geoid::Place(fips::State(arr[0]), fips::Place(arr[1])))
                 }
@@ -185,10 +335,10 @@ impl GeoidType {
             GeoidType::CensusTract => {
                 let arr = as_usizes(vals)?;
                 if arr.len() != 3 {
-                    Err(format!(
+                    Err(CensusError::Identifier(format!(
                         "for census tract-level query, expected 3 geoid column, found: {}",
                         arr.into_iter().join(",")
-                    ))
+                    )))
                 } else {
                     Ok(Geoid::CensusTract(
                         fips::State(arr[0]),
@@ -201,11 +351,11 @@ impl GeoidType {
                 let arr = as_usizes(vals)?;
                 let arr_len = arr.len();
                 if arr_len != 4 {
-                    Err(format!(
+                    Err(CensusError::Identifier(format!(
                         "for block group-level query, expected 4 geoid columns, found: {} with length {}",
                         arr.into_iter().join(","),
                         arr_len
-                    ))
+                    )))
                 } else {
                     Ok(Geoid::BlockGroup(
                         fips::State(arr[0]),
@@ -218,34 +368,102 @@ impl GeoidType {
             GeoidType::Block => {
                 let arr = as_usizes(vals)?;
                 if arr.len() != 4 {
-                    Err(format!(
+                    Err(CensusError::Identifier(format!(
                         "for block-level query, expected 4 geoid columns, found: {}",
                         arr.into_iter().join(",")
-                    ))
+                    )))
                 } else {
                     Ok(Geoid::Block(
                         fips::State(arr[0]),
                         fips::County(arr[1]),
                         fips::CensusTract(arr[2]),
-                        fips::Block(format!("{}", arr[3])),
+                        fips::Block(vals[3].clone()),
                     ))
                 }
             }
+            GeoidType::CongressionalDistrict => {
+                let arr = as_usizes(vals)?;
+                if arr.len() != 2 {
+                    Err(CensusError::Identifier(format!(
+                        "for congressional district-level query, expected 2 geoid columns, found: {}",
+                        arr.into_iter().join(",")
+                    )))
+                } else {
+                    Ok(Geoid::CongressionalDistrict(
+                        fips::State(arr[0]),
+                        fips::CongressionalDistrict(arr[1]),
+                    ))
+                }
+            }
+            GeoidType::StateLegislativeDistrictUpperChamber => {
+                if vals.len() != 2 {
+                    Err(CensusError::Identifier(format!(
+                        "for state legislative district (upper chamber)-level query, expected 2 geoid columns, found: {}",
+                        vals.iter().join(",")
+                    )))
+                } else {
+                    let arr = as_usizes(&vals[0..1])?;
+                    Ok(Geoid::StateLegislativeDistrictUpperChamber(
+                        fips::State(arr[0]),
+                        fips::StateLegislativeDistrictUpperChamber(as_district_code(&vals[1])?),
+                    ))
+                }
+            }
+            GeoidType::StateLegislativeDistrictLowerChamber => {
+                if vals.len() != 2 {
+                    Err(CensusError::Identifier(format!(
+                        "for state legislative district (lower chamber)-level query, expected 2 geoid columns, found: {}",
+                        vals.iter().join(",")
+                    )))
+                } else {
+                    let arr = as_usizes(&vals[0..1])?;
+                    Ok(Geoid::StateLegislativeDistrictLowerChamber(
+                        fips::State(arr[0]),
+                        fips::StateLegislativeDistrictLowerChamber(as_district_code(&vals[1])?),
+                    ))
+                }
+            }
+            GeoidType::ZipCodeTabulationArea => {
+                let arr = as_usizes(vals)?;
+                if arr.len() != 1 {
+                    Err(CensusError::Identifier(format!(
+                        "for zip code tabulation area-level query, expected 1 geoid column, found: {}",
+                        arr.into_iter().join(",")
+                    )))
+                } else {
+                    Ok(Geoid::ZipCodeTabulationArea(fips::ZipCodeTabulationArea(
+                        arr[0],
+                    )))
+                }
+            }
         }
     }
 }
 
 /// helper function to convert a slice of strings into u64s used to build fips::* values.
-fn as_usizes(arr: &[String]) -> Result<Vec<u64>, String> {
+fn as_usizes(arr: &[String]) -> Result<Vec<u64>, CensusError> {
     arr.iter()
         .map(|v| {
             let v_u64 = v.parse::<u64>().map_err(|e| {
-                format!(
+                CensusError::Identifier(format!(
                     "raw geoid value should be a string wrapping an integer, found '{}'. error: {}",
                     v, e
-                )
+                ))
             })?;
             Ok(v_u64)
         })
-        .collect::<Result<Vec<u64>, String>>()
+        .collect::<Result<Vec<u64>, CensusError>>()
+}
+
+/// helper function to validate a state legislative district code, which is 3 alphanumeric
+/// characters, such as "019" or "A01".
+fn as_district_code(value: &str) -> Result<String, CensusError> {
+    if value.len() == 3 && value.chars().all(|c| c.is_ascii_alphanumeric()) {
+        Ok(value.to_uppercase())
+    } else {
+        Err(CensusError::Identifier(format!(
+            "state legislative district code should be 3 alphanumeric characters, found '{}'",
+            value
+        )))
+    }
 }
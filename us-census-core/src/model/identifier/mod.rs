@@ -1,8 +1,16 @@
 pub mod fips;
+pub mod geography_vintage;
 pub mod geoid;
+pub mod geoid_set;
 pub mod geoid_type;
 pub mod has_geoid_string;
 pub mod has_geoid_type;
+pub mod packed_geoid;
+pub mod summary_level;
 
+pub use geography_vintage::{GeographyVintage, HasGeographyVintage};
 pub use geoid::Geoid;
+pub use geoid_set::GeoidSet;
 pub use geoid_type::GeoidType;
+pub use packed_geoid::PackedGeoid;
+pub use summary_level::SummaryLevel;
@@ -0,0 +1,393 @@
+use std::fmt::Display;
+
+use super::{fips, geoid::Geoid, geoid_type::GeoidType, has_geoid_string::HasGeoidString};
+use crate::error::CensusError;
+
+/// maximum number of GEOID digits that can be packed. the longest GEOID is a
+/// 15-digit block, optionally followed by a single suffix letter.
+const MAX_DIGITS: u32 = 15;
+const TAG_BITS: u32 = 4;
+const SUFFIX_BITS: u32 = 5;
+const LEN_BITS: u32 = 4;
+const SUFFIX_SHIFT: u32 = TAG_BITS;
+const LEN_SHIFT: u32 = SUFFIX_SHIFT + SUFFIX_BITS;
+const DIGITS_SHIFT: u32 = LEN_SHIFT + LEN_BITS;
+
+/// compact, copyable representation of a [`Geoid`] packed into a single u64.
+///
+/// the GEOID digits are stored left-aligned (zero-padded to 15 digits) in the high
+/// bits, followed by the digit count, the block suffix letter (if any) and the
+/// GeoidType. as a result, the natural integer ordering of a PackedGeoid matches the
+/// ordering of GEOID strings, with parents sorted directly before their children.
+/// GEOIDs of different types that share the same string are ordered by type.
+///
+/// used in place of `Geoid` as a hash/sort key when grouping or joining large
+/// (block-level) datasets, since it avoids the allocations of `Geoid::Block`.
+///
+/// # Examples
+///
+/// ```rust
+/// use us_census_core::model::identifier::{fips, Geoid, PackedGeoid};
+///
+/// let block = Geoid::Block(
+///     fips::State(8),
+///     fips::County(59),
+///     fips::CensusTract(9838),
+///     fips::Block(String::from("1001")),
+/// );
+/// let packed = PackedGeoid::try_from(&block).unwrap();
+/// assert_eq!(Geoid::from(packed), block);
+///
+/// let county = PackedGeoid::try_from(&Geoid::County(fips::State(8), fips::County(59))).unwrap();
+/// let state = PackedGeoid::try_from(&Geoid::State(fips::State(9))).unwrap();
+/// let mut sorted = vec![state, packed, county];
+/// sorted.sort();
+/// assert_eq!(sorted, vec![county, packed, state]);
+/// ```
+///
+/// 2010-vintage block suffix letters are preserved.
+///
+/// ```rust
+/// use us_census_core::model::identifier::{fips, Geoid, PackedGeoid};
+/// use us_census_core::model::identifier::has_geoid_string::HasGeoidString;
+///
+/// let block = Geoid::Block(
+///     fips::State(8),
+///     fips::County(59),
+///     fips::CensusTract(9838),
+///     fips::Block(String::from("1001A")),
+/// );
+/// let packed = PackedGeoid::try_from(&block).unwrap();
+/// assert_eq!(packed.geoid_string(), "080590098381001A");
+/// assert_eq!(Geoid::from(packed), block);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct PackedGeoid(u64);
+
+impl PackedGeoid {
+    pub fn geoid_type(&self) -> GeoidType {
+        tag_to_type(self.tag())
+    }
+
+    /// truncates this GEOID to a parent GeoidType, following the same rules as
+    /// [`Geoid::truncate_geoid_to_type`], without unpacking the GEOID.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use us_census_core::model::identifier::{fips, Geoid, GeoidType, PackedGeoid};
+    ///
+    /// let tract = Geoid::CensusTract(fips::State(8), fips::County(59), fips::CensusTract(9838));
+    /// let packed = PackedGeoid::try_from(&tract).unwrap();
+    /// let county = packed.truncate_to_type(&GeoidType::County).unwrap();
+    /// assert_eq!(Geoid::from(county), Geoid::County(fips::State(8), fips::County(59)));
+    /// assert!(packed.truncate_to_type(&GeoidType::Place).is_err());
+    /// ```
+    pub fn truncate_to_type(&self, target: &GeoidType) -> Result<PackedGeoid, CensusError> {
+        use GeoidType as GT;
+        let source = self.geoid_type();
+        if source == *target {
+            return Ok(*self);
+        }
+        let target_len = match (source, target) {
+            (
+                GT::County
+                | GT::CountySubdivision
+                | GT::Place
+                | GT::CensusTract
+                | GT::BlockGroup
+                | GT::Block
+                | GT::CongressionalDistrict
+                | GT::StateLegislativeDistrictUpperChamber
+                | GT::StateLegislativeDistrictLowerChamber,
+                GT::State,
+            ) => 2,
+            (GT::CountySubdivision | GT::CensusTract | GT::BlockGroup | GT::Block, GT::County) => 5,
+            (GT::BlockGroup | GT::Block, GT::CensusTract) => 11,
+            (GT::Block, GT::BlockGroup) => 12,
+            _ => {
+                return Err(CensusError::Identifier(format!(
+                    "{} not a parent type of {}, cannot truncate geoid {}.",
+                    target,
+                    source,
+                    self.geoid_string()
+                )))
+            }
+        };
+        let scale = 10u64.pow(MAX_DIGITS - target_len);
+        let digits = (self.digits() / scale) * scale;
+        Ok(PackedGeoid::pack(
+            digits,
+            target_len,
+            0,
+            type_to_tag(target),
+        ))
+    }
+
+    /// bounds of the range `[lower, upper)` containing every PackedGeoid whose GEOID
+    /// string begins with this GEOID string. this includes, but is not limited to,
+    /// the hierarchical descendants of this GEOID.
+    pub(crate) fn prefix_range(&self) -> (PackedGeoid, PackedGeoid) {
+        let len = self.len();
+        let lower = PackedGeoid::pack(self.digits(), len, 0, 0);
+        let upper = PackedGeoid::pack(self.digits() + 10u64.pow(MAX_DIGITS - len), 0, 0, 0);
+        (lower, upper)
+    }
+
+    fn pack(digits: u64, len: u32, suffix: u8, tag: u8) -> PackedGeoid {
+        PackedGeoid(
+            (digits << DIGITS_SHIFT)
+                | ((len as u64) << LEN_SHIFT)
+                | ((suffix as u64) << SUFFIX_SHIFT)
+                | tag as u64,
+        )
+    }
+
+    /// GEOID digits, left-aligned to 15 digits.
+    fn digits(&self) -> u64 {
+        self.0 >> DIGITS_SHIFT
+    }
+
+    fn len(&self) -> u32 {
+        ((self.0 >> LEN_SHIFT) & ((1 << LEN_BITS) - 1)) as u32
+    }
+
+    fn suffix(&self) -> u8 {
+        ((self.0 >> SUFFIX_SHIFT) & ((1 << SUFFIX_BITS) - 1)) as u8
+    }
+
+    fn tag(&self) -> u8 {
+        (self.0 & ((1 << TAG_BITS) - 1)) as u8
+    }
+
+    /// splits the GEOID digits into the numeric components of its GeoidType.
+    fn components(&self) -> Vec<u64> {
+        let mut value = self.digits() / 10u64.pow(MAX_DIGITS - self.len());
+        let widths = component_widths(&self.geoid_type());
+        let mut result = vec![0; widths.len()];
+        for (idx, width) in widths.iter().enumerate().rev() {
+            let scale = 10u64.pow(*width);
+            result[idx] = value % scale;
+            value /= scale;
+        }
+        result
+    }
+}
+
+impl TryFrom<&Geoid> for PackedGeoid {
+    type Error = CensusError;
+
+    fn try_from(geoid: &Geoid) -> Result<Self, Self::Error> {
+        let (parts, suffix): (Vec<u64>, u8) = match geoid {
+            Geoid::State(s) => (vec![s.0], 0),
+            Geoid::County(s, c) => (vec![s.0, c.0], 0),
+            Geoid::CountySubdivision(s, c, cs) => (vec![s.0, c.0, cs.0], 0),
+            Geoid::Place(s, p) => (vec![s.0, p.0], 0),
+            Geoid::CensusTract(s, c, t) => (vec![s.0, c.0, t.0], 0),
+            Geoid::BlockGroup(s, c, t, bg) => (vec![s.0, c.0, t.0, bg.0], 0),
+            Geoid::Block(s, c, t, b) => {
+                let (block, suffix) = parse_block(b)?;
+                (vec![s.0, c.0, t.0, block], suffix)
+            }
+            Geoid::CongressionalDistrict(s, d) => (vec![s.0, d.0], 0),
+            Geoid::StateLegislativeDistrictUpperChamber(s, d) => {
+                (vec![s.0, parse_district(&d.0)?], 0)
+            }
+            Geoid::StateLegislativeDistrictLowerChamber(s, d) => {
+                (vec![s.0, parse_district(&d.0)?], 0)
+            }
+            Geoid::ZipCodeTabulationArea(z) => (vec![z.0], 0),
+        };
+        let geoid_type = geoid.geoid_type();
+        let widths = component_widths(&geoid_type);
+        let mut value: u64 = 0;
+        let mut len: u32 = 0;
+        for (part, width) in parts.iter().zip(widths) {
+            let scale = 10u64.pow(*width);
+            if *part >= scale {
+                return Err(CensusError::Identifier(format!(
+                    "cannot pack {} geoid, component {} exceeds {} digits",
+                    geoid_type, part, width
+                )));
+            }
+            value = value * scale + part;
+            len += width;
+        }
+        let digits = value * 10u64.pow(MAX_DIGITS - len);
+        Ok(PackedGeoid::pack(
+            digits,
+            len,
+            suffix,
+            type_to_tag(&geoid_type),
+        ))
+    }
+}
+
+impl TryFrom<Geoid> for PackedGeoid {
+    type Error = CensusError;
+
+    fn try_from(geoid: Geoid) -> Result<Self, Self::Error> {
+        PackedGeoid::try_from(&geoid)
+    }
+}
+
+impl From<PackedGeoid> for Geoid {
+    fn from(packed: PackedGeoid) -> Self {
+        let c = packed.components();
+        match packed.geoid_type() {
+            GeoidType::State => Geoid::State(fips::State(c[0])),
+            GeoidType::County => Geoid::County(fips::State(c[0]), fips::County(c[1])),
+            GeoidType::CountySubdivision => Geoid::CountySubdivision(
+                fips::State(c[0]),
+                fips::County(c[1]),
+                fips::CountySubdivision(c[2]),
+            ),
+            GeoidType::Place => Geoid::Place(fips::State(c[0]), fips::Place(c[1])),
+            GeoidType::CensusTract => Geoid::CensusTract(
+                fips::State(c[0]),
+                fips::County(c[1]),
+                fips::CensusTract(c[2]),
+            ),
+            GeoidType::BlockGroup => Geoid::BlockGroup(
+                fips::State(c[0]),
+                fips::County(c[1]),
+                fips::CensusTract(c[2]),
+                fips::BlockGroup(c[3]),
+            ),
+            GeoidType::Block => {
+                let block = match packed.suffix() {
+                    0 => format!("{:04}", c[3]),
+                    s => format!("{:04}{}", c[3], (b'A' + s - 1) as char),
+                };
+                Geoid::Block(
+                    fips::State(c[0]),
+                    fips::County(c[1]),
+                    fips::CensusTract(c[2]),
+                    fips::Block(block),
+                )
+            }
+            GeoidType::CongressionalDistrict => {
+                Geoid::CongressionalDistrict(fips::State(c[0]), fips::CongressionalDistrict(c[1]))
+            }
+            GeoidType::StateLegislativeDistrictUpperChamber => {
+                Geoid::StateLegislativeDistrictUpperChamber(
+                    fips::State(c[0]),
+                    fips::StateLegislativeDistrictUpperChamber(format!("{:03}", c[1])),
+                )
+            }
+            GeoidType::StateLegislativeDistrictLowerChamber => {
+                Geoid::StateLegislativeDistrictLowerChamber(
+                    fips::State(c[0]),
+                    fips::StateLegislativeDistrictLowerChamber(format!("{:03}", c[1])),
+                )
+            }
+            GeoidType::ZipCodeTabulationArea => {
+                Geoid::ZipCodeTabulationArea(fips::ZipCodeTabulationArea(c[0]))
+            }
+        }
+    }
+}
+
+impl HasGeoidString for PackedGeoid {
+    fn geoid_string(&self) -> String {
+        let len = self.len();
+        let value = self.digits() / 10u64.pow(MAX_DIGITS - len);
+        let digits = format!("{:0width$}", value, width = len as usize);
+        match self.suffix() {
+            0 => digits,
+            s => format!("{}{}", digits, (b'A' + s - 1) as char),
+        }
+    }
+}
+
+impl Display for PackedGeoid {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}={}", self.geoid_type(), self.geoid_string())
+    }
+}
+
+/// digit widths of each numeric component of a GEOID, by GeoidType.
+fn component_widths(geoid_type: &GeoidType) -> &'static [u32] {
+    match geoid_type {
+        GeoidType::State => &[2],
+        GeoidType::County => &[2, 3],
+        GeoidType::CountySubdivision => &[2, 3, 5],
+        GeoidType::Place => &[2, 5],
+        GeoidType::CensusTract => &[2, 3, 6],
+        GeoidType::BlockGroup => &[2, 3, 6, 1],
+        GeoidType::Block => &[2, 3, 6, 4],
+        GeoidType::CongressionalDistrict => &[2, 2],
+        GeoidType::StateLegislativeDistrictUpperChamber => &[2, 3],
+        GeoidType::StateLegislativeDistrictLowerChamber => &[2, 3],
+        GeoidType::ZipCodeTabulationArea => &[5],
+    }
+}
+
+fn type_to_tag(geoid_type: &GeoidType) -> u8 {
+    match geoid_type {
+        GeoidType::State => 0,
+        GeoidType::County => 1,
+        GeoidType::CountySubdivision => 2,
+        GeoidType::Place => 3,
+        GeoidType::CensusTract => 4,
+        GeoidType::BlockGroup => 5,
+        GeoidType::Block => 6,
+        GeoidType::CongressionalDistrict => 7,
+        GeoidType::StateLegislativeDistrictUpperChamber => 8,
+        GeoidType::StateLegislativeDistrictLowerChamber => 9,
+        GeoidType::ZipCodeTabulationArea => 10,
+    }
+}
+
+fn tag_to_type(tag: u8) -> GeoidType {
+    match tag {
+        0 => GeoidType::State,
+        1 => GeoidType::County,
+        2 => GeoidType::CountySubdivision,
+        3 => GeoidType::Place,
+        4 => GeoidType::CensusTract,
+        5 => GeoidType::BlockGroup,
+        6 => GeoidType::Block,
+        7 => GeoidType::CongressionalDistrict,
+        8 => GeoidType::StateLegislativeDistrictUpperChamber,
+        9 => GeoidType::StateLegislativeDistrictLowerChamber,
+        // tags are only assigned by type_to_tag
+        _ => GeoidType::ZipCodeTabulationArea,
+    }
+}
+
+/// reads a 4-digit block number with an optional suffix letter, such as
+/// the "1001A" block suffixes found in 2010-vintage data.
+fn parse_block(block: &fips::Block) -> Result<(u64, u8), CensusError> {
+    let b = block.0.as_bytes();
+    let err = || {
+        CensusError::Identifier(format!(
+            "block '{}' should be 4 digits with an optional suffix letter",
+            block.0
+        ))
+    };
+    let (digits, suffix) = match b.len() {
+        4 => (b, 0),
+        5 if b[4].is_ascii_uppercase() => (&b[0..4], b[4] - b'A' + 1),
+        _ => return Err(err()),
+    };
+    if !digits.iter().all(|d| d.is_ascii_digit()) {
+        return Err(err());
+    }
+    let value = digits
+        .iter()
+        .fold(0u64, |acc, d| acc * 10 + (d - b'0') as u64);
+    Ok((value, suffix))
+}
+
+/// reads a numeric state legislative district code. alphanumeric district codes
+/// cannot be packed.
+fn parse_district(district: &str) -> Result<u64, CensusError> {
+    match district.len() == 3 && district.bytes().all(|d| d.is_ascii_digit()) {
+        true => Ok(district.parse::<u64>().unwrap_or_default()),
+        false => Err(CensusError::Identifier(format!(
+            "cannot pack state legislative district '{}', only numeric district codes can be packed",
+            district
+        ))),
+    }
+}
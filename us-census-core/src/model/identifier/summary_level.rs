@@ -0,0 +1,113 @@
+use std::{fmt::Display, str::FromStr};
+
+use serde::{Deserialize, Serialize};
+
+use super::geoid_type::GeoidType;
+use crate::error::CensusError;
+
+/// a three-digit Census summary level (SUMLEV) code, which identifies the type of
+/// a geographic area in GEO_IDs, summary files and API metadata.
+///
+/// only the summary levels that correspond to a [`GeoidType`] can be converted into
+/// one, but any code can be represented.
+///
+/// # Examples
+///
+/// ```rust
+/// use us_census_core::model::identifier::{GeoidType, SummaryLevel};
+///
+/// let sumlev: SummaryLevel = "140".parse().unwrap();
+/// assert_eq!(sumlev, SummaryLevel::CENSUS_TRACT);
+/// assert_eq!(GeoidType::try_from(sumlev).unwrap(), GeoidType::CensusTract);
+/// assert_eq!(GeoidType::County.summary_level().to_string(), "050");
+/// assert_eq!(sumlev.description().unwrap(), "state-county-census_tract");
+/// ```
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct SummaryLevel(pub u16);
+
+impl SummaryLevel {
+    pub const STATE: SummaryLevel = SummaryLevel(40);
+    pub const COUNTY: SummaryLevel = SummaryLevel(50);
+    pub const COUNTY_SUBDIVISION: SummaryLevel = SummaryLevel(60);
+    /// census block, as published from 2020 onward
+    pub const BLOCK: SummaryLevel = SummaryLevel(100);
+    /// census block, as published in 2010-vintage data
+    pub const BLOCK_2010: SummaryLevel = SummaryLevel(101);
+    pub const CENSUS_TRACT: SummaryLevel = SummaryLevel(140);
+    pub const BLOCK_GROUP: SummaryLevel = SummaryLevel(150);
+    pub const PLACE: SummaryLevel = SummaryLevel(160);
+    pub const CONGRESSIONAL_DISTRICT: SummaryLevel = SummaryLevel(500);
+    pub const STATE_LEGISLATIVE_DISTRICT_UPPER_CHAMBER: SummaryLevel = SummaryLevel(610);
+    pub const STATE_LEGISLATIVE_DISTRICT_LOWER_CHAMBER: SummaryLevel = SummaryLevel(620);
+    pub const ZIP_CODE_TABULATION_AREA: SummaryLevel = SummaryLevel(860);
+
+    /// the GEOID component hierarchy of this summary level, such as
+    /// `state-county-census_tract` for summary level 140.
+    pub fn description(&self) -> Result<String, CensusError> {
+        let geoid_type = GeoidType::try_from(*self)?;
+        let components = geoid_type
+            .components()
+            .iter()
+            .map(|c| c.to_string())
+            .collect::<Vec<_>>();
+        Ok(components.join("-"))
+    }
+}
+
+impl Display for SummaryLevel {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:03}", self.0)
+    }
+}
+
+impl FromStr for SummaryLevel {
+    type Err = CensusError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let value = s.trim();
+        if value.len() != 3 {
+            return Err(CensusError::Identifier(format!(
+                "summary level should be a 3-digit code, found '{}'",
+                value
+            )));
+        }
+        let code = value.parse::<u16>().map_err(|e| {
+            CensusError::Identifier(format!("invalid summary level '{}': {}", value, e))
+        })?;
+        Ok(SummaryLevel(code))
+    }
+}
+
+impl From<GeoidType> for SummaryLevel {
+    fn from(value: GeoidType) -> Self {
+        value.summary_level()
+    }
+}
+
+impl TryFrom<SummaryLevel> for GeoidType {
+    type Error = CensusError;
+
+    fn try_from(value: SummaryLevel) -> Result<Self, Self::Error> {
+        match value {
+            SummaryLevel::STATE => Ok(GeoidType::State),
+            SummaryLevel::COUNTY => Ok(GeoidType::County),
+            SummaryLevel::COUNTY_SUBDIVISION => Ok(GeoidType::CountySubdivision),
+            SummaryLevel::BLOCK | SummaryLevel::BLOCK_2010 => Ok(GeoidType::Block),
+            SummaryLevel::CENSUS_TRACT => Ok(GeoidType::CensusTract),
+            SummaryLevel::BLOCK_GROUP => Ok(GeoidType::BlockGroup),
+            SummaryLevel::PLACE => Ok(GeoidType::Place),
+            SummaryLevel::CONGRESSIONAL_DISTRICT => Ok(GeoidType::CongressionalDistrict),
+            SummaryLevel::STATE_LEGISLATIVE_DISTRICT_UPPER_CHAMBER => {
+                Ok(GeoidType::StateLegislativeDistrictUpperChamber)
+            }
+            SummaryLevel::STATE_LEGISLATIVE_DISTRICT_LOWER_CHAMBER => {
+                Ok(GeoidType::StateLegislativeDistrictLowerChamber)
+            }
+            SummaryLevel::ZIP_CODE_TABULATION_AREA => Ok(GeoidType::ZipCodeTabulationArea),
+            _ => Err(CensusError::Identifier(format!(
+                "summary level {} has no matching geoid type",
+                value
+            ))),
+        }
+    }
+}
@@ -1,7 +1,8 @@
 use super::{LodesEdition, LodesJobType, OdPart, WorkplaceSegment, BASE_URL, LATEST_YEAR};
+use crate::error::CensusError;
 use crate::model::{
-    fips::state_code::StateCode,
-    identifier::{Geoid, GeoidType},
+    fips::{state_code::StateCode, CensusProduct},
+    identifier::{GeographyVintage, Geoid, GeoidType},
 };
 use serde::{Deserialize, Serialize};
 use std::fmt::Display;
@@ -89,8 +90,16 @@ impl LodesDataset {
     /// creates a URI to a LODES datasets based on the directory and file
     /// naming conventions described in the LODESTechDoc8.1.pdf file.
     /// see https://lehd.ces.census.gov/data/lodes/LODES8/LODESTechDoc8.1.pdf
-    pub fn create_uri(&self, geoid: &Geoid) -> Result<String, String> {
-        let sc: StateCode = geoid.to_state().try_into()?;
+    pub fn create_uri(&self, geoid: &Geoid) -> Result<String, CensusError> {
+        let sc: StateCode = geoid.to_state()?.try_into()?;
+        if !sc.coverage(&CensusProduct::Lodes).is_covered() {
+            return Err(CensusError::InvalidQuery(format!(
+                "{} does not publish data for {} (code {})",
+                CensusProduct::Lodes,
+                sc.to_full_name(),
+                sc.to_fips_string()
+            )));
+        }
         let state_code = sc.to_state_abbreviation();
         match self {
             LodesDataset::OD {
@@ -194,17 +203,30 @@ impl LodesDataset {
             } => edition.tiger_year(),
         }
     }
+
+    /// the decennial census geography of the blocks in this dataset, see
+    /// [`LodesEdition::geography_vintage`]. RAC datasets are not yet supported
+    /// and have no edition, which is an error.
+    pub fn geography_vintage(&self) -> Result<GeographyVintage, CensusError> {
+        match self {
+            LodesDataset::OD { edition, .. } => Ok(edition.geography_vintage()),
+            LodesDataset::RAC => Err(CensusError::InvalidQuery(String::from(
+                "RAC datasets are not supported and have no geography vintage",
+            ))),
+            LodesDataset::WAC { edition, .. } => Ok(edition.geography_vintage()),
+        }
+    }
 }
 
 /// as outlined in the tech doc, some states do not have WAC data for certain years
-fn validate_wac_availability(year: u64, state_code: &StateCode) -> Result<(), String> {
+fn validate_wac_availability(year: u64, state_code: &StateCode) -> Result<(), CensusError> {
     let err = || {
-        Err(format!(
+        Err(CensusError::InvalidQuery(format!(
             "WAC is not available in {} for {} (code {})",
             year,
             state_code.to_full_name(),
             state_code.to_fips_string()
-        ))
+        )))
     };
     match (year, state_code) {
         (2002, StateCode::Arkansas) => err(),
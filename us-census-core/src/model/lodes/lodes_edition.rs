@@ -1,4 +1,5 @@
 use super::LodesDataset;
+use crate::model::identifier::GeographyVintage;
 use crate::model::lodes as lodes_model;
 use clap::ValueEnum;
 use serde::{Deserialize, Serialize};
@@ -43,6 +44,16 @@ impl LodesEdition {
         }
     }
 
+    /// the decennial census geography of the blocks that this LODES edition is
+    /// enumerated with.
+    pub fn geography_vintage(&self) -> GeographyVintage {
+        match self {
+            LodesEdition::Lodes6 => GeographyVintage::Census2010,
+            LodesEdition::Lodes7 => GeographyVintage::Census2010,
+            LodesEdition::Lodes8 => GeographyVintage::Census2020,
+        }
+    }
+
     pub fn create_url(
         &self,
         state_code: &str,
@@ -1,3 +1,4 @@
+use crate::error::CensusError;
 use crate::model::identifier::{geoid::Geoid, geoid_type::GeoidType};
 use serde::{Deserialize, Serialize};
 
@@ -100,7 +101,7 @@ impl WacRow {
 }
 
 impl WacRow {
-    pub fn geoid(&self) -> Result<Geoid, String> {
+    pub fn geoid(&self) -> Result<Geoid, CensusError> {
         GeoidType::Block.geoid_from_str(&self.w_geocode)
     }
 }
@@ -1,3 +1,4 @@
+use crate::error::CensusError;
 use clap::ValueEnum;
 use serde::{Deserialize, Serialize};
 use std::fmt::Display;
@@ -50,7 +51,7 @@ pub enum WacSegment {
 }
 
 impl TryFrom<&str> for WacSegment {
-    type Error = String;
+    type Error = CensusError;
 
     fn try_from(value: &str) -> Result<Self, Self::Error> {
         match value {
@@ -95,7 +96,10 @@ impl TryFrom<&str> for WacSegment {
             "CD04" => Ok(Self::CD04),
             "CS01" => Ok(Self::CS01),
             "CS02" => Ok(Self::CS02),
-            _ => Err(format!("unknown WAC Segment {}", value)),
+            _ => Err(CensusError::InvalidQuery(format!(
+                "unknown WAC Segment {}",
+                value
+            ))),
         }
     }
 }
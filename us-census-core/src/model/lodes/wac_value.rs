@@ -1,4 +1,7 @@
 use super::WacSegment;
+use crate::error::CensusError;
+use crate::model::identifier::{GeographyVintage, HasGeographyVintage};
+use crate::ops::agg::CanAggregateAsNumber;
 use serde::{Deserialize, Serialize};
 use std::fmt::Display;
 
@@ -6,11 +9,28 @@ use std::fmt::Display;
 pub struct WacValue {
     pub segment: WacSegment,
     pub value: f64,
+    /// geography vintage of the census block this value was reported for, if known
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub vintage: Option<GeographyVintage>,
 }
 
 impl WacValue {
     pub fn new(segment: WacSegment, value: f64) -> WacValue {
-        WacValue { segment, value }
+        WacValue {
+            segment,
+            value,
+            vintage: None,
+        }
+    }
+
+    pub fn with_vintage(self, vintage: Option<GeographyVintage>) -> WacValue {
+        WacValue { vintage, ..self }
+    }
+}
+
+impl HasGeographyVintage for WacValue {
+    fn geography_vintage(&self) -> Option<GeographyVintage> {
+        self.vintage
     }
 }
 
@@ -25,3 +45,19 @@ impl Display for WacValue {
         )
     }
 }
+
+impl CanAggregateAsNumber for WacValue {
+    type Key = WacSegment;
+
+    fn aggregation_key(&self) -> WacSegment {
+        self.segment
+    }
+
+    fn aggregation_value(&self) -> Result<f64, CensusError> {
+        Ok(self.value)
+    }
+
+    fn from_aggregate(key: WacSegment, value: f64, vintage: Option<GeographyVintage>) -> WacValue {
+        WacValue::new(key, value).with_vintage(vintage)
+    }
+}
@@ -1,4 +1,9 @@
 pub mod acs;
+pub mod crosswalk;
+pub mod dataset;
+pub mod decennial;
+pub mod derived;
 pub mod fips;
 pub mod identifier;
 pub mod lodes;
+pub mod zone;
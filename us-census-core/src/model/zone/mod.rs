@@ -0,0 +1,3 @@
+pub mod zone_mapping;
+
+pub use zone_mapping::ZoneMapping;
@@ -0,0 +1,208 @@
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::Read;
+use std::path::Path;
+
+use itertools::Itertools;
+use serde::Deserialize;
+
+use crate::error::CensusError;
+use crate::model::identifier::{Geoid, GeoidType};
+
+/// tolerance for allocation fractions of a GEOID that sum to slightly more than 1.
+const FRACTION_TOLERANCE: f64 = 1e-6;
+
+/// assigns GEOIDs to user-defined zones, such as traffic analysis zones, neighborhoods or
+/// service areas, which are not part of the Census GEOID hierarchy. all GEOIDs of a mapping
+/// share a GeoidType. a GEOID may be split between zones with allocation fractions that sum
+/// to at most 1; an unsplit GEOID has a fraction of 1.
+///
+/// mappings are read from CSV files with `geoid` and `zone` columns and an optional
+/// `fraction` column.
+///
+/// # Examples
+///
+/// ```rust
+/// use us_census_core::model::identifier::{Geoid, GeoidType};
+/// use us_census_core::model::zone::ZoneMapping;
+///
+/// let contents = "\
+/// geoid,zone,fraction
+/// 08059012000,downtown,1.0
+/// 08059012100,downtown,0.25
+/// 08059012100,north,0.75
+/// ";
+/// let zones = ZoneMapping::read_csv(contents.as_bytes()).unwrap();
+/// assert_eq!(zones.geoid_type(), Some(GeoidType::CensusTract));
+/// assert_eq!(zones.zone_ids(), vec!["downtown", "north"]);
+///
+/// // block GEOIDs are allocated through the tract that contains them
+/// let block: Geoid = "080590121001000".parse().unwrap();
+/// let allocation = zones.allocate(&block).unwrap();
+/// assert_eq!(allocation, vec![(String::from("downtown"), 0.25), (String::from("north"), 0.75)]);
+///
+/// // allocation fractions of a GEOID may not exceed 1
+/// let contents = "\
+/// geoid,zone,fraction
+/// 08059012000,downtown,0.5
+/// 08059012000,north,0.6
+/// ";
+/// assert!(ZoneMapping::read_csv(contents.as_bytes()).is_err());
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct ZoneMapping {
+    geoid_type: Option<GeoidType>,
+    zones: HashMap<Geoid, Vec<(String, f64)>>,
+}
+
+#[derive(Deserialize)]
+struct ZoneMappingRow {
+    geoid: String,
+    zone: String,
+    fraction: Option<f64>,
+}
+
+impl ZoneMapping {
+    pub fn new() -> ZoneMapping {
+        ZoneMapping::default()
+    }
+
+    /// assigns a fraction of a GEOID to a zone. the mapping is left unchanged when the
+    /// assignment is invalid.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use us_census_core::model::identifier::Geoid;
+    /// use us_census_core::model::zone::ZoneMapping;
+    ///
+    /// let mut zones = ZoneMapping::new();
+    /// let tract: Geoid = "08059012000".parse().unwrap();
+    /// assert!(zones.insert(tract.clone(), "downtown", 1.5).is_err());
+    /// assert!(zones.is_empty());
+    /// assert_eq!(zones.geoid_type(), None);
+    /// assert!(zones.insert(tract.clone(), "downtown", 0.5).is_ok());
+    /// assert!(zones.insert(tract.clone(), "north", 0.6).is_err());
+    /// assert_eq!(zones.allocate(&tract).unwrap(), vec![(String::from("downtown"), 0.5)]);
+    /// ```
+    pub fn insert(&mut self, geoid: Geoid, zone: &str, fraction: f64) -> Result<(), CensusError> {
+        let geoid_type = geoid.geoid_type();
+        if let Some(expected) = self.geoid_type.filter(|t| *t != geoid_type) {
+            return Err(CensusError::InvalidQuery(format!(
+                "zone mapping GEOIDs must share a type, found {} geoid {} in a mapping of {} geoids",
+                geoid_type, geoid, expected
+            )));
+        }
+        if !(fraction > 0.0 && fraction <= 1.0) {
+            return Err(CensusError::InvalidQuery(format!(
+                "allocation fraction of geoid {} to zone {} must be in (0, 1], found {}",
+                geoid, zone, fraction
+            )));
+        }
+        let allocated: f64 = match self.zones.get(&geoid) {
+            Some(allocation) => allocation.iter().map(|(_, f)| f).sum(),
+            None => 0.0,
+        };
+        let total = allocated + fraction;
+        if total > 1.0 + FRACTION_TOLERANCE {
+            return Err(CensusError::InvalidQuery(format!(
+                "allocation fractions of geoid {} sum to {}, which is greater than 1",
+                geoid, total
+            )));
+        }
+        self.geoid_type = Some(geoid_type);
+        let allocation = self.zones.entry(geoid).or_default();
+        match allocation.iter_mut().find(|(z, _)| z == zone) {
+            Some((_, f)) => *f += fraction,
+            None => allocation.push((String::from(zone), fraction)),
+        }
+        Ok(())
+    }
+
+    /// reads a zone mapping from a CSV file.
+    pub fn from_csv_path(path: &Path) -> Result<ZoneMapping, CensusError> {
+        let file = File::open(path).map_err(|e| {
+            CensusError::InvalidQuery(format!(
+                "failure opening zone mapping {}: {}",
+                path.display(),
+                e
+            ))
+        })?;
+        ZoneMapping::read_csv(file)
+    }
+
+    /// reads a zone mapping from CSV with `geoid` and `zone` columns and an optional
+    /// `fraction` column.
+    pub fn read_csv<R: Read>(reader: R) -> Result<ZoneMapping, CensusError> {
+        let mut csv_reader = csv::ReaderBuilder::new()
+            .trim(csv::Trim::All)
+            .from_reader(reader);
+        let mut mapping = ZoneMapping::new();
+        for row in csv_reader.deserialize() {
+            let row: ZoneMappingRow = row.map_err(|e| {
+                CensusError::InvalidQuery(format!("failure reading zone mapping row: {}", e))
+            })?;
+            let geoid: Geoid = row.geoid.parse()?;
+            mapping.insert(geoid, &row.zone, row.fraction.unwrap_or(1.0))?;
+        }
+        Ok(mapping)
+    }
+
+    /// the GeoidType of the GEOIDs in this mapping, or None for an empty mapping.
+    pub fn geoid_type(&self) -> Option<GeoidType> {
+        self.geoid_type
+    }
+
+    /// number of GEOIDs assigned to zones.
+    pub fn len(&self) -> usize {
+        self.zones.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.zones.is_empty()
+    }
+
+    pub fn geoids(&self) -> impl Iterator<Item = &Geoid> {
+        self.zones.keys()
+    }
+
+    /// the distinct zone ids of this mapping, sorted.
+    pub fn zone_ids(&self) -> Vec<&str> {
+        self.zones
+            .values()
+            .flatten()
+            .map(|(zone, _)| zone.as_str())
+            .unique()
+            .sorted()
+            .collect_vec()
+    }
+
+    /// the zones and allocation fractions of a GEOID of the mapping GeoidType.
+    pub fn zones(&self, geoid: &Geoid) -> Option<&[(String, f64)]> {
+        self.zones.get(geoid).map(|z| z.as_slice())
+    }
+
+    /// the zones and allocation fractions of a GEOID at or below the mapping GeoidType,
+    /// found by truncating the GEOID to the mapping GeoidType. GEOIDs outside of all
+    /// zones have no allocation.
+    pub fn allocate(&self, geoid: &Geoid) -> Result<Vec<(String, f64)>, CensusError> {
+        let Some(geoid_type) = self.geoid_type else {
+            return Ok(vec![]);
+        };
+        let key = geoid.truncate_geoid_to_type(&geoid_type)?;
+        Ok(self.zones(&key).map(|z| z.to_vec()).unwrap_or_default())
+    }
+}
+
+impl TryFrom<HashMap<Geoid, String>> for ZoneMapping {
+    type Error = CensusError;
+
+    /// builds a mapping where each GEOID is assigned to a single zone.
+    fn try_from(value: HashMap<Geoid, String>) -> Result<Self, Self::Error> {
+        let mut mapping = ZoneMapping::new();
+        for (geoid, zone) in value.into_iter() {
+            mapping.insert(geoid, &zone, 1.0)?;
+        }
+        Ok(mapping)
+    }
+}
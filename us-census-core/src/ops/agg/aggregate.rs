@@ -0,0 +1,335 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+
+use indexmap::IndexMap;
+use itertools::Itertools;
+use rayon::prelude::*;
+
+use super::{CanAggregateAsNumber, NumericAggregation};
+use crate::error::CensusError;
+use crate::model::identifier::{geography_vintage::dataset_vintage, Geoid, GeoidType, PackedGeoid};
+use crate::model::zone::ZoneMapping;
+
+/// the (value, weight) pairs of each key in a group, in order of first appearance.
+pub type KeyedValues<K> = IndexMap<K, Vec<(f64, f64)>>;
+type PartitionedRows<G, K> = (Vec<Vec<(G, KeyedValues<K>)>>, Vec<CensusError>);
+
+/// groups rows by their GEOID truncated to the target GeoidType and reduces the values
+/// of each key in a group with the aggregation function. rows are grouped by hash, so
+/// they do not need to be sorted, and both the grouping and the reduction run on the
+/// rayon thread pool. the result is sorted by GEOID.
+///
+/// rows must share a single geography vintage, which is carried over to the aggregated
+/// values. weighted aggregation functions weight each row's values by that row's value
+/// for the weight key, which must be provided.
+///
+/// # Examples
+///
+/// ```rust
+/// use us_census_core::model::acs::AcsValue;
+/// use us_census_core::model::identifier::{fips, Geoid, GeoidType};
+/// use us_census_core::ops::agg::{self, NumericAggregation};
+/// use serde_json::json;
+///
+/// let value = |name: &str, x: f64| AcsValue::new(String::from(name), json![x]);
+/// let tract = |state: u64, county: u64, tract: u64| {
+///     Geoid::CensusTract(fips::State(state), fips::County(county), fips::CensusTract(tract))
+/// };
+/// // rows of two counties, out of order
+/// let rows = vec![
+///     (tract(8, 59, 1), vec![value("B01001_001E", 10.0), value("B11001_001E", 4.0)]),
+///     (tract(8, 31, 1), vec![value("B01001_001E", 20.0), value("B11001_001E", 8.0)]),
+///     (tract(8, 59, 2), vec![value("B01001_001E", 30.0), value("B11001_001E", 12.0)]),
+/// ];
+/// let result = agg::aggregate(&rows, GeoidType::County, NumericAggregation::Sum, None).unwrap();
+/// assert_eq!(result.len(), 2);
+/// assert_eq!(result[0].0, Geoid::County(fips::State(8), fips::County(31)));
+/// assert_eq!(result[1].0, Geoid::County(fips::State(8), fips::County(59)));
+/// assert_eq!(result[1].1[0].name, "B01001_001E");
+/// assert_eq!(result[1].1[0].as_number(), Some(40.0));
+/// assert_eq!(result[1].1[1].as_number(), Some(16.0));
+///
+/// let weight = String::from("B11001_001E");
+/// let agg = NumericAggregation::WeightedMean;
+/// let result = agg::aggregate(&rows, GeoidType::County, agg, Some(&weight)).unwrap();
+/// assert_eq!(result[1].1[0].as_number(), Some(25.0));
+/// assert!(agg::aggregate(&rows, GeoidType::County, agg, None).is_err());
+/// ```
+pub fn aggregate<T>(
+    rows: &[(Geoid, Vec<T>)],
+    target: GeoidType,
+    agg: NumericAggregation,
+    weight: Option<&T::Key>,
+) -> Result<Vec<(Geoid, Vec<T>)>, CensusError>
+where
+    T: CanAggregateAsNumber + Send + Sync,
+{
+    let weight = validate_weight(agg, weight)?;
+    aggregate_with(
+        rows,
+        target,
+        |_| weight.cloned(),
+        |keyed| Ok(reduce_keyed(agg, keyed)),
+    )
+}
+
+/// [`aggregate`] with a custom weight and reduction for each key, for datasets whose
+/// values are not all reduced the same way, such as ACS medians and margins of error.
+/// `weight` returns the key whose value in a row weights that row's values for a key,
+/// if any. `reduce` reduces the keyed (value, weight) pairs of a group to the aggregated
+/// value of each key; keys missing from its result are dropped from the output.
+pub fn aggregate_with<T, W, F>(
+    rows: &[(Geoid, Vec<T>)],
+    target: GeoidType,
+    weight: W,
+    reduce: F,
+) -> Result<Vec<(Geoid, Vec<T>)>, CensusError>
+where
+    T: CanAggregateAsNumber + Send + Sync,
+    W: Fn(&T::Key) -> Option<T::Key> + Sync,
+    F: Fn(KeyedValues<T::Key>) -> Result<Vec<(T::Key, f64)>, CensusError> + Sync,
+{
+    // the packed representation avoids cloning block GEOID strings when hashing millions of rows.
+    let group = |geoid: &Geoid| {
+        let trunc_geoid = PackedGeoid::try_from(geoid)?.truncate_to_type(&target)?;
+        Ok(vec![(trunc_geoid, 1.0)])
+    };
+    let output = aggregate_by(rows, group, weight, reduce)?;
+    let result = output
+        .into_iter()
+        .map(|(geoid, values)| (Geoid::from(geoid), values))
+        .collect_vec();
+    Ok(result)
+}
+
+/// aggregates rows to the zones of a [`ZoneMapping`] with the aggregation function. rows
+/// are allocated to zones through their GEOID truncated to the mapping GeoidType, and rows
+/// outside of all zones are dropped. split GEOIDs contribute to each of their zones by their
+/// allocation fraction, see [`NumericAggregation::aggregate_allocated`]. the result is
+/// sorted by zone id.
+///
+/// # Examples
+///
+/// ```rust
+/// use us_census_core::model::identifier::Geoid;
+/// use us_census_core::model::lodes::{WacSegment, WacValue};
+/// use us_census_core::model::zone::ZoneMapping;
+/// use us_census_core::ops::agg::{self, NumericAggregation};
+///
+/// let contents = "\
+/// geoid,zone,fraction
+/// 08059012000,downtown,1.0
+/// 08059012100,downtown,0.25
+/// 08059012100,north,0.75
+/// ";
+/// let zones = ZoneMapping::read_csv(contents.as_bytes()).unwrap();
+/// let block = |geoid: &str| geoid.parse::<Geoid>().unwrap();
+/// let rows = vec![
+///     (block("080590120001000"), vec![WacValue::new(WacSegment::C000, 10.0)]),
+///     (block("080590120001001"), vec![WacValue::new(WacSegment::C000, 20.0)]),
+///     (block("080590121001000"), vec![WacValue::new(WacSegment::C000, 40.0)]),
+///     (block("080590122001000"), vec![WacValue::new(WacSegment::C000, 80.0)]),
+/// ];
+/// let result = agg::aggregate_zones(&rows, &zones, NumericAggregation::Sum, None).unwrap();
+/// assert_eq!(result.len(), 2);
+/// assert_eq!(result[0].0, "downtown");
+/// assert_eq!(result[0].1[0].value, 40.0);
+/// assert_eq!(result[1].0, "north");
+/// assert_eq!(result[1].1[0].value, 30.0);
+/// ```
+pub fn aggregate_zones<T>(
+    rows: &[(Geoid, Vec<T>)],
+    zones: &ZoneMapping,
+    agg: NumericAggregation,
+    weight: Option<&T::Key>,
+) -> Result<Vec<(String, Vec<T>)>, CensusError>
+where
+    T: CanAggregateAsNumber + Send + Sync,
+{
+    let weight = validate_weight(agg, weight)?;
+    aggregate_by(
+        rows,
+        |geoid| zones.allocate(geoid),
+        |_| weight.cloned(),
+        |keyed| Ok(reduce_keyed(agg, keyed)),
+    )
+}
+
+/// the grouping step shared by all aggregations. `group` assigns each row to groups with
+/// allocation fractions, which scale the weights of the row's (value, weight) pairs in each
+/// group. rows without a group are dropped. the result is sorted by group.
+pub fn aggregate_by<T, K, G, W, F>(
+    rows: &[(Geoid, Vec<T>)],
+    group: G,
+    weight: W,
+    reduce: F,
+) -> Result<Vec<(K, Vec<T>)>, CensusError>
+where
+    T: CanAggregateAsNumber + Send + Sync,
+    K: Clone + Eq + Hash + Ord + Send + Sync,
+    G: Fn(&Geoid) -> Result<Vec<(K, f64)>, CensusError> + Sync,
+    W: Fn(&T::Key) -> Option<T::Key> + Sync,
+    F: Fn(KeyedValues<T::Key>) -> Result<Vec<(T::Key, f64)>, CensusError> + Sync,
+{
+    // GEOIDs from different decennial geographies cannot be grouped together
+    let vintage = dataset_vintage(rows)?;
+
+    // assign each row to its groups and collect the numeric values of each row with their weights.
+    let (row_oks, row_errs): PartitionedRows<K, T::Key> = rows
+        .par_iter()
+        .map(|(geoid, values)| {
+            let groups = group(geoid)?;
+            let mut row_weights: Vec<(T::Key, f64)> = vec![];
+            let mut keyed: KeyedValues<T::Key> = IndexMap::new();
+            for value in values.iter() {
+                let key = value.aggregation_key();
+                let value_weight = match weight(&key) {
+                    Some(weight_key) => row_weight(geoid, values, weight_key, &mut row_weights)?,
+                    None => 1.0,
+                };
+                let pair = (value.aggregation_value()?, value_weight);
+                keyed.entry(key).or_default().push(pair);
+            }
+            Ok(allocate_keyed(groups, keyed))
+        })
+        .collect::<Vec<_>>()
+        .into_iter()
+        .partition_result();
+
+    if !row_errs.is_empty() {
+        return Err(aggregation_error(row_errs));
+    }
+
+    // hash-group by group key. rayon reduces adjacent partial results in order,
+    // so keys keep their order of first appearance.
+    let grouped = row_oks
+        .into_par_iter()
+        .flatten()
+        .fold(
+            HashMap::new,
+            |mut acc: HashMap<K, KeyedValues<T::Key>>, (group_key, keyed)| {
+                merge_keyed(acc.entry(group_key).or_default(), keyed);
+                acc
+            },
+        )
+        .reduce(HashMap::new, |mut acc, other| {
+            for (group_key, keyed) in other.into_iter() {
+                merge_keyed(acc.entry(group_key).or_default(), keyed);
+            }
+            acc
+        });
+
+    // reduce each key of each group
+    let (mut output, group_errs): (Vec<_>, Vec<_>) = grouped
+        .into_par_iter()
+        .map(|(group_key, keyed)| {
+            let values = reduce(keyed)?
+                .into_iter()
+                .map(|(key, value)| T::from_aggregate(key, value, vintage))
+                .collect_vec();
+            Ok((group_key, values))
+        })
+        .collect::<Vec<_>>()
+        .into_iter()
+        .partition_result();
+
+    if !group_errs.is_empty() {
+        return Err(aggregation_error(group_errs));
+    }
+    output.par_sort_by(|(a, _), (b, _)| a.cmp(b));
+    Ok(output)
+}
+
+/// the weight key of a weighted aggregation, which must be provided.
+fn validate_weight<K>(
+    agg: NumericAggregation,
+    weight: Option<&K>,
+) -> Result<Option<&K>, CensusError> {
+    match weight {
+        Some(key) if agg.is_weighted() => Ok(Some(key)),
+        None if agg.is_weighted() => Err(CensusError::InvalidQuery(format!(
+            "{} aggregation requires a weight",
+            agg
+        ))),
+        _ => Ok(None),
+    }
+}
+
+/// reduces each key of a group with the aggregation function.
+fn reduce_keyed<K>(agg: NumericAggregation, keyed: KeyedValues<K>) -> Vec<(K, f64)> {
+    keyed
+        .into_iter()
+        .map(|(key, pairs)| (key, agg.aggregate_allocated(&mut pairs.into_iter())))
+        .collect_vec()
+}
+
+/// copies the keyed values of a row to each of its groups, scaling weights by the
+/// allocation fraction of the group. the common single, whole group is moved as is.
+fn allocate_keyed<K, V: Clone + Eq + Hash>(
+    groups: Vec<(K, f64)>,
+    keyed: KeyedValues<V>,
+) -> Vec<(K, KeyedValues<V>)> {
+    if let [(_, fraction)] = groups[..] {
+        if fraction == 1.0 {
+            let group_key = groups.into_iter().map(|(k, _)| k);
+            return group_key.zip(std::iter::once(keyed)).collect_vec();
+        }
+    }
+    groups
+        .into_iter()
+        .map(|(group_key, fraction)| {
+            let scaled = keyed
+                .iter()
+                .map(|(key, pairs)| {
+                    let pairs = pairs.iter().map(|(v, w)| (*v, w * fraction)).collect_vec();
+                    (key.clone(), pairs)
+                })
+                .collect();
+            (group_key, scaled)
+        })
+        .collect_vec()
+}
+
+/// the row's value for a weight key, memoized in the weights found so far in the row.
+fn row_weight<T: CanAggregateAsNumber>(
+    geoid: &Geoid,
+    values: &[T],
+    weight: T::Key,
+    found: &mut Vec<(T::Key, f64)>,
+) -> Result<f64, CensusError> {
+    if let Some((_, w)) = found.iter().find(|(k, _)| *k == weight) {
+        return Ok(*w);
+    }
+    let w = values
+        .iter()
+        .find(|v| v.aggregation_key() == weight)
+        .ok_or_else(|| {
+            CensusError::Aggregation(format!(
+                "geoid {} has no value for weight {}",
+                geoid, weight
+            ))
+        })?
+        .aggregation_value()?;
+    found.push((weight, w));
+    Ok(w)
+}
+
+fn aggregation_error(errors: Vec<CensusError>) -> CensusError {
+    let msg = errors
+        .into_iter()
+        .map(|e| e.to_string())
+        .unique()
+        .take(5)
+        .join("\n");
+    CensusError::Aggregation(format!(
+        "errors during aggregation. first 5 unique errors: \n{}",
+        msg
+    ))
+}
+
+/// appends the values of each key to the values of that key in the accumulator.
+fn merge_keyed<K: Eq + Hash>(acc: &mut KeyedValues<K>, other: KeyedValues<K>) {
+    for (key, pairs) in other.into_iter() {
+        acc.entry(key).or_default().extend(pairs);
+    }
+}
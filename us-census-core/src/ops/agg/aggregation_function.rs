@@ -1,29 +1,173 @@
 use std::fmt::Display;
 
 use clap::ValueEnum;
+use itertools::Itertools;
 use serde::{Deserialize, Serialize};
 
-#[derive(Serialize, Deserialize, Clone, ValueEnum, Default)]
+/// functions for reducing a group of numeric values to a single value. all functions
+/// return 0.0 for an empty group, except Min and Max, which return NaN.
+///
+/// # Examples
+///
+/// ```rust
+/// use us_census_core::ops::agg::NumericAggregation;
+///
+/// let values = vec![4.0, 1.0, 3.0, 2.0];
+/// let agg = |f: NumericAggregation| f.aggregate(&mut values.clone().into_iter());
+/// assert_eq!(agg(NumericAggregation::Sum), 10.0);
+/// assert_eq!(agg(NumericAggregation::Mean), 2.5);
+/// assert_eq!(agg(NumericAggregation::Median), 2.5);
+/// assert_eq!(agg(NumericAggregation::Min), 1.0);
+/// assert_eq!(agg(NumericAggregation::Max), 4.0);
+/// assert_eq!(agg(NumericAggregation::Count), 4.0);
+/// assert!((agg(NumericAggregation::StdDev) - 1.2909944).abs() < 1e-6);
+///
+/// // median household income of two areas, weighted by population
+/// let weighted = vec![(50000.0, 1000.0), (80000.0, 3000.0)];
+/// let result = NumericAggregation::WeightedMean.aggregate_weighted(&mut weighted.into_iter());
+/// assert_eq!(result, 72500.0);
+/// ```
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, ValueEnum, Default)]
 #[serde(rename_all = "snake_case")]
 pub enum NumericAggregation {
     #[default]
     Sum,
     Mean,
+    /// mean of the values weighted by a weight per value
+    WeightedMean,
+    Median,
+    Min,
+    Max,
+    /// number of values in the group
+    Count,
+    /// sample standard deviation, or 0.0 for groups of fewer than two values
+    StdDev,
 }
 
 impl NumericAggregation {
+    /// true if this function uses the weight of each value, see [`NumericAggregation::aggregate_weighted`].
+    pub fn is_weighted(&self) -> bool {
+        matches!(self, NumericAggregation::WeightedMean)
+    }
+
+    /// aggregates unweighted values. a WeightedMean of unweighted values is their Mean.
     pub fn aggregate(&self, values: &mut dyn Iterator<Item = f64>) -> f64 {
+        self.aggregate_weighted(&mut values.map(|v| (v, 1.0)))
+    }
+
+    /// aggregates (value, weight) pairs. weights are ignored by all functions other than
+    /// WeightedMean. a WeightedMean with a total weight of zero is 0.0.
+    pub fn aggregate_weighted(&self, values: &mut dyn Iterator<Item = (f64, f64)>) -> f64 {
         use NumericAggregation as Fn;
         match self {
-            Fn::Sum => values.fold(0.0, |acc, v| acc + v),
+            Fn::Sum => values.fold(0.0, |acc, (v, _)| acc + v),
             Fn::Mean => {
-                let (acc, n) = values.fold((0.0, 0.0), |(acc, n), v| (acc + v, n + 1.0));
+                let (acc, n) = values.fold((0.0, 0.0), |(acc, n), (v, _)| (acc + v, n + 1.0));
                 if n == 0.0 {
                     0.0
                 } else {
                     acc / n
                 }
             }
+            Fn::WeightedMean => {
+                let (acc, total) =
+                    values.fold((0.0, 0.0), |(acc, total), (v, w)| (acc + v * w, total + w));
+                if total == 0.0 {
+                    0.0
+                } else {
+                    acc / total
+                }
+            }
+            Fn::Median => {
+                let sorted = values
+                    .map(|(v, _)| v)
+                    .sorted_by(f64::total_cmp)
+                    .collect_vec();
+                let n = sorted.len();
+                match n {
+                    0 => 0.0,
+                    _ if n % 2 == 1 => sorted[n / 2],
+                    _ => (sorted[n / 2 - 1] + sorted[n / 2]) / 2.0,
+                }
+            }
+            Fn::Min => values.map(|(v, _)| v).fold(f64::NAN, f64::min),
+            Fn::Max => values.map(|(v, _)| v).fold(f64::NAN, f64::max),
+            Fn::Count => values.count() as f64,
+            Fn::StdDev => {
+                let xs = values.map(|(v, _)| v).collect_vec();
+                let n = xs.len() as f64;
+                if n < 2.0 {
+                    return 0.0;
+                }
+                let mean = xs.iter().sum::<f64>() / n;
+                let sum_sq = xs.iter().map(|x| (x - mean).powi(2)).sum::<f64>();
+                (sum_sq / (n - 1.0)).sqrt()
+            }
+        }
+    }
+
+    /// aggregates (value, weight) pairs whose weights are scaled by allocation fractions,
+    /// as when values are apportioned to the zones that split a GEOID. Sum sums the values
+    /// scaled by their weights and Mean is the mean weighted by allocation, so a value half
+    /// allocated to a group counts half. all other functions aggregate as in
+    /// [`NumericAggregation::aggregate_weighted`]. pairs with weights of 1.0 (unallocated,
+    /// unweighted values) aggregate as in [`NumericAggregation::aggregate_weighted`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use us_census_core::ops::agg::NumericAggregation;
+    ///
+    /// let allocated = vec![(100.0, 1.0), (40.0, 0.5)];
+    /// let sum = NumericAggregation::Sum.aggregate_allocated(&mut allocated.clone().into_iter());
+    /// assert_eq!(sum, 120.0);
+    /// let mean = NumericAggregation::Mean.aggregate_allocated(&mut allocated.into_iter());
+    /// assert_eq!(mean, 80.0);
+    /// ```
+    pub fn aggregate_allocated(&self, values: &mut dyn Iterator<Item = (f64, f64)>) -> f64 {
+        match self {
+            NumericAggregation::Sum => values.map(|(v, w)| v * w).sum(),
+            NumericAggregation::Mean => NumericAggregation::WeightedMean.aggregate_weighted(values),
+            _ => self.aggregate_weighted(values),
+        }
+    }
+
+    /// the margin of error of the aggregate of independent (margin of error, weight) pairs,
+    /// by root-sum-of-squares. as in [`NumericAggregation::aggregate_allocated`], the weights
+    /// of Sum and Mean margins of error are allocation fractions. margins of error cannot be
+    /// propagated through Median, Min, Max, Count or StdDev aggregations, which return None.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use us_census_core::ops::agg::NumericAggregation;
+    ///
+    /// let moes = vec![(30.0, 1.0), (40.0, 1.0)];
+    /// let sum = NumericAggregation::Sum.aggregate_margin_of_error(&mut moes.clone().into_iter());
+    /// assert_eq!(sum, Some(50.0));
+    /// let mean = NumericAggregation::Mean.aggregate_margin_of_error(&mut moes.clone().into_iter());
+    /// assert_eq!(mean, Some(25.0));
+    /// let median = NumericAggregation::Median.aggregate_margin_of_error(&mut moes.into_iter());
+    /// assert_eq!(median, None);
+    /// ```
+    pub fn aggregate_margin_of_error(
+        &self,
+        moes: &mut dyn Iterator<Item = (f64, f64)>,
+    ) -> Option<f64> {
+        use NumericAggregation as Fn;
+        match self {
+            Fn::Sum => Some(moes.map(|(m, w)| (w * m).powi(2)).sum::<f64>().sqrt()),
+            Fn::Mean | Fn::WeightedMean => {
+                let (sum_sq, total) = moes.fold((0.0, 0.0), |(acc, total), (m, w)| {
+                    (acc + (w * m).powi(2), total + w)
+                });
+                if total == 0.0 {
+                    Some(0.0)
+                } else {
+                    Some(sum_sq.sqrt() / total)
+                }
+            }
+            Fn::Median | Fn::Min | Fn::Max | Fn::Count | Fn::StdDev => None,
         }
     }
 }
@@ -33,6 +177,12 @@ impl Display for NumericAggregation {
         match self {
             NumericAggregation::Sum => write!(f, "sum"),
             NumericAggregation::Mean => write!(f, "mean"),
+            NumericAggregation::WeightedMean => write!(f, "weighted_mean"),
+            NumericAggregation::Median => write!(f, "median"),
+            NumericAggregation::Min => write!(f, "min"),
+            NumericAggregation::Max => write!(f, "max"),
+            NumericAggregation::Count => write!(f, "count"),
+            NumericAggregation::StdDev => write!(f, "std_dev"),
         }
     }
 }
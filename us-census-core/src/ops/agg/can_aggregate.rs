@@ -1,3 +1,21 @@
-pub trait CanAggregateAsNumber {
-    fn aggregation_value(&self) -> Result<f64, String>;
+use std::fmt::Display;
+use std::hash::Hash;
+
+use crate::error::CensusError;
+use crate::model::identifier::{GeographyVintage, HasGeographyVintage};
+
+/// implemented by keyed numeric dataset values, such as a WAC segment count or an ACS
+/// variable estimate, so that values with the same key can be grouped and reduced, for
+/// example when aggregating to a coarser GeoidType or reallocating between geography vintages.
+pub trait CanAggregateAsNumber: HasGeographyVintage + Sized {
+    type Key: Clone + Eq + Hash + Display + Send + Sync;
+
+    /// identifies the measure this value belongs to, such as a WAC segment or an ACS variable.
+    fn aggregation_key(&self) -> Self::Key;
+
+    /// the numeric value to aggregate.
+    fn aggregation_value(&self) -> Result<f64, CensusError>;
+
+    /// builds a value from the aggregated result for a key.
+    fn from_aggregate(key: Self::Key, value: f64, vintage: Option<GeographyVintage>) -> Self;
 }
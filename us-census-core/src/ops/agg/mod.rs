@@ -1,3 +1,7 @@
+pub mod aggregate;
 pub mod aggregation_function;
+pub mod can_aggregate;
 
+pub use aggregate::{aggregate, aggregate_by, aggregate_with, aggregate_zones, KeyedValues};
 pub use aggregation_function::NumericAggregation;
+pub use can_aggregate::CanAggregateAsNumber;
@@ -0,0 +1,117 @@
+use std::collections::{HashMap, HashSet};
+use std::fmt::Display;
+
+use rayon::prelude::*;
+
+use crate::error::CensusError;
+use crate::model::derived::{DerivedValue, DerivedVariable};
+use crate::ops::agg::CanAggregateAsNumber;
+
+/// evaluates derived variables over the values of each row, returning the derived values
+/// of each row in the order of the rows. rows are keyed by GEOID or by any other id, such
+/// as a zone id. variables are looked up by the name of each
+/// value's aggregation key, such as an ACS variable or a WAC segment, and derived variables
+/// may reference the derived variables defined before them. derived values carry the
+/// geography vintage of their row.
+///
+/// each row is evaluated on its own: a derived value is NaN in rows where a referenced
+/// variable is missing or not a number, such as a jammed ACS estimate. a variable that is
+/// missing from every row is an error.
+///
+/// # Examples
+///
+/// ```rust
+/// use us_census_core::model::derived::DerivedVariable;
+/// use us_census_core::model::identifier::{fips, Geoid};
+/// use us_census_core::model::lodes::{WacSegment, WacValue};
+/// use us_census_core::ops::derive;
+///
+/// let derived = DerivedVariable::parse_list(
+///     "retail_share = CNS07 / C000; retail_pct = retail_share * 100"
+/// ).unwrap();
+/// let rows = vec![
+///     (
+///         Geoid::County(fips::State(08), fips::County(59)),
+///         vec![WacValue::new(WacSegment::C000, 200.0), WacValue::new(WacSegment::CNS07, 50.0)],
+///     ),
+///     (
+///         Geoid::County(fips::State(08), fips::County(31)),
+///         vec![WacValue::new(WacSegment::C000, 100.0)],
+///     ),
+/// ];
+/// let result = derive::derive(&rows, &derived).unwrap();
+/// assert_eq!(result[0].1[0].name, "retail_share");
+/// assert_eq!(result[0].1[0].value, 0.25);
+/// assert_eq!(result[0].1[1].value, 25.0);
+///
+/// // rows missing a referenced variable have NaN derived values
+/// assert!(result[1].1[0].value.is_nan());
+/// assert!(result[1].1[1].value.is_nan());
+///
+/// // referenced variables must be present in some row
+/// let derived = DerivedVariable::parse_list("ag_share = CNS01 / C000").unwrap();
+/// assert!(derive::derive(&rows, &derived).is_err());
+/// ```
+pub fn derive<K, T>(
+    rows: &[(K, Vec<T>)],
+    derived: &[DerivedVariable],
+) -> Result<Vec<(K, Vec<DerivedValue>)>, CensusError>
+where
+    K: Clone + Display + Send + Sync,
+    T: CanAggregateAsNumber + Sync,
+{
+    let present = rows
+        .iter()
+        .flat_map(|(_, values)| values.iter().map(|v| v.aggregation_key().to_string()))
+        .collect::<HashSet<_>>();
+    let mut errors = vec![];
+    for (idx, variable) in derived.iter().enumerate() {
+        for name in variable.expression.variables() {
+            let is_derived = derived[..idx].iter().any(|d| d.name == name);
+            if !is_derived && !present.contains(name) {
+                errors.push(format!(
+                    "derived variable {} references {}, which is missing from all rows",
+                    variable.name, name
+                ));
+            }
+        }
+    }
+    if !rows.is_empty() && !errors.is_empty() {
+        return Err(CensusError::InvalidQuery(format!(
+            "errors evaluating derived variables: \n{}",
+            errors.join("\n")
+        )));
+    }
+
+    let result = rows
+        .par_iter()
+        .map(|(row_key, values)| (row_key.clone(), derive_row(values, derived)))
+        .collect();
+    Ok(result)
+}
+
+/// evaluates derived variables over the values of a single row. derived values are NaN
+/// when a referenced variable is missing from the row or is not a number.
+pub fn derive_row<T: CanAggregateAsNumber>(
+    values: &[T],
+    derived: &[DerivedVariable],
+) -> Vec<DerivedValue> {
+    let vintage = values.iter().find_map(|v| v.geography_vintage());
+    let mut lookup = values
+        .iter()
+        .map(|v| {
+            let value = v.aggregation_value().unwrap_or(f64::NAN);
+            (v.aggregation_key().to_string(), value)
+        })
+        .collect::<HashMap<_, _>>();
+    let mut result: Vec<DerivedValue> = Vec::with_capacity(derived.len());
+    for variable in derived.iter() {
+        let value = variable
+            .expression
+            .evaluate(&mut |name| Ok(lookup.get(name).copied().unwrap_or(f64::NAN)))
+            .unwrap_or(f64::NAN);
+        lookup.insert(variable.name.clone(), value);
+        result.push(DerivedValue::new(variable.name.clone(), value).with_vintage(vintage));
+    }
+    result
+}
@@ -1 +1,2 @@
 pub mod agg;
+pub mod derive;
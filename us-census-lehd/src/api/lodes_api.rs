@@ -8,24 +8,30 @@ use reqwest::Client;
 use std::sync::{Arc, Mutex};
 use us_census_core::model::lodes::{wac_row::WacRow, wac_value::WacValue, WacSegment};
 use us_census_core::{
-    model::identifier::{Geoid, GeoidType},
+    error::CensusError,
+    model::identifier::{GeographyVintage, Geoid, GeoidType},
     ops::agg::aggregation_function::NumericAggregation,
 };
 
 /// runs a set of LODES queries. each required LODES file is collected in
 /// memory and deserialized into rows of Geoids with WacValues for each
-/// requested WacSegment. the entire dataset is aggregated to the requested
-/// output GeoidType, which should be
+/// requested WacSegment, tagged with the geography vintage of the LODES edition
+/// if provided. if an aggregation is provided, the entire dataset is aggregated
+/// to its output GeoidType with its aggregation function, weighted by the
+/// optional weight segment.
 pub async fn run_wac(
     client: &Client,
     queries: &[String],
     wac_segments: &[WacSegment],
-    agg: Option<(GeoidType, NumericAggregation)>,
-) -> Result<Vec<(Geoid, Vec<WacValue>)>, String> {
+    vintage: Option<GeographyVintage>,
+    agg: Option<(GeoidType, NumericAggregation, Option<WacSegment>)>,
+) -> Result<Vec<(Geoid, Vec<WacValue>)>, CensusError> {
     let pb_builder = kdam::BarBuilder::default()
         .total(queries.len())
         .desc("LODES downloads");
-    let pb = Arc::new(Mutex::new(pb_builder.build()?));
+    let pb = Arc::new(Mutex::new(pb_builder.build().map_err(|e| {
+        CensusError::Internal(format!("error building progress bar: {}", e))
+    })?));
 
     let responses = queries.iter().map(|url| {
         let client = &client;
@@ -36,32 +42,46 @@ pub async fn run_wac(
                 .get(url)
                 .send()
                 .await
-                .map_err(|e| format!("failure sending LODES HTTP request: {}", e))?;
-            let gzip_bytes = res
-                .bytes()
-                .await
-                .map_err(|e| format!("failure reading response body: {}", e))?;
+                .map_err(|e| CensusError::Http {
+                    url: url.clone(),
+                    source: Box::new(e),
+                })?;
+            if !res.status().is_success() {
+                return Err(CensusError::HttpStatus {
+                    url: url.clone(),
+                    status: res.status().as_u16(),
+                });
+            }
+            let gzip_bytes = res.bytes().await.map_err(|e| CensusError::Http {
+                url: url.clone(),
+                source: Box::new(e),
+            })?;
             let mut reader = ReaderBuilder::new().from_reader(GzDecoder::new(&gzip_bytes[..]));
             let mut result = vec![];
             for r in reader.deserialize() {
-                let row: WacRow =
-                    r.map_err(|e| format!("failure reading LODES response row: {}", e))?;
+                let row: WacRow = r.map_err(|e| {
+                    CensusError::ResponseSchema(format!(
+                        "failure reading LODES response row: {}",
+                        e
+                    ))
+                })?;
                 let geoid = row.geoid()?;
                 let mut row_result = vec![];
                 for segment in wac_segments.iter() {
-                    row_result.push(WacValue::new(*segment, row.get(segment)));
+                    row_result
+                        .push(WacValue::new(*segment, row.get(segment)).with_vintage(vintage));
                 }
                 result.push((geoid, row_result));
             }
 
             // update progress bar
-            let mut pb_update = pb
-                .lock()
-                .map_err(|e| format!("failure aquiring progress bar mutex lock: {}", e))?;
+            let mut pb_update = pb.lock().map_err(|e| {
+                CensusError::Internal(format!("failure aquiring progress bar mutex lock: {}", e))
+            })?;
             pb_update
                 .update(1)
-                .map_err(|e| format!("failure on pb update: {}", e))?;
-            pb_update.set_description(url.split('/').last().unwrap_or_default());
+                .map_err(|e| CensusError::Internal(format!("failure on pb update: {}", e)))?;
+            pb_update.set_description(url.split('/').next_back().unwrap_or_default());
 
             Ok(result)
         }
@@ -69,14 +89,14 @@ pub async fn run_wac(
     let response_rows = future::join_all(responses)
         .await
         .into_iter()
-        .collect::<Result<Vec<_>, String>>()?
+        .collect::<Result<Vec<_>, CensusError>>()?
         .into_iter()
         .flatten()
         .collect_vec();
     eprintln!(); // progress bar terminated
     let aggregated_rows = match agg {
-        Some((output_geoid_type, agg)) => {
-            lodes_agg::aggregate_lodes_wac(&response_rows, output_geoid_type, agg)?
+        Some((output_geoid_type, agg, weight)) => {
+            lodes_agg::aggregate_lodes_wac(&response_rows, output_geoid_type, agg, weight)?
         }
         None => response_rows.to_vec(),
     };
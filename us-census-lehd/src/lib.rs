@@ -1,2 +1,4 @@
 pub mod api;
 pub mod ops;
+
+pub use us_census_core::error::CensusError;
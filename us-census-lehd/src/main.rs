@@ -1,8 +1,10 @@
 use clap::Parser;
+use us_census_core::model::fips::StateScope;
 use us_census_core::model::identifier::{Geoid, GeoidType};
 use us_census_core::model::lodes::{self as lodes_model, LodesDataset, WacSegment};
 use us_census_core::ops::agg::aggregation_function::NumericAggregation;
 use us_census_lehd::api::lodes_api;
+use us_census_lehd::CensusError;
 
 // todo: top level here should be a LEHD command
 #[derive(Parser)]
@@ -11,9 +13,13 @@ pub struct LodesCliArgs {
     /// LODES year in [2002, 2016]
     #[arg(short, long)]
     year: u64,
-    /// states to download by FIPS code. omit to download all states.
+    /// comma-delimited states to download by FIPS code, abbreviation or name, or Census
+    /// divisions or regions. omit to download all states.
     #[arg(short, long)]
     geoids: Option<String>,
+    /// when geoids are omitted, the set of states and territories to download.
+    #[arg(long)]
+    state_scope: Option<StateScope>,
     /// LODES are created in editions, see website for details. LODES8 by default if not provided.
     #[arg(long)]
     edition: Option<lodes_model::LodesEdition>,
@@ -29,18 +35,18 @@ pub struct LodesCliArgs {
     /// function to aggregate result value
     #[arg(long)]
     agg_fn: Option<NumericAggregation>,
+    /// WAC segment used to weight values when agg_fn is a weighted function
+    #[arg(long)]
+    agg_weight: Option<WacSegment>,
     // todo: use clap.Parser's subcommand structures to flip between WAC, OD, and RAC data since they
     // are structurally different
 }
 
 impl LodesCliArgs {
-    pub fn get_state_geoids(&self) -> Result<Vec<Geoid>, String> {
+    pub fn get_state_geoids(&self) -> Result<Vec<Geoid>, CensusError> {
         match &self.geoids {
-            Some(s) => s
-                .split(',')
-                .map(Geoid::try_from)
-                .collect::<Result<Vec<_>, _>>(),
-            None => Ok(Geoid::all_states()),
+            Some(s) => Geoid::parse_list(s),
+            None => Ok(Geoid::all_states(&self.state_scope.unwrap_or_default())),
         }
     }
 }
@@ -76,7 +82,8 @@ async fn main() {
         &client,
         &queries,
         &wac_segments,
-        Some((output_geoid_type, agg_fn)),
+        Some(dataset.geography_vintage().unwrap()),
+        Some((output_geoid_type, agg_fn, args.agg_weight)),
     )
     .await
     .unwrap();
@@ -1,14 +1,16 @@
-use itertools::Itertools;
-use kdam::BarExt;
-use std::collections::HashMap;
 use us_census_core::model::lodes::{wac_value::WacValue, WacSegment};
 use us_census_core::{
-    model::identifier::{Geoid, GeoidType},
-    ops::agg::aggregation_function::NumericAggregation,
+    error::CensusError,
+    model::identifier::{geography_vintage::dataset_vintage, Geoid, GeoidType},
+    model::zone::ZoneMapping,
+    ops::agg::{self, aggregation_function::NumericAggregation},
 };
 
 /// groups rows to the target Geoid hierarchy level and then
 /// applies the provided aggregation function to the grouped WacValues.
+/// rows must share a single geography vintage, which is carried over to the
+/// aggregated values. weighted aggregation functions weight each row's values
+/// by that row's value for the weight segment. see [`agg::aggregate`].
 ///
 /// # Example
 ///
@@ -31,7 +33,7 @@ use us_census_core::{
 /// ];
 /// let target = GeoidType::State;
 /// let agg = NumericAggregation::Sum;
-/// let result = lodes_agg::aggregate_lodes_wac(&rows, target, agg).unwrap();
+/// let result = lodes_agg::aggregate_lodes_wac(&rows, target, agg, None).unwrap();
 /// let expected_cnt = 106497.0 + 3858.0;
 /// let expected = vec![
 ///   (
@@ -46,94 +48,59 @@ use us_census_core::{
 ///     assert_eq!(v_a.value, v_b.value);
 ///   }
 /// }
+///
+/// // rows tagged with different geography vintages cannot be aggregated together
+/// use us_census_core::model::identifier::GeographyVintage;
+/// let mixed = vec![
+///   (
+///     Geoid::County(fips::State(08), fips::County(213)),
+///     vec![WacValue::new(WacSegment::C000, 1.0).with_vintage(Some(GeographyVintage::Census2010))]
+///   ),
+///   (
+///     Geoid::County(fips::State(08), fips::County(215)),
+///     vec![WacValue::new(WacSegment::C000, 1.0).with_vintage(Some(GeographyVintage::Census2020))]
+///   )
+/// ];
+/// assert!(lodes_agg::aggregate_lodes_wac(&mixed, target, NumericAggregation::Sum, None).is_err());
+///
+/// // mean earnings-segment share of jobs, weighted by total jobs (C000)
+/// let rows = vec![
+///   (
+///     Geoid::County(fips::State(08), fips::County(213)),
+///     vec![WacValue::new(WacSegment::C000, 300.0), WacValue::new(WacSegment::CE01, 0.5)]
+///   ),
+///   (
+///     Geoid::County(fips::State(08), fips::County(215)),
+///     vec![WacValue::new(WacSegment::C000, 100.0), WacValue::new(WacSegment::CE01, 0.1)]
+///   )
+/// ];
+/// let agg = NumericAggregation::WeightedMean;
+/// let result = lodes_agg::aggregate_lodes_wac(&rows, target, agg, Some(WacSegment::C000)).unwrap();
+/// let ce01 = result[0].1.iter().find(|v| v.segment == WacSegment::CE01).unwrap();
+/// assert!((ce01.value - 0.4).abs() < 1e-9);
+/// assert!(lodes_agg::aggregate_lodes_wac(&rows, target, agg, None).is_err());
 /// ```
 pub fn aggregate_lodes_wac(
     rows: &[(Geoid, Vec<WacValue>)],
     target: GeoidType,
     agg: NumericAggregation,
-) -> Result<Vec<(Geoid, Vec<WacValue>)>, String> {
+    weight: Option<WacSegment>,
+) -> Result<Vec<(Geoid, Vec<WacValue>)>, CensusError> {
     if target == GeoidType::Block {
         // LODES data is stored at the block level, this is a no-op
+        dataset_vintage(rows)?;
         return Ok(rows.to_vec());
     }
+    agg::aggregate(rows, target, agg, weight.as_ref())
+}
 
-    // aggregate Geoids
-    let (geoid_oks, geoid_errs): (Vec<(Geoid, &Vec<WacValue>)>, Vec<String>) = rows
-        .iter()
-        .map(|(geoid, values)| {
-            let trunc_geoid = geoid.truncate_geoid_to_type(&target)?;
-            Ok((trunc_geoid, values))
-        })
-        .partition_result();
-
-    if !geoid_errs.is_empty() {
-        let msg = geoid_errs.into_iter().unique().take(5).join("\n");
-        return Err(format!(
-            "errors during aggregation. first 5 unique errors: \n{}",
-            msg
-        ));
-    }
-
-    // nested groupby operation collected into a hashmap
-    let mut grouped: HashMap<Geoid, HashMap<WacSegment, Vec<f64>>> = HashMap::new();
-    let n_geoid_oks = geoid_oks.len();
-    let group_iter_desc = format!("LODES - geoids to {}", target);
-    let pb1_builder = kdam::BarBuilder::default()
-        .total(n_geoid_oks)
-        .desc(group_iter_desc);
-    let mut pb1 = pb1_builder
-        .build()
-        .map_err(|e| format!("error building progress bar: {}", e))?;
-
-    for (geoid, values) in geoid_oks.into_iter() {
-        for wac in values.iter() {
-            match grouped.get_mut(&geoid) {
-                Some(inner) => match inner.get_mut(&wac.segment) {
-                    Some(inner_vec) => {
-                        inner_vec.push(wac.value);
-                    }
-                    None => {
-                        let _ = inner.insert(wac.segment, vec![wac.value]);
-                    }
-                },
-                None => {
-                    let mut map = HashMap::new();
-                    map.insert(wac.segment, vec![wac.value]);
-                    grouped.insert(geoid.clone(), map);
-                }
-            }
-        }
-        pb1.update(1)
-            .map_err(|e| format!("error updating progress bar: {}", e))?;
-    }
-    eprintln!();
-
-    // flattended into vector collection
-    let n_grouped = grouped.len();
-    let reduce_desc = format!("LODES - aggregate by {}", agg);
-    let pb2_builder = kdam::BarBuilder::default()
-        .total(n_grouped)
-        .desc(reduce_desc);
-    let mut pb2 = pb2_builder
-        .build()
-        .map_err(|e| format!("error building progress bar: {}", e))?;
-    let output: Result<Vec<(Geoid, Vec<WacValue>)>, String> = grouped
-        .into_iter()
-        .map(|(geoid, map)| {
-            let values = map
-                .into_iter()
-                .map(|(seg, values)| {
-                    // let mut mut_values = values;
-                    let value = agg.aggregate(&mut values.into_iter());
-                    WacValue::new(seg, value)
-                })
-                .collect_vec();
-            pb2.update(1)
-                .map_err(|e| format!("error updating progress bar: {}", e))?;
-            Ok((geoid, values))
-        })
-        .collect::<Result<Vec<_>, _>>();
-    eprintln!(); // end progress bar
-
-    output
+/// aggregates LODES WAC rows to the zones of a [`ZoneMapping`], see [`agg::aggregate_zones`].
+/// the jobs of blocks split between zones are allocated to each zone by fraction.
+pub fn aggregate_lodes_wac_to_zones(
+    rows: &[(Geoid, Vec<WacValue>)],
+    zones: &ZoneMapping,
+    agg: NumericAggregation,
+    weight: Option<WacSegment>,
+) -> Result<Vec<(String, Vec<WacValue>)>, CensusError> {
+    agg::aggregate_zones(rows, zones, agg, weight.as_ref())
 }
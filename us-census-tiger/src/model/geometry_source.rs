@@ -0,0 +1,25 @@
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
+use std::fmt::Display;
+
+/// selects how geometries are found for data aggregated to a coarser GeoidType.
+#[derive(Serialize, Deserialize, ValueEnum, Default, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum GeometrySource {
+    /// download the TIGER/Lines layer of the aggregated GeoidType
+    #[default]
+    Tiger,
+    /// download the TIGER/Lines layer of the source GEOIDs and dissolve all source
+    /// geometries contained in each aggregated GEOID into its geometry, including those
+    /// of source GEOIDs without data
+    Dissolve,
+}
+
+impl Display for GeometrySource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GeometrySource::Tiger => write!(f, "tiger"),
+            GeometrySource::Dissolve => write!(f, "dissolve"),
+        }
+    }
+}
@@ -1,3 +1,4 @@
+pub mod geometry_source;
 pub mod tiger_resource;
 pub mod tiger_uri_builder;
 
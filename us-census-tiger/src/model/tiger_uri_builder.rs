@@ -1,7 +1,8 @@
 use super::tiger_resource::TigerResource;
 use std::{collections::HashSet, fmt::Display};
+use us_census_core::error::CensusError;
 use us_census_core::model::identifier::{
-    geoid::Geoid, geoid_type::GeoidType, has_geoid_string::HasGeoidString,
+    geoid::Geoid, geoid_type::GeoidType, has_geoid_string::HasGeoidString, GeographyVintage,
 };
 
 /// builds URIs for TIGER/Lines Shapefile resources.
@@ -41,19 +42,31 @@ impl Display for TigerUriBuilder {
 impl TigerUriBuilder {
     pub const TIGER_BASE_URL: &'static str = "https://www2.census.gov/geo/tiger";
 
-    pub fn new(year: u64) -> Result<TigerUriBuilder, String> {
+    pub fn new(year: u64) -> Result<TigerUriBuilder, CensusError> {
         match year {
             2010 => Ok(TigerUriBuilder::Tiger2010),
             y if 2010 < y && y < 2020 => Ok(TigerUriBuilder::Tiger2010Format { year }),
             y if 2020 <= y => Ok(TigerUriBuilder::Tiger2020Format { year }),
-            _ => Err(format!("unsupported TIGER year {}", year)),
+            _ => Err(CensusError::InvalidQuery(format!(
+                "unsupported TIGER year {}",
+                year
+            ))),
+        }
+    }
+
+    /// the decennial census geography of the TIGER/Lines files built by this builder.
+    pub fn geography_vintage(&self) -> GeographyVintage {
+        match self {
+            TigerUriBuilder::Tiger2010 => GeographyVintage::Census2010,
+            TigerUriBuilder::Tiger2010Format { year } => GeographyVintage::from_year(*year),
+            TigerUriBuilder::Tiger2020Format { year } => GeographyVintage::from_year(*year),
         }
     }
 
     /// batch operation that only returns the unique set of TigerUris required to cover
     /// the provided set of Geoids. this is the public API since we should only be
     /// downloading each file once. for details on implementation, see `[create_uri]`.
-    pub fn create_uris(&self, geoids: &[&Geoid]) -> Result<Vec<TigerResource>, String> {
+    pub fn create_uris(&self, geoids: &[&Geoid]) -> Result<Vec<TigerResource>, CensusError> {
         let mut unique_uris: HashSet<TigerResource> = HashSet::new();
         for geoid in geoids {
             let uri = self.create_uri(geoid)?;
@@ -96,11 +109,10 @@ impl TigerUriBuilder {
     ///     expected_uri,
     ///     GeoidType::CountySubdivision,
     ///     Some(expected_file_scope),
-    ///     String::from("GEOID"),
     /// );
     /// assert_eq!(uri, expected);
     /// ```
-    pub fn create_uri(&self, geoid: &Geoid) -> Result<TigerResource, String> {
+    pub fn create_uri(&self, geoid: &Geoid) -> Result<TigerResource, CensusError> {
         let suffix: String = match (self, geoid) {
             //// ~~~~ 2010 ~~~~ ////
             // 2010 has two versions, one in 2000 format, one in 2010 format
@@ -136,6 +148,18 @@ impl TigerUriBuilder {
                 state.geoid_string(),
                 county.geoid_string()
             ),
+            (TigerUriBuilder::Tiger2010, Geoid::CongressionalDistrict(state, _)) => {
+                format!("CD/111/tl_2010_{}_cd111.zip", state.geoid_string())
+            }
+            (TigerUriBuilder::Tiger2010, Geoid::StateLegislativeDistrictUpperChamber(state, _)) => {
+                format!("SLDU/2010/tl_2010_{}_sldu10.zip", state.geoid_string())
+            }
+            (TigerUriBuilder::Tiger2010, Geoid::StateLegislativeDistrictLowerChamber(state, _)) => {
+                format!("SLDL/2010/tl_2010_{}_sldl10.zip", state.geoid_string())
+            }
+            (TigerUriBuilder::Tiger2010, Geoid::ZipCodeTabulationArea(_)) => {
+                String::from("ZCTA5/2010/tl_2010_us_zcta510.zip")
+            }
             //// ~~~~ 2011-2019 ~~~~ ////
             (TigerUriBuilder::Tiger2010Format { year }, Geoid::State(_)) => {
                 format!("STATE/tl_{}_us_state.zip", year,)
@@ -163,6 +187,21 @@ impl TigerUriBuilder {
                     state.geoid_string()
                 )
             }
+            (TigerUriBuilder::Tiger2010Format { year }, Geoid::CongressionalDistrict(_, _)) => {
+                let congress = congress_number(*year)?;
+                format!("CD/tl_{}_us_cd{}.zip", year, congress)
+            }
+            (
+                TigerUriBuilder::Tiger2010Format { year },
+                Geoid::StateLegislativeDistrictUpperChamber(state, _),
+            ) => format!("SLDU/tl_{}_{}_sldu.zip", year, state.geoid_string()),
+            (
+                TigerUriBuilder::Tiger2010Format { year },
+                Geoid::StateLegislativeDistrictLowerChamber(state, _),
+            ) => format!("SLDL/tl_{}_{}_sldl.zip", year, state.geoid_string()),
+            (TigerUriBuilder::Tiger2010Format { year }, Geoid::ZipCodeTabulationArea(_)) => {
+                format!("ZCTA5/tl_{}_us_zcta510.zip", year)
+            }
             //// ~~~~ 2020-2029 ~~~~ ////
             (TigerUriBuilder::Tiger2020Format { year }, Geoid::State(_)) => {
                 format!("STATE/tl_{}_us_state.zip", year,)
@@ -189,6 +228,25 @@ impl TigerUriBuilder {
                     state.geoid_string()
                 )
             }
+            (TigerUriBuilder::Tiger2020Format { year }, Geoid::CongressionalDistrict(state, _)) => {
+                let congress = congress_number(*year)?;
+                if *year < 2022 {
+                    format!("CD/tl_{}_us_cd{}.zip", year, congress)
+                } else {
+                    format!("CD/tl_{}_{}_cd{}.zip", year, state.geoid_string(), congress)
+                }
+            }
+            (
+                TigerUriBuilder::Tiger2020Format { year },
+                Geoid::StateLegislativeDistrictUpperChamber(state, _),
+            ) => format!("SLDU/tl_{}_{}_sldu.zip", year, state.geoid_string()),
+            (
+                TigerUriBuilder::Tiger2020Format { year },
+                Geoid::StateLegislativeDistrictLowerChamber(state, _),
+            ) => format!("SLDL/tl_{}_{}_sldl.zip", year, state.geoid_string()),
+            (TigerUriBuilder::Tiger2020Format { year }, Geoid::ZipCodeTabulationArea(_)) => {
+                format!("ZCTA520/tl_{}_us_zcta520.zip", year)
+            }
         };
 
         let file_scope = match (self, geoid) {
@@ -201,6 +259,16 @@ impl TigerUriBuilder {
             (TigerUriBuilder::Tiger2010, Geoid::CensusTract(_, _, _)) => Some(GeoidType::County),
             (TigerUriBuilder::Tiger2010, Geoid::BlockGroup(_, _, _, _)) => Some(GeoidType::County),
             (TigerUriBuilder::Tiger2010, Geoid::Block(_, _, _, _)) => Some(GeoidType::County),
+            (TigerUriBuilder::Tiger2010, Geoid::CongressionalDistrict(_, _)) => {
+                Some(GeoidType::State)
+            }
+            (TigerUriBuilder::Tiger2010, Geoid::StateLegislativeDistrictUpperChamber(_, _)) => {
+                Some(GeoidType::State)
+            }
+            (TigerUriBuilder::Tiger2010, Geoid::StateLegislativeDistrictLowerChamber(_, _)) => {
+                Some(GeoidType::State)
+            }
+            (TigerUriBuilder::Tiger2010, Geoid::ZipCodeTabulationArea(_)) => None,
             (TigerUriBuilder::Tiger2010Format { year: _ }, Geoid::State(_)) => None,
             (TigerUriBuilder::Tiger2010Format { year: _ }, Geoid::County(_, _)) => None,
             (TigerUriBuilder::Tiger2010Format { year: _ }, Geoid::CountySubdivision(_, _, _)) => {
@@ -218,6 +286,18 @@ impl TigerUriBuilder {
             (TigerUriBuilder::Tiger2010Format { year: _ }, Geoid::Block(_, _, _, _)) => {
                 Some(GeoidType::State)
             }
+            (TigerUriBuilder::Tiger2010Format { year: _ }, Geoid::CongressionalDistrict(_, _)) => {
+                None
+            }
+            (
+                TigerUriBuilder::Tiger2010Format { year: _ },
+                Geoid::StateLegislativeDistrictUpperChamber(_, _),
+            ) => Some(GeoidType::State),
+            (
+                TigerUriBuilder::Tiger2010Format { year: _ },
+                Geoid::StateLegislativeDistrictLowerChamber(_, _),
+            ) => Some(GeoidType::State),
+            (TigerUriBuilder::Tiger2010Format { year: _ }, Geoid::ZipCodeTabulationArea(_)) => None,
             (TigerUriBuilder::Tiger2020Format { year: _ }, Geoid::State(_)) => None,
             (TigerUriBuilder::Tiger2020Format { year: _ }, Geoid::County(_, _)) => None,
             (TigerUriBuilder::Tiger2020Format { year: _ }, Geoid::CountySubdivision(_, _, _)) => {
@@ -235,6 +315,22 @@ impl TigerUriBuilder {
             (TigerUriBuilder::Tiger2020Format { year: _ }, Geoid::Block(_, _, _, _)) => {
                 Some(GeoidType::State)
             }
+            (TigerUriBuilder::Tiger2020Format { year }, Geoid::CongressionalDistrict(_, _)) => {
+                if *year < 2022 {
+                    None
+                } else {
+                    Some(GeoidType::State)
+                }
+            }
+            (
+                TigerUriBuilder::Tiger2020Format { year: _ },
+                Geoid::StateLegislativeDistrictUpperChamber(_, _),
+            ) => Some(GeoidType::State),
+            (
+                TigerUriBuilder::Tiger2020Format { year: _ },
+                Geoid::StateLegislativeDistrictLowerChamber(_, _),
+            ) => Some(GeoidType::State),
+            (TigerUriBuilder::Tiger2020Format { year: _ }, Geoid::ZipCodeTabulationArea(_)) => None,
         };
 
         let prefix = self.base_url();
@@ -273,3 +369,23 @@ impl TigerUriBuilder {
         format!("{}/TIGER{}", TigerUriBuilder::TIGER_BASE_URL, year)
     }
 }
+
+/// the congressional district files in TIGER/Lines are named by the session of
+/// Congress whose districts they describe (e.g. `cd116`). the session published
+/// does not follow directly from the TIGER year, since districts are only redrawn
+/// after redistricting, so this is a lookup of the published file names.
+fn congress_number(year: u64) -> Result<u64, CensusError> {
+    match year {
+        2011 | 2012 => Ok(112),
+        2013 => Ok(113),
+        2014 | 2015 => Ok(114),
+        2016 | 2017 => Ok(115),
+        2018..=2022 => Ok(116),
+        2023 => Ok(118),
+        2024 => Ok(119),
+        _ => Err(CensusError::InvalidQuery(format!(
+            "congressional district TIGER/Lines files not supported for year {}",
+            year
+        ))),
+    }
+}
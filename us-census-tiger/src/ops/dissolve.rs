@@ -0,0 +1,175 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+
+use geo::{BooleanOps, Geometry, MultiPolygon};
+use itertools::Itertools;
+use rayon::prelude::*;
+use us_census_core::error::CensusError;
+use us_census_core::model::identifier::{Geoid, GeoidType, PackedGeoid};
+use us_census_core::model::zone::ZoneMapping;
+
+/// dissolves geometries into parent geometries at a coarser GeoidType, by unioning the
+/// polygons of all GEOIDs contained in each parent. this builds parent geometries from
+/// geometries that are already loaded, such as blocks, instead of downloading the
+/// TIGER/Lines layer of the parent GeoidType.
+///
+/// # Examples
+///
+/// ```rust
+/// use geo::{polygon, Area, Geometry};
+/// use us_census_core::model::identifier::{Geoid, GeoidType, PackedGeoid};
+/// use us_census_tiger::ops::dissolve;
+///
+/// let square = |x: f64| -> Geometry {
+///     polygon![(x: x, y: 0.0), (x: x + 1.0, y: 0.0), (x: x + 1.0, y: 1.0), (x: x, y: 1.0)].into()
+/// };
+/// let geometries = vec![
+///     ("080590120001000".parse::<Geoid>().unwrap(), square(0.0)),
+///     ("080590120001001".parse::<Geoid>().unwrap(), square(1.0)),
+///     ("080590121001000".parse::<Geoid>().unwrap(), square(5.0)),
+/// ];
+/// let (dissolved, errors) = dissolve::dissolve_to_type(geometries, GeoidType::CensusTract);
+/// assert!(errors.is_empty());
+/// assert_eq!(dissolved.len(), 2);
+/// assert_eq!(dissolved[0].0, "08059012000".parse::<Geoid>().unwrap());
+/// assert!((dissolved[0].1.unsigned_area() - 2.0).abs() < 1e-9);
+/// assert!((dissolved[1].1.unsigned_area() - 1.0).abs() < 1e-9);
+/// ```
+pub fn dissolve_to_type(
+    geometries: Vec<(Geoid, Geometry)>,
+    geoid_type: GeoidType,
+) -> (Vec<(Geoid, Geometry)>, Vec<CensusError>) {
+    let (dissolved, errors) = dissolve_by(geometries, |geoid| {
+        let parent = PackedGeoid::try_from(geoid)?.truncate_to_type(&geoid_type)?;
+        Ok(vec![parent])
+    });
+    let dissolved = dissolved
+        .into_iter()
+        .map(|(parent, geometry)| (Geoid::from(parent), geometry))
+        .collect_vec();
+    (dissolved, errors)
+}
+
+/// dissolves the geometries of the GEOIDs of a zone mapping into one geometry per zone,
+/// by unioning the polygons of the GEOIDs assigned to each zone. a GEOID split between
+/// zones contributes its whole polygon to each of its zones. geometries of GEOIDs outside
+/// of the mapping are ignored, and mapped GEOIDs without a geometry are reported as errors.
+///
+/// # Examples
+///
+/// ```rust
+/// use geo::{polygon, Area, Geometry};
+/// use us_census_core::model::identifier::Geoid;
+/// use us_census_core::model::zone::ZoneMapping;
+/// use us_census_tiger::ops::dissolve;
+///
+/// let contents = "\
+/// geoid,zone
+/// 08059012000,downtown
+/// 08059012100,downtown
+/// ";
+/// let zones = ZoneMapping::read_csv(contents.as_bytes()).unwrap();
+/// let square = |x: f64| -> Geometry {
+///     polygon![(x: x, y: 0.0), (x: x + 1.0, y: 0.0), (x: x + 1.0, y: 1.0), (x: x, y: 1.0)].into()
+/// };
+/// let geometries = vec![
+///     ("08059012000".parse::<Geoid>().unwrap(), square(0.0)),
+///     ("08059012100".parse::<Geoid>().unwrap(), square(1.0)),
+///     ("08059012200".parse::<Geoid>().unwrap(), square(2.0)),
+/// ];
+/// let (dissolved, errors) = dissolve::dissolve_zones(geometries, &zones);
+/// assert!(errors.is_empty());
+/// assert_eq!(dissolved.len(), 1);
+/// assert_eq!(dissolved[0].0, "downtown");
+/// assert!((dissolved[0].1.unsigned_area() - 2.0).abs() < 1e-9);
+/// ```
+pub fn dissolve_zones(
+    geometries: Vec<(Geoid, Geometry)>,
+    zones: &ZoneMapping,
+) -> (Vec<(String, Geometry)>, Vec<CensusError>) {
+    let found = geometries
+        .iter()
+        .filter(|(geoid, _)| zones.zones(geoid).is_some())
+        .count();
+    let (dissolved, mut errors) = dissolve_by(geometries, |geoid| {
+        let allocation = zones.zones(geoid).unwrap_or_default();
+        Ok(allocation
+            .iter()
+            .map(|(zone, _)| zone.clone())
+            .collect_vec())
+    });
+    if found < zones.len() {
+        errors.push(CensusError::Join(format!(
+            "geometries not found for {} of the {} geoids of the zone mapping",
+            zones.len() - found,
+            zones.len()
+        )));
+    }
+    (dissolved, errors)
+}
+
+/// dissolves geometries into groups, where each GEOID belongs to zero or more groups.
+/// the polygons of each group are unioned in parallel. GEOIDs without a group are
+/// ignored, and GEOIDs that fail to group or are not polygonal are reported as errors.
+/// results are sorted by group key.
+pub fn dissolve_by<K, G>(
+    geometries: Vec<(Geoid, Geometry)>,
+    group: G,
+) -> (Vec<(K, Geometry)>, Vec<CensusError>)
+where
+    K: Ord + Hash + Clone + Send,
+    G: Fn(&Geoid) -> Result<Vec<K>, CensusError>,
+{
+    let mut members: HashMap<K, Vec<MultiPolygon>> = HashMap::new();
+    let mut errors = vec![];
+    for (geoid, geometry) in geometries.into_iter() {
+        let keys = match group(&geoid) {
+            Ok(keys) if keys.is_empty() => continue,
+            Ok(keys) => keys,
+            Err(e) => {
+                errors.push(e);
+                continue;
+            }
+        };
+        match to_multi_polygon(geometry) {
+            Ok(polygons) => {
+                for key in keys.into_iter() {
+                    members.entry(key).or_default().push(polygons.clone());
+                }
+            }
+            Err(e) => errors.push(CensusError::Join(format!(
+                "cannot dissolve geometry of geoid {}: {}",
+                geoid, e
+            ))),
+        }
+    }
+
+    let mut dissolved = members
+        .into_par_iter()
+        .map(|(key, polygons)| (key, Geometry::from(union_all(polygons))))
+        .collect::<Vec<_>>();
+    dissolved.par_sort_by(|(a, _), (b, _)| a.cmp(b));
+    (dissolved, errors)
+}
+
+fn to_multi_polygon(geometry: Geometry) -> Result<MultiPolygon, String> {
+    match geometry {
+        Geometry::Polygon(p) => Ok(MultiPolygon::new(vec![p])),
+        Geometry::MultiPolygon(mp) => Ok(mp),
+        _ => Err(String::from("expected a polygon or multipolygon")),
+    }
+}
+
+/// unions polygons pairwise, which keeps the intermediate unions small.
+fn union_all(mut polygons: Vec<MultiPolygon>) -> MultiPolygon {
+    while polygons.len() > 1 {
+        polygons = polygons
+            .chunks(2)
+            .map(|pair| match pair {
+                [a, b] => a.union(b),
+                _ => pair[0].clone(),
+            })
+            .collect_vec();
+    }
+    polygons.pop().unwrap_or_else(|| MultiPolygon::new(vec![]))
+}
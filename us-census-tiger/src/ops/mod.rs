@@ -1 +1,3 @@
+pub mod dissolve;
+pub mod relationship_api;
 pub mod tiger_api;
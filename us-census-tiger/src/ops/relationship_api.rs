@@ -0,0 +1,87 @@
+use crate::ops::tiger_api::{download, get_zip_filename, zip_file_into_string};
+use reqwest::Client;
+use std::fs::File;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use us_census_core::error::CensusError;
+use us_census_core::model::crosswalk::{RelationshipFile, RelationshipRecord};
+use zip::ZipArchive;
+
+/// downloads a 2010 to 2020 relationship file into a local directory, unpacking
+/// block relationship files from their .zip archive. if the file is already present
+/// in the directory, it is not downloaded again. returns the path to the text file.
+pub async fn download_relationship_file(
+    client: &Client,
+    file: &RelationshipFile,
+    directory: &Path,
+) -> Result<PathBuf, CensusError> {
+    let path = directory.join(file.filename());
+    if path.exists() {
+        log::debug!("using existing relationship file {}", path.display());
+        return Ok(path);
+    }
+    std::fs::create_dir_all(directory).map_err(|e| CensusError::Archive {
+        message: format!("failure creating directory {}", directory.display()),
+        source: Box::new(e),
+    })?;
+
+    let uri = file.uri();
+    log::debug!("downloading {}", uri);
+    // download into a temporary file so that an interrupted download is never
+    // mistaken for a complete relationship file
+    let named_tmp =
+        tempfile::NamedTempFile::new_in(directory).map_err(|e| CensusError::Archive {
+            message: String::from("failure creating temporary relationship file"),
+            source: Box::new(e),
+        })?;
+    let write_file = named_tmp.reopen().map_err(|e| CensusError::Archive {
+        message: String::from("failure opening temporary relationship file"),
+        source: Box::new(e),
+    })?;
+    download(client, &uri, write_file).await?;
+
+    if file.is_archive() {
+        let read_file = File::open(named_tmp.path()).map_err(|e| CensusError::Archive {
+            message: String::from("failure opening relationship file archive"),
+            source: Box::new(e),
+        })?;
+        let mut z = ZipArchive::new(read_file).map_err(|e| CensusError::Archive {
+            message: String::from("failure reading relationship file archive"),
+            source: Box::new(e),
+        })?;
+        let txt_filename = get_zip_filename(&z, ".txt")?;
+        let contents = zip_file_into_string(&mut z, &txt_filename)?;
+        let mut extracted =
+            tempfile::NamedTempFile::new_in(directory).map_err(|e| CensusError::Archive {
+                message: String::from("failure creating temporary relationship file"),
+                source: Box::new(e),
+            })?;
+        extracted
+            .write_all(&contents)
+            .map_err(|e| CensusError::Archive {
+                message: format!("failure unpacking {} from archive", txt_filename),
+                source: Box::new(e),
+            })?;
+        extracted.persist(&path).map_err(|e| CensusError::Archive {
+            message: format!("failure writing relationship file {}", path.display()),
+            source: Box::new(e),
+        })?;
+    } else {
+        named_tmp.persist(&path).map_err(|e| CensusError::Archive {
+            message: format!("failure writing relationship file {}", path.display()),
+            source: Box::new(e),
+        })?;
+    }
+    Ok(path)
+}
+
+/// loads the records of a 2010 to 2020 relationship file, downloading it into the
+/// local directory first if it is not already present.
+pub async fn load_relationship_records(
+    client: &Client,
+    file: &RelationshipFile,
+    directory: &Path,
+) -> Result<Vec<RelationshipRecord>, CensusError> {
+    let path = download_relationship_file(client, file, directory).await?;
+    file.read_path(&path)
+}
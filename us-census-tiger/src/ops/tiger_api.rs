@@ -8,13 +8,13 @@ use log;
 use reqwest::Client;
 use shapefile::dbase::Record;
 use shapefile::{dbase, Shape, ShapeReader};
-use std::collections::HashSet;
 use std::fs::File;
 use std::io::{Cursor, Read};
 use std::sync::{Arc, Mutex};
 use tokio::io::AsyncWriteExt;
+use us_census_core::error::CensusError;
 use us_census_core::model::identifier::geoid::Geoid;
-use us_census_core::model::identifier::GeoidType;
+use us_census_core::model::identifier::{GeoidSet, GeoidType, PackedGeoid};
 use zip::ZipArchive;
 
 /// runs as many downloads of TIGER/Lines files as needed to cover
@@ -24,44 +24,77 @@ use zip::ZipArchive;
 /// site. the archives are Shapefile archives, and there isn't great
 /// tooling to stream these data sources, so here we chose to download
 /// the archives, unpack, and then load from the extracted file paths.
-pub async fn run<'a>(
+pub async fn run(
     client: &Client,
     builder: &TigerUriBuilder,
     geoids: &[&Geoid],
-) -> Result<Vec<Result<Vec<(Geoid, Geometry)>, String>>, String> {
+) -> Result<Vec<Result<Vec<(Geoid, Geometry)>, CensusError>>, CensusError> {
     let uris = builder.create_uris(geoids)?;
-    let lookup = geoids.iter().collect::<HashSet<_>>();
+    let lookup = geoids
+        .iter()
+        .map(|g| PackedGeoid::try_from(*g))
+        .collect::<Result<GeoidSet, _>>()?;
+    run_uris(client, uris, &|geoid| lookup.contains(geoid)).await
+}
+
+/// runs the downloads of the TIGER/Lines files of the target geoids, like [`run`], but
+/// returns all rows contained in the parent geoids instead of only the target geoids,
+/// such as all blocks of a set of census tracts. the files are chosen from the target
+/// geoids, so each parent should contain at least one target geoid.
+pub async fn run_within(
+    client: &Client,
+    builder: &TigerUriBuilder,
+    geoids: &[&Geoid],
+    parents: &GeoidSet,
+) -> Result<Vec<Result<Vec<(Geoid, Geometry)>, CensusError>>, CensusError> {
+    let uris = builder.create_uris(geoids)?;
+    run_uris(client, uris, &|geoid| parents.covers(geoid)).await
+}
 
+/// downloads each TIGER/Lines file, keeping the rows with a GEOID accepted by `keep`.
+async fn run_uris(
+    client: &Client,
+    uris: Vec<TigerResource>,
+    keep: &(dyn Fn(&Geoid) -> bool + Sync),
+) -> Result<Vec<Result<Vec<(Geoid, Geometry)>, CensusError>>, CensusError> {
     let pb_builder = kdam::BarBuilder::default()
         .total(uris.len())
         .desc("TIGER/Lines downloads");
-    let pb = Arc::new(Mutex::new(pb_builder.build()?));
+    let pb = Arc::new(Mutex::new(pb_builder.build().map_err(|e| {
+        CensusError::Internal(format!("error building progress bar: {}", e))
+    })?));
 
     let run_results = uris
         .into_iter()
         .map(|tiger| {
             log::debug!("downloading {}", tiger.uri);
             let client = &client;
-            let lookup = &lookup;
             let pb = pb.clone();
             async move {
                 // create temporary file for writing .zip download
-                let named_tmp = tempfile::NamedTempFile::new().map_err(|e| {
-                    format!("failure creating temporary zip archive filepath: {}", e)
-                })?;
+                let named_tmp =
+                    tempfile::NamedTempFile::new().map_err(|e| CensusError::Archive {
+                        message: String::from("failure creating temporary zip archive filepath"),
+                        source: Box::new(e),
+                    })?;
                 let read_path = named_tmp.path().to_path_buf().clone();
 
                 // download archive
-                let write_file = File::create(&read_path)
-                    .map_err(|e| format!("failure creating temporary zip archive file: {}", e))?;
+                let write_file = File::create(&read_path).map_err(|e| CensusError::Archive {
+                    message: String::from("failure creating temporary zip archive file"),
+                    source: Box::new(e),
+                })?;
                 download(client, &tiger.uri, write_file).await?;
 
                 // unpack archive
-                let read_file = File::open(&read_path).map_err(|e| {
-                    format!("failure opening temporary zip archive file location: {}", e)
+                let read_file = File::open(&read_path).map_err(|e| CensusError::Archive {
+                    message: String::from("failure opening temporary zip archive file location"),
+                    source: Box::new(e),
+                })?;
+                let mut z = ZipArchive::new(read_file).map_err(|e| CensusError::Archive {
+                    message: String::from("failure reading temporary zip archive"),
+                    source: Box::new(e),
                 })?;
-                let mut z = ZipArchive::new(read_file)
-                    .map_err(|e| format!("failure reading temporary zip archive: {}", e))?;
                 let shp_filename = get_zip_filename(&z, ".shp")?;
                 let dbf_filename = get_zip_filename(&z, ".dbf")?;
                 let shp_contents = zip_file_into_string(&mut z, &shp_filename)?;
@@ -72,22 +105,26 @@ pub async fn run<'a>(
                 let read_result = reader
                     .iter_shapes_and_records()
                     .map(|row| {
-                        let (shape, record) = row.map_err(|e| {
-                            format!("failure reading shapefile shape/record: {}", e)
+                        let (shape, record) = row.map_err(|e| CensusError::Shapefile {
+                            message: String::from("failure reading shapefile shape/record"),
+                            source: Box::new(e),
                         })?;
-                        into_geoid_and_geometry(shape, record, lookup, &tiger)
+                        into_geoid_and_geometry(shape, record, keep, &tiger)
                     })
-                    .collect::<Result<Vec<_>, String>>()?;
+                    .collect::<Result<Vec<_>, CensusError>>()?;
                 let result = read_result.into_iter().flatten().collect_vec();
 
                 // update progress bar
-                let mut pb_update = pb
-                    .lock()
-                    .map_err(|e| format!("failure aquiring progress bar mutex lock: {}", e))?;
+                let mut pb_update = pb.lock().map_err(|e| {
+                    CensusError::Internal(format!(
+                        "failure aquiring progress bar mutex lock: {}",
+                        e
+                    ))
+                })?;
                 pb_update
                     .update(1)
-                    .map_err(|e| format!("failure on pb update: {}", e))?;
-                pb_update.set_description(tiger.uri.split('/').last().unwrap_or_default());
+                    .map_err(|e| CensusError::Internal(format!("failure on pb update: {}", e)))?;
+                pb_update.set_description(tiger.uri.split('/').next_back().unwrap_or_default());
 
                 Ok(result)
             }
@@ -101,14 +138,16 @@ pub async fn run<'a>(
 fn into_geoid_and_geometry(
     shape: Shape,
     record: Record,
-    lookup: &HashSet<&&Geoid>,
+    keep: &(dyn Fn(&Geoid) -> bool + Sync),
     tiger_uri: &TigerResource,
-) -> Result<Option<(Geoid, Geometry)>, String> {
+) -> Result<Option<(Geoid, Geometry)>, CensusError> {
     let geoid = get_geoid_from_record(&record, &tiger_uri.geoid_type)?;
-    if lookup.contains(&&geoid) {
-        let geometry: Geometry<f64> = shape
-            .try_into()
-            .map_err(|e| format!("could not convert shape into geometry. {}", e))?;
+    if keep(&geoid) {
+        let geometry: Geometry<f64> =
+            shape.try_into().map_err(|e: &str| CensusError::Shapefile {
+                message: String::from("could not convert shape into geometry"),
+                source: e.into(),
+            })?;
         Ok(Some((geoid, geometry)))
     } else {
         Ok(None)
@@ -121,100 +160,135 @@ const GEOID_COLUMN_NAMES: [&str; 3] = ["GEOID", "GEOID20", "GEOID10"];
 /// 1. "GEOID"   - most general
 /// 2. "GEOID20" - latest
 /// 3. "GEOID10" - when general or latest is not present
-fn get_geoid_from_record(record: &Record, geoid_type: &GeoidType) -> Result<Geoid, String> {
+fn get_geoid_from_record(record: &Record, geoid_type: &GeoidType) -> Result<Geoid, CensusError> {
     let field_name = GEOID_COLUMN_NAMES
         .iter()
         .find(|col| record.get(col).is_some())
         .ok_or_else(|| {
-            format!(
+            CensusError::ResponseSchema(format!(
                 "could not find any of {} in shapefile",
                 GEOID_COLUMN_NAMES.iter().join(","),
-            )
+            ))
         })?;
     let field_value = record.get(field_name).ok_or_else(|| {
-        format!(
+        CensusError::ResponseSchema(format!(
             "could not find any of {} in shapefile",
             GEOID_COLUMN_NAMES.iter().join(","),
-        )
+        ))
     })?;
     let geoid = match field_value {
         dbase::FieldValue::Character(s) => match s {
             Some(geoid_string) => geoid_type.geoid_from_str(geoid_string),
-            None => Err(format!(
+            None => Err(CensusError::ResponseSchema(format!(
                 "value at Geoid field '{}' is empty, should be a GEOID string",
                 field_name
-            )),
+            ))),
         },
-        _ => Err(format!(
+        _ => Err(CensusError::ResponseSchema(format!(
             "value at column '{}' is not valid GEOID, found '{}'",
             field_name, field_value
-        )),
+        ))),
     }?;
     Ok(geoid)
 }
 
-async fn download(client: &Client, uri: &str, write_file: File) -> Result<(), String> {
+pub(crate) async fn download(
+    client: &Client,
+    uri: &str,
+    write_file: File,
+) -> Result<(), CensusError> {
     let mut async_file = tokio::fs::File::from(write_file);
 
-    let mut response = client
+    let response = client
         .get(uri)
         .send()
         .await
-        .map_err(|e| format!("failure retrieving TIGER zip archive: {}", e))?
-        .bytes_stream();
+        .map_err(|e| CensusError::Http {
+            url: uri.to_string(),
+            source: Box::new(e),
+        })?;
+    if !response.status().is_success() {
+        return Err(CensusError::HttpStatus {
+            url: uri.to_string(),
+            status: response.status().as_u16(),
+        });
+    }
+    let mut response = response.bytes_stream();
 
     while let Some(buf) = response.next().await {
-        let item = buf.map_err(|e| format!("failed to buffer response: {}", e))?;
+        let item = buf.map_err(|e| CensusError::Http {
+            url: uri.to_string(),
+            source: Box::new(e),
+        })?;
         tokio::io::copy(&mut item.as_ref(), &mut async_file)
             .await
-            .map_err(|e| format!("failed to write response buffer: {}", e))?;
+            .map_err(|e| CensusError::Archive {
+                message: String::from("failed to write response buffer"),
+                source: Box::new(e),
+            })?;
     }
 
-    async_file.flush().await.map_err(|e| {
-        format!(
-            "error closing async write connection to temp zip file: {}",
-            e
-        )
+    async_file.flush().await.map_err(|e| CensusError::Archive {
+        message: String::from("error closing async write connection to temp zip file"),
+        source: Box::new(e),
     })?;
     Ok(())
 }
 
-fn get_zip_filename(archive: &ZipArchive<File>, suffix: &str) -> Result<String, String> {
+pub(crate) fn get_zip_filename(
+    archive: &ZipArchive<File>,
+    suffix: &str,
+) -> Result<String, CensusError> {
     let shp_filename = archive
         .file_names()
         .find(|s| s.ends_with(suffix))
-        .ok_or_else(|| format!("no files in archive have '{}' suffix", suffix))?;
+        .ok_or_else(|| {
+            CensusError::ResponseSchema(format!("no files in archive have '{}' suffix", suffix))
+        })?;
     Ok(String::from(shp_filename))
 }
 
-fn zip_file_into_string(archive: &mut ZipArchive<File>, filename: &str) -> Result<Vec<u8>, String> {
+pub(crate) fn zip_file_into_string(
+    archive: &mut ZipArchive<File>,
+    filename: &str,
+) -> Result<Vec<u8>, CensusError> {
     let mut contents = Vec::new();
-    let mut zipfile = archive.by_name(filename).map_err(|e| {
-        format!(
-            "expected file {} cannot be retrieved by name from zip archive: {}",
-            filename, e
-        )
-    })?;
+    let mut zipfile = archive
+        .by_name(filename)
+        .map_err(|e| CensusError::Archive {
+            message: format!(
+                "expected file {} cannot be retrieved by name from zip archive",
+                filename
+            ),
+            source: Box::new(e),
+        })?;
     zipfile
         .read_to_end(&mut contents)
-        .map_err(|e| format!("failure reading {} from zip archive: {}", filename, e))?;
+        .map_err(|e| CensusError::Archive {
+            message: format!("failure reading {} from zip archive", filename),
+            source: Box::new(e),
+        })?;
     // let string =
     //     String::from_utf8(contents).map_err(|e| format!("failure parsing zip as utf-8: {}", e))?;
     Ok(contents)
 }
 
 type TigerShapefileReader<'a> =
-    Result<shapefile::Reader<Cursor<&'a Vec<u8>>, Cursor<&'a Vec<u8>>>, String>;
+    Result<shapefile::Reader<Cursor<&'a Vec<u8>>, Cursor<&'a Vec<u8>>>, CensusError>;
 fn create_shapefile_reader<'a>(
     shp_contents: &'a Vec<u8>,
     dbf_contents: &'a Vec<u8>,
 ) -> TigerShapefileReader<'a> {
     let shp_cursor = Cursor::new(shp_contents);
     let dbf_cursor = Cursor::new(dbf_contents);
-    let shape_reader = ShapeReader::new(shp_cursor)
-        .map_err(|e| format!("failure building shape reader: {}", e))?;
-    let database_reader = dbase::Reader::new(dbf_cursor)
-        .map_err(|e| format!("failure building dbf reader: {}", e))?;
+    let shape_reader = ShapeReader::new(shp_cursor).map_err(|e| CensusError::Shapefile {
+        message: String::from("failure building shape reader"),
+        source: Box::new(e),
+    })?;
+    let database_reader = dbase::Reader::new(dbf_cursor).map_err(|e| CensusError::Shapefile {
+        message: String::from("failure building dbf reader"),
+        source: Box::new(e),
+    })?;
     let reader: shapefile::Reader<Cursor<&Vec<u8>>, Cursor<&Vec<u8>>> =
         shapefile::Reader::new(shape_reader, database_reader);
     Ok(reader)